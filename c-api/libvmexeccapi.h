@@ -18,6 +18,123 @@ typedef enum {
   VM_EXEC_ERROR = 2,
 } vm_exec_result_t;
 
+/**
+ * The reason a call did not complete.
+ */
+typedef enum {
+  /**
+   * The call completed.
+   */
+  VM_EXEC_TRAP_NONE = 0,
+  /**
+   * The call was stopped by the host, e.g. by a failing VM hook.
+   */
+  VM_EXEC_TRAP_HOST = 1,
+  /**
+   * Also used by breakpoints, see `breakpoint_value` for the reason.
+   */
+  VM_EXEC_TRAP_UNREACHABLE = 2,
+  VM_EXEC_TRAP_MEMORY_OUT_OF_BOUNDS = 3,
+  VM_EXEC_TRAP_TABLE_OUT_OF_BOUNDS = 4,
+  VM_EXEC_TRAP_INTEGER_OVERFLOW = 5,
+  VM_EXEC_TRAP_INTEGER_DIVISION_BY_ZERO = 6,
+  VM_EXEC_TRAP_INVALID_CONVERSION_TO_INTEGER = 7,
+  VM_EXEC_TRAP_STACK_OVERFLOW = 8,
+  VM_EXEC_TRAP_INDIRECT_CALL_TO_NULL = 9,
+  VM_EXEC_TRAP_INDIRECT_CALL_SIGNATURE_MISMATCH = 10,
+  VM_EXEC_TRAP_OTHER = 11,
+} vm_exec_trap_code_t;
+
+/**
+ * Identifies the kind of the last error.
+ *
+ * The values are stable, new kinds of errors only ever get new values.
+ */
+typedef enum {
+  /**
+   * No error occurred yet.
+   */
+  VM_EXEC_ERROR_CODE_NONE = 0,
+  /**
+   * Any error of the underlying runtime not covered below.
+   */
+  VM_EXEC_ERROR_CODE_RUNTIME = 1,
+  /**
+   * The executor service rejected the operation.
+   */
+  VM_EXEC_ERROR_CODE_SERVICE = 2,
+  /**
+   * A pointer given to the C API was null.
+   */
+  VM_EXEC_ERROR_CODE_NULL_POINTER = 3,
+  /**
+   * An argument given to the C API was invalid.
+   */
+  VM_EXEC_ERROR_CODE_INVALID_ARGUMENT = 4,
+  /**
+   * The contract bytes could not be parsed.
+   */
+  VM_EXEC_ERROR_CODE_INVALID_MODULE = 10,
+  VM_EXEC_ERROR_CODE_COMPILATION = 11,
+  VM_EXEC_ERROR_CODE_NO_MEMORY = 20,
+  VM_EXEC_ERROR_CODE_MULTIPLE_MEMORIES = 21,
+  VM_EXEC_ERROR_CODE_MEMORY_TOO_LARGE = 22,
+  VM_EXEC_ERROR_CODE_TOO_MANY_LOCALS = 23,
+  VM_EXEC_ERROR_CODE_UNSUPPORTED_OPERATOR = 24,
+  VM_EXEC_ERROR_CODE_INVALID_IMPORTS = 25,
+  VM_EXEC_ERROR_CODE_RESERVED_EXPORT_NAME = 26,
+  VM_EXEC_ERROR_CODE_INSTANTIATION = 30,
+  /**
+   * A cached module was rejected.
+   */
+  VM_EXEC_ERROR_CODE_CACHE = 31,
+  /**
+   * An instance snapshot was rejected.
+   */
+  VM_EXEC_ERROR_CODE_SNAPSHOT = 32,
+  /**
+   * The execution was interrupted, see `vm_exec_instance_get_breakpoint_value` for the reason.
+   */
+  VM_EXEC_ERROR_CODE_TRAP = 40,
+  VM_EXEC_ERROR_CODE_MISSING_EXPORT = 50,
+  /**
+   * The arguments of a typed call do not match the function signature.
+   */
+  VM_EXEC_ERROR_CODE_SIGNATURE_MISMATCH = 51,
+  VM_EXEC_ERROR_CODE_MEMORY_OUT_OF_BOUNDS = 60,
+  VM_EXEC_ERROR_CODE_MEMORY_GROW_LIMIT = 61,
+  VM_EXEC_ERROR_CODE_METERING_DISABLED = 70,
+  VM_EXEC_ERROR_CODE_RUNTIME_BREAKPOINTS_DISABLED = 71,
+  VM_EXEC_ERROR_CODE_UNKNOWN_BREAKPOINT_VALUE = 72,
+  /**
+   * A gas schedule was rejected, or the requested one is not registered.
+   */
+  VM_EXEC_ERROR_CODE_GAS_SCHEDULE = 73,
+  VM_EXEC_ERROR_CODE_GAS_PROFILING_DISABLED = 74,
+  VM_EXEC_ERROR_CODE_OPCODE_HISTOGRAM_DISABLED = 75,
+  /**
+   * The opcode trace could not be written to its sink.
+   */
+  VM_EXEC_ERROR_CODE_OPCODE_TRACE = 76,
+  VM_EXEC_ERROR_CODE_EXECUTION_TRACE_DISABLED = 77,
+  VM_EXEC_ERROR_CODE_SNAPSHOTS_DISABLED = 78,
+} vm_exec_error_code_t;
+
+/**
+ * The lists of names that can be read from a `vm_exec_module_info_t`.
+ */
+typedef enum {
+  /**
+   * Entries look like `env.getNumArguments:function:[] -> [I32]`.
+   */
+  VM_EXEC_MODULE_IMPORTS = 1,
+  /**
+   * Entries look like `init:function`.
+   */
+  VM_EXEC_MODULE_EXPORTS = 2,
+  VM_EXEC_MODULE_CUSTOM_SECTIONS = 3,
+} vm_exec_module_info_list_t;
+
 /**
  * Opaque pointer to a `wasmer_runtime::Instance` value in Rust.
  *
@@ -33,8 +150,34 @@ typedef struct {
 
 } vm_exec_executor_t;
 
+/**
+ * The compilation options of a contract, as laid out by C callers.
+ *
+ * The caller sets `struct_size` to `sizeof(vm_exec_compilation_options_t)`, so that options
+ * laid out against another version of this header are rejected instead of misread. The flags
+ * are 0 or 1, any other value is rejected as an invalid argument.
+ *
+ * The `compiler` is 0 for Singlepass and 1 for Cranelift, any other value fails the
+ * compilation with an invalid argument. With `execution_trace`, the `execution_trace_length`
+ * must be from 1 to 128.
+ */
 typedef struct {
-
+  uint32_t struct_size;
+  uint64_t gas_limit;
+  uintptr_t unmetered_locals;
+  uintptr_t max_memory_grow;
+  uintptr_t max_memory_grow_delta;
+  uint8_t opcode_trace;
+  uint8_t metering;
+  uint8_t runtime_breakpoints;
+  uint8_t compiler;
+  uint64_t max_memory_pages;
+  uint32_t gas_schedule_version;
+  uint8_t gas_profiling;
+  uint8_t opcode_histogram;
+  uint8_t execution_trace;
+  uint8_t snapshots;
+  uint32_t execution_trace_length;
 } vm_exec_compilation_options_t;
 
 typedef struct {
@@ -284,6 +427,59 @@ typedef struct {
 
 } vm_exec_opcode_cost_t;
 
+/**
+ * Opaque pointer to the costs of the bulk memory operators.
+ *
+ * The caller lays out 7 `uint32_t` fields, in this order: data_drop, memory_copy,
+ * memory_copy_per_byte, memory_fill, memory_fill_per_byte, memory_init and
+ * memory_init_per_byte. Further costs will come with a new version of the struct and of the
+ * functions that take it.
+ */
+typedef struct {
+
+} vm_exec_bulk_memory_cost_v1_t;
+
+typedef struct {
+
+} vm_exec_module_info_t;
+
+/**
+ * The scalar facts about a contract.
+ *
+ * The memory fields describe the first memory of the contract, they are zero if it has none.
+ */
+typedef struct {
+  uint32_t import_count;
+  uint32_t export_count;
+  uint32_t function_count;
+  uint32_t memory_count;
+  uint64_t memory_min_pages;
+  uint64_t memory_max_pages;
+  bool memory_has_max;
+  uint64_t max_memory_pages_allowed;
+  bool memory_within_limits;
+  uint32_t table_count;
+  uint32_t data_segment_count;
+  uint64_t data_segment_bytes;
+  bool has_start_function;
+  uint32_t custom_section_count;
+} vm_exec_module_summary_t;
+
+/**
+ * How a call went.
+ */
+typedef struct {
+  bool success;
+  uint64_t breakpoint_value;
+  uint64_t points_used_before;
+  uint64_t points_used_after;
+  vm_exec_trap_code_t trap_code;
+  /**
+   * The buffer length needed for the backtrace, zero if there is none.
+   */
+  int backtrace_length;
+} vm_exec_call_report_t;
+
 /**
  * Checks that all public module functions (SC endpoints) have no arguments or results.
  *
@@ -304,6 +500,93 @@ vm_exec_result_t vm_check_signatures(vm_exec_instance_t *instance_ptr);
  */
 void vm_exec_executor_destroy(vm_exec_executor_t *executor_ptr);
 
+/**
+ * Returns the version of the registered gas schedule active at the given epoch.
+ *
+ * Returns the default version, 0, if no registered schedule is active yet.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+uint32_t vm_exec_executor_gas_schedule_version_at_epoch(const vm_exec_executor_t *executor_ptr,
+                                                        uint64_t epoch);
+
+/**
+ * Gathers facts about a contract, without compiling or instantiating it.
+ *
+ * The memory of the contract is checked against `max_memory_pages`, the limit set in the
+ * compilation options it is going to be instantiated with.
+ *
+ * The result must be freed with `vm_exec_module_info_destroy`.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+vm_exec_result_t vm_exec_executor_inspect_module(vm_exec_executor_t *executor_ptr,
+                                                 uint8_t *wasm_bytes_ptr,
+                                                 uint32_t wasm_bytes_len,
+                                                 uint64_t max_memory_pages,
+                                                 vm_exec_module_info_t **module_info_ptr_ptr);
+
+/**
+ * Registers opcode costs under a new gas schedule version, active from the given epoch on.
+ *
+ * Contracts are compiled with a registered schedule by setting its version in the
+ * compilation options. Contracts that use bulk memory operators are rejected under this
+ * schedule, see `vm_exec_executor_register_gas_schedule_with_bulk_memory_v1`.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+vm_exec_result_t vm_exec_executor_register_gas_schedule(vm_exec_executor_t *executor_ptr,
+                                                        const char *name_ptr,
+                                                        uint32_t version,
+                                                        uint64_t activation_epoch,
+                                                        const vm_exec_opcode_cost_t *opcode_cost_ptr);
+
+/**
+ * Registers opcode costs and bulk memory operator costs under a new gas schedule version,
+ * active from the given epoch on.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+vm_exec_result_t vm_exec_executor_register_gas_schedule_with_bulk_memory_v1(vm_exec_executor_t *executor_ptr,
+                                                                           const char *name_ptr,
+                                                                           uint32_t version,
+                                                                           uint64_t activation_epoch,
+                                                                           const vm_exec_opcode_cost_t *opcode_cost_ptr,
+                                                                           const vm_exec_bulk_memory_cost_v1_t *bulk_memory_cost_ptr);
+
+/**
+ * Sets the maximum number of compiled modules kept in memory by the executor.
+ *
+ * A capacity of zero disables the cache.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+vm_exec_result_t vm_exec_executor_set_module_cache_capacity(vm_exec_executor_t *executor_ptr,
+                                                            uint64_t capacity);
+
+/**
+ * Sets the file the contracts compiled with `opcode_trace` are traced to, one JSON entry per line.
+ *
+ * If `path_ptr` is null, the trace is kept by each instance instead, which is the default,
+ * see `vm_exec_instance_opcode_trace`.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+vm_exec_result_t vm_exec_executor_set_opcode_trace_file(vm_exec_executor_t *executor_ptr,
+                                                        const char *path_ptr);
+
 /**
  * Sets the data that can be hold by an instance context.
  *
@@ -321,6 +604,21 @@ void vm_exec_executor_destroy(vm_exec_executor_t *executor_ptr);
 vm_exec_result_t vm_exec_executor_set_vm_hooks_ptr(vm_exec_executor_t *executor_ptr,
                                                    void *vm_hooks_ptr);
 
+/**
+ * Checks the imports of a contract against the VM hooks, without instantiating it.
+ *
+ * This function returns `vm_exec_result_t::VM_EXEC_ERROR` if the contract cannot be parsed or
+ * has imports that the VM hooks do not provide. All offending imports are then listed in the
+ * last error message.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+vm_exec_result_t vm_exec_executor_validate_imports(vm_exec_executor_t *executor_ptr,
+                                                   uint8_t *wasm_bytes_ptr,
+                                                   uint32_t wasm_bytes_len);
+
 /**
  * Caches an instance.
  *
@@ -353,6 +651,28 @@ vm_exec_result_t vm_exec_instance_cache(const vm_exec_instance_t *instance_ptr,
  */
 vm_exec_result_t vm_exec_instance_call(vm_exec_instance_t *instance_ptr, const char *func_name_ptr);
 
+/**
+ * Calls an exported function of a WebAssembly instance by `name`, and fills in
+ * a report of how the call went.
+ *
+ * A trap does not make this function fail: the report describes it, and the trap message is
+ * also available via `vm_exec_last_error_message`. This function returns
+ * `vm_exec_result_t::VM_EXEC_ERROR` only if the call could not be made, e.g. because
+ * the function does not exist.
+ *
+ * The backtrace frames are separated by pipes. They are copied into `backtrace_buffer` if it
+ * is not null and at least `backtrace_length` bytes long, otherwise they are skipped.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+vm_exec_result_t vm_exec_instance_call_with_report(vm_exec_instance_t *instance_ptr,
+                                                   const char *func_name_ptr,
+                                                   vm_exec_call_report_t *report_ptr,
+                                                   char *backtrace_buffer,
+                                                   int backtrace_buffer_len);
+
 /**
  * Frees memory for the given `vm_exec_instance_t`.
  *
@@ -382,6 +702,42 @@ vm_exec_result_t vm_exec_instance_from_cache(vm_exec_executor_t *executor_ptr,
                                              uint32_t cache_bytes_len,
                                              const vm_exec_compilation_options_t *options_ptr);
 
+/**
+ * Returns the gas profile of the given instance, one entry per function defined by the contract,
+ * separated by pipes.
+ *
+ * Entries look like `3:transfer:1200:2`, for the function index, the function name,
+ * the gas used and the call count. The name is empty if the contract has no name section.
+ *
+ * It is necessary to first call `vm_exec_instance_gas_profile_length` and pre-allocate a buffer of this length.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+int vm_exec_instance_gas_profile(const vm_exec_instance_t *instance_ptr,
+                                 char *dest_buffer,
+                                 int dest_buffer_len);
+
+/**
+ * Returns the buffer length needed by `vm_exec_instance_gas_profile`, `-1` if the profile
+ * cannot be read, e.g. because gas profiling is disabled for the given instance.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+int vm_exec_instance_gas_profile_length(const vm_exec_instance_t *instance_ptr);
+
+/**
+ * Returns the version of the gas schedule the given instance was compiled with.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+uint32_t vm_exec_instance_gas_schedule_version(const vm_exec_instance_t *instance_ptr);
+
 /**
  * Returns the runtime breakpoint value from the given instance.
  *
@@ -409,6 +765,46 @@ uint64_t vm_exec_instance_get_points_used(const vm_exec_instance_t *instance_ptr
  */
 int vm_exec_instance_has_function(vm_exec_instance_t *instance_ptr, const char *func_name_ptr);
 
+/**
+ * Returns the last basic blocks entered by the last call of the given instance, oldest first,
+ * separated by pipes. It is empty unless that call trapped or stopped on a breakpoint.
+ *
+ * Entries look like `3:12`, the function index followed by the index of the first operator
+ * of the block.
+ *
+ * It is necessary to first call `vm_exec_instance_last_execution_trace_length` and pre-allocate
+ * a buffer of this length.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+int vm_exec_instance_last_execution_trace(const vm_exec_instance_t *instance_ptr,
+                                          char *dest_buffer,
+                                          int dest_buffer_len);
+
+/**
+ * Returns the buffer length needed by `vm_exec_instance_last_execution_trace`, `-1` if the trace
+ * cannot be read, e.g. because it is disabled for the given instance.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+int vm_exec_instance_last_execution_trace_length(const vm_exec_instance_t *instance_ptr);
+
+/**
+ * Records that the caller wrote `mem_length` bytes at `mem_ptr`, through the pointer given by
+ * `vm_exec_instance_memory_data`, so that `vm_exec_instance_reset_changed_pages` restores them.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+vm_exec_result_t vm_exec_instance_mark_memory_written(vm_exec_instance_t *instance_ptr,
+                                                      uint32_t mem_ptr,
+                                                      uint32_t mem_length);
+
 /**
  * Gets a pointer to the beginning of the contiguous memory data
  * bytes.
@@ -418,6 +814,8 @@ int vm_exec_instance_has_function(vm_exec_instance_t *instance_ptr, const char *
  * Note that when the memory grows, it can be reallocated, and thus
  * the returned pointer can be invalidated.
  *
+ * Writes through the pointer must be reported with `vm_exec_instance_mark_memory_written`.
+ *
  * # Safety
  *
  * C API function, works with raw object pointers.
@@ -450,6 +848,56 @@ uint64_t vm_exec_instance_memory_data_length(vm_exec_instance_t *instance_ptr);
 vm_exec_result_t vm_exec_instance_memory_grow(vm_exec_instance_t *instance_ptr,
                                               uint32_t by_num_pages);
 
+/**
+ * Returns the number of executed operators of the given instance by class, separated by pipes.
+ *
+ * Entries look like `load:120`. The classes that were never executed are left out.
+ *
+ * It is necessary to first call `vm_exec_instance_opcode_histogram_length` and pre-allocate a buffer of this length.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+int vm_exec_instance_opcode_histogram(const vm_exec_instance_t *instance_ptr,
+                                      char *dest_buffer,
+                                      int dest_buffer_len);
+
+/**
+ * Returns the buffer length needed by `vm_exec_instance_opcode_histogram`, `-1` if the histogram
+ * cannot be read, e.g. because it is disabled for the given instance.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+int vm_exec_instance_opcode_histogram_length(const vm_exec_instance_t *instance_ptr);
+
+/**
+ * Returns the opcode trace kept by the instance, one JSON entry per line.
+ *
+ * The trace is only kept if the executor traces to memory, see
+ * `vm_exec_executor_set_opcode_trace_file`, it is empty otherwise.
+ *
+ * It is necessary to first call `vm_exec_instance_opcode_trace_length` and pre-allocate a buffer of this length.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+int vm_exec_instance_opcode_trace(const vm_exec_instance_t *instance_ptr,
+                                  char *dest_buffer,
+                                  int dest_buffer_len);
+
+/**
+ * Returns the buffer length needed by `vm_exec_instance_opcode_trace`.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+int vm_exec_instance_opcode_trace_length(const vm_exec_instance_t *instance_ptr);
+
 /**
  * Resets an instance, cleaning memories and globals.
  *
@@ -459,6 +907,37 @@ vm_exec_result_t vm_exec_instance_memory_grow(vm_exec_instance_t *instance_ptr,
  */
 vm_exec_result_t vm_exec_instance_reset(vm_exec_instance_t *instance_ptr);
 
+/**
+ * Resets an instance like `vm_exec_instance_reset`, but only copies back the memory pages
+ * written since instantiation, by the contract or as reported by
+ * `vm_exec_instance_mark_memory_written`.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+vm_exec_result_t vm_exec_instance_reset_changed_pages(vm_exec_instance_t *instance_ptr);
+
+/**
+ * Sets all the counters of the opcode histogram of the given instance back to zero.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+vm_exec_result_t vm_exec_instance_reset_opcode_histogram(const vm_exec_instance_t *instance_ptr);
+
+/**
+ * Returns an instance to the state captured by `vm_exec_instance_snapshot`.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+vm_exec_result_t vm_exec_instance_restore(const vm_exec_instance_t *instance_ptr,
+                                          const uint8_t *snapshot_bytes_ptr,
+                                          uint32_t snapshot_bytes_len);
+
 /**
  * Sets the runtime breakpoint value for the given instance.
  *
@@ -501,6 +980,27 @@ vm_exec_result_t vm_exec_instance_set_points_limit(const vm_exec_instance_t *ins
 vm_exec_result_t vm_exec_instance_set_points_used(const vm_exec_instance_t *instance_ptr,
                                                   uint64_t points);
 
+/**
+ * Captures the memory, the mutable globals and the breakpoint value of an instance.
+ *
+ * The snapshot bytes can be stored, and passed to `vm_exec_instance_restore` later.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+vm_exec_result_t vm_exec_instance_snapshot(const vm_exec_instance_t *instance_ptr,
+                                           const uint8_t **snapshot_bytes_ptr,
+                                           uint32_t *snapshot_bytes_len);
+
+/**
+ * Gets the code of the last error, `VM_EXEC_ERROR_CODE_NONE` if there was none.
+ *
+ * The code identifies the kind of the error, the message returned by
+ * `vm_exec_last_error_message` gives the details.
+ */
+vm_exec_error_code_t vm_exec_last_error_code(void);
+
 /**
  * Gets the length in bytes of the last error if any.
  *
@@ -531,6 +1031,51 @@ int vm_exec_last_error_length(void);
  */
 int vm_exec_last_error_message(char *dest_buffer, int dest_buffer_len);
 
+/**
+ * Frees memory for the given `vm_exec_module_info_t`.
+ *
+ * If `module_info_ptr` is a null pointer, this function does nothing.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+void vm_exec_module_info_destroy(vm_exec_module_info_t *module_info_ptr);
+
+/**
+ * Returns the entries of one of the lists of an inspected contract, separated by pipes.
+ *
+ * It is necessary to first call `vm_exec_module_info_list_length` and pre-allocate a buffer of this length.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+int vm_exec_module_info_list(const vm_exec_module_info_t *module_info_ptr,
+                             vm_exec_module_info_list_t list,
+                             char *dest_buffer,
+                             int dest_buffer_len);
+
+/**
+ * Returns the buffer length needed by `vm_exec_module_info_list`.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+int vm_exec_module_info_list_length(const vm_exec_module_info_t *module_info_ptr,
+                                    vm_exec_module_info_list_t list);
+
+/**
+ * Fills in the scalar facts about an inspected contract.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+vm_exec_result_t vm_exec_module_info_summary(const vm_exec_module_info_t *module_info_ptr,
+                                             vm_exec_module_summary_t *summary_ptr);
+
 /**
  * Creates a new VM executor.
  *
@@ -556,6 +1101,18 @@ vm_exec_result_t vm_exec_new_instance(vm_exec_executor_t *executor_ptr,
                                       uint32_t wasm_bytes_len,
                                       const vm_exec_compilation_options_t *options_ptr);
 
+/**
+ * Sets the costs of the bulk memory operators for the given executor.
+ *
+ * Until they are set, contracts that use bulk memory operators are rejected.
+ *
+ * # Safety
+ *
+ * C API function, works with raw object pointers.
+ */
+vm_exec_result_t vm_exec_set_bulk_memory_costs_v1(vm_exec_executor_t *executor_ptr,
+                                                  const vm_exec_bulk_memory_cost_v1_t *bulk_memory_cost_ptr);
+
 /**
  * Sets the log level.
  */
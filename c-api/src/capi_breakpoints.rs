@@ -1,7 +1,7 @@
-use klever_chain_vm_executor::Instance;
+use klever_chain_vm_executor::{ExecutorError, Instance};
 
+use crate::capi_error::update_last_error;
 use crate::capi_instance::{vm_exec_instance_t, CapiInstance};
-use crate::service_singleton::with_service;
 use crate::vm_exec_result_t;
 
 /// Sets the runtime breakpoint value for the given instance.
@@ -24,7 +24,7 @@ pub unsafe extern "C" fn vm_exec_instance_set_breakpoint_value(
     match result {
         Ok(()) => vm_exec_result_t::VM_EXEC_OK,
         Err(message) => {
-            with_service(|service| service.update_last_error_str(message));
+            update_last_error(&message);
             vm_exec_result_t::VM_EXEC_ERROR
         }
     }
@@ -46,12 +46,15 @@ pub unsafe extern "C" fn vm_exec_instance_get_breakpoint_value(
     match result {
         Ok(breakpoint_value) => breakpoint_value.as_u64(),
         Err(message) => {
-            with_service(|service| service.update_last_error_str(message));
+            update_last_error(&message);
             0
         }
     }
 }
 
-fn set_breakpoint_value_u64(instance: &dyn Instance, value: u64) -> Result<(), String> {
-    instance.set_breakpoint_value(value.try_into()?)
+fn set_breakpoint_value_u64(instance: &dyn Instance, value: u64) -> Result<(), ExecutorError> {
+    let breakpoint_value = value
+        .try_into()
+        .map_err(|_| ExecutorError::UnknownBreakpointValue(value))?;
+    instance.set_breakpoint_value(breakpoint_value)
 }
@@ -0,0 +1,146 @@
+//! Call a function and report how the call went, in a single call.
+
+use crate::{
+    capi_error::{update_last_error, update_last_error_message, vm_exec_error_code_t},
+    capi_instance::{vm_exec_instance_t, CapiInstance},
+    string_copy, string_length, vm_exec_result_t,
+};
+use klever_chain_vm_executor::{CallOutcome, ExecutorError, TrapCode};
+use libc::{c_char, c_int};
+use std::ffi::CStr;
+
+/// The reason a call did not complete.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub enum vm_exec_trap_code_t {
+    /// The call completed.
+    VM_EXEC_TRAP_NONE = 0,
+
+    /// The call was stopped by the host, e.g. by a failing VM hook.
+    VM_EXEC_TRAP_HOST = 1,
+
+    /// Also used by breakpoints, see `breakpoint_value` for the reason.
+    VM_EXEC_TRAP_UNREACHABLE = 2,
+
+    VM_EXEC_TRAP_MEMORY_OUT_OF_BOUNDS = 3,
+    VM_EXEC_TRAP_TABLE_OUT_OF_BOUNDS = 4,
+    VM_EXEC_TRAP_INTEGER_OVERFLOW = 5,
+    VM_EXEC_TRAP_INTEGER_DIVISION_BY_ZERO = 6,
+    VM_EXEC_TRAP_INVALID_CONVERSION_TO_INTEGER = 7,
+    VM_EXEC_TRAP_STACK_OVERFLOW = 8,
+    VM_EXEC_TRAP_INDIRECT_CALL_TO_NULL = 9,
+    VM_EXEC_TRAP_INDIRECT_CALL_SIGNATURE_MISMATCH = 10,
+    VM_EXEC_TRAP_OTHER = 11,
+}
+
+/// How a call went.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub struct vm_exec_call_report_t {
+    pub success: bool,
+    pub breakpoint_value: u64,
+    pub points_used_before: u64,
+    pub points_used_after: u64,
+    pub trap_code: vm_exec_trap_code_t,
+
+    /// The buffer length needed for the backtrace, zero if there is none.
+    pub backtrace_length: c_int,
+}
+
+fn trap_code(call_outcome: &CallOutcome) -> vm_exec_trap_code_t {
+    let trap = match &call_outcome.trap {
+        Some(trap) => trap,
+        None => return vm_exec_trap_code_t::VM_EXEC_TRAP_NONE,
+    };
+    match trap.code {
+        None => vm_exec_trap_code_t::VM_EXEC_TRAP_HOST,
+        Some(TrapCode::Unreachable) => vm_exec_trap_code_t::VM_EXEC_TRAP_UNREACHABLE,
+        Some(TrapCode::MemoryOutOfBounds) => vm_exec_trap_code_t::VM_EXEC_TRAP_MEMORY_OUT_OF_BOUNDS,
+        Some(TrapCode::TableOutOfBounds) => vm_exec_trap_code_t::VM_EXEC_TRAP_TABLE_OUT_OF_BOUNDS,
+        Some(TrapCode::IntegerOverflow) => vm_exec_trap_code_t::VM_EXEC_TRAP_INTEGER_OVERFLOW,
+        Some(TrapCode::IntegerDivisionByZero) => {
+            vm_exec_trap_code_t::VM_EXEC_TRAP_INTEGER_DIVISION_BY_ZERO
+        }
+        Some(TrapCode::InvalidConversionToInteger) => {
+            vm_exec_trap_code_t::VM_EXEC_TRAP_INVALID_CONVERSION_TO_INTEGER
+        }
+        Some(TrapCode::StackOverflow) => vm_exec_trap_code_t::VM_EXEC_TRAP_STACK_OVERFLOW,
+        Some(TrapCode::IndirectCallToNull) => {
+            vm_exec_trap_code_t::VM_EXEC_TRAP_INDIRECT_CALL_TO_NULL
+        }
+        Some(TrapCode::IndirectCallSignatureMismatch) => {
+            vm_exec_trap_code_t::VM_EXEC_TRAP_INDIRECT_CALL_SIGNATURE_MISMATCH
+        }
+        Some(TrapCode::Other) => vm_exec_trap_code_t::VM_EXEC_TRAP_OTHER,
+    }
+}
+
+/// Calls an exported function of a WebAssembly instance by `name`, and fills in
+/// a report of how the call went.
+///
+/// A trap does not make this function fail: the report describes it, and the trap message is
+/// also available via `vm_exec_last_error_message`. This function returns
+/// `vm_exec_result_t::VM_EXEC_ERROR` only if the call could not be made, e.g. because
+/// the function does not exist.
+///
+/// The backtrace frames are separated by pipes. They are copied into `backtrace_buffer` if it
+/// is not null and at least `backtrace_length` bytes long, otherwise they are skipped.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_instance_call_with_report(
+    instance_ptr: *mut vm_exec_instance_t,
+    func_name_ptr: *const c_char,
+    report_ptr: *mut vm_exec_call_report_t,
+    backtrace_buffer: *mut c_char,
+    backtrace_buffer_len: c_int,
+) -> vm_exec_result_t {
+    let capi_instance = cast_input_ptr!(instance_ptr, CapiInstance, "instance ptr is null");
+    return_if_ptr_null!(report_ptr, "report ptr is null");
+    if func_name_ptr.is_null() {
+        update_last_error_message(
+            "name ptr is null".to_string(),
+            vm_exec_error_code_t::VM_EXEC_ERROR_CODE_NULL_POINTER,
+        );
+        return vm_exec_result_t::VM_EXEC_ERROR;
+    }
+    let func_name = CStr::from_ptr(func_name_ptr).to_str().unwrap();
+
+    let call_outcome = match capi_instance.content.call_with_report(func_name) {
+        Ok(call_outcome) => call_outcome,
+        Err(err) => {
+            update_last_error(&err);
+            return vm_exec_result_t::VM_EXEC_ERROR;
+        }
+    };
+
+    let backtrace = call_outcome
+        .trap
+        .as_ref()
+        .map(|trap| trap.backtrace.join("|"))
+        .unwrap_or_default();
+    let backtrace_length = string_length(backtrace.clone());
+    if !backtrace_buffer.is_null() && backtrace_buffer_len >= backtrace_length {
+        string_copy(backtrace, backtrace_buffer, backtrace_buffer_len);
+    }
+
+    if let Some(trap) = &call_outcome.trap {
+        update_last_error(&ExecutorError::Trap {
+            breakpoint: call_outcome.breakpoint,
+            message: trap.message.clone(),
+        });
+    }
+
+    *report_ptr = vm_exec_call_report_t {
+        success: call_outcome.is_success(),
+        breakpoint_value: call_outcome.breakpoint.as_u64(),
+        points_used_before: call_outcome.points_used_before,
+        points_used_after: call_outcome.points_used_after,
+        trap_code: trap_code(&call_outcome),
+        backtrace_length,
+    };
+    vm_exec_result_t::VM_EXEC_OK
+}
@@ -0,0 +1,186 @@
+//! Read the compilation options of a contract from C memory.
+
+use crate::capi_error::{update_last_error_message, vm_exec_error_code_t};
+use klever_chain_vm_executor::CompilationOptions;
+use std::mem;
+
+/// The compilation options of a contract, as laid out by C callers.
+///
+/// The caller sets `struct_size` to `sizeof(vm_exec_compilation_options_t)`, so that options
+/// laid out against another version of this header are rejected instead of misread. The flags
+/// are 0 or 1, any other value is rejected as an invalid argument.
+///
+/// The `compiler` is 0 for Singlepass and 1 for Cranelift, any other value fails the
+/// compilation with an invalid argument. With `execution_trace`, the `execution_trace_length`
+/// must be from 1 to 128.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub struct vm_exec_compilation_options_t {
+    pub struct_size: u32,
+    pub gas_limit: u64,
+    pub unmetered_locals: usize,
+    pub max_memory_grow: usize,
+    pub max_memory_grow_delta: usize,
+    pub opcode_trace: u8,
+    pub metering: u8,
+    pub runtime_breakpoints: u8,
+    pub compiler: u8,
+    pub max_memory_pages: u64,
+    pub gas_schedule_version: u32,
+    pub gas_profiling: u8,
+    pub opcode_histogram: u8,
+    pub execution_trace: u8,
+    pub snapshots: u8,
+    pub execution_trace_length: u32,
+}
+
+#[cfg(target_pointer_width = "64")]
+const _: () = {
+    use std::mem::{offset_of, size_of};
+    assert!(offset_of!(vm_exec_compilation_options_t, struct_size) == 0);
+    assert!(offset_of!(vm_exec_compilation_options_t, gas_limit) == 8);
+    assert!(offset_of!(vm_exec_compilation_options_t, unmetered_locals) == 16);
+    assert!(offset_of!(vm_exec_compilation_options_t, max_memory_grow) == 24);
+    assert!(offset_of!(vm_exec_compilation_options_t, max_memory_grow_delta) == 32);
+    assert!(offset_of!(vm_exec_compilation_options_t, opcode_trace) == 40);
+    assert!(offset_of!(vm_exec_compilation_options_t, metering) == 41);
+    assert!(offset_of!(vm_exec_compilation_options_t, runtime_breakpoints) == 42);
+    assert!(offset_of!(vm_exec_compilation_options_t, compiler) == 43);
+    assert!(offset_of!(vm_exec_compilation_options_t, max_memory_pages) == 48);
+    assert!(offset_of!(vm_exec_compilation_options_t, gas_schedule_version) == 56);
+    assert!(offset_of!(vm_exec_compilation_options_t, gas_profiling) == 60);
+    assert!(offset_of!(vm_exec_compilation_options_t, opcode_histogram) == 61);
+    assert!(offset_of!(vm_exec_compilation_options_t, execution_trace) == 62);
+    assert!(offset_of!(vm_exec_compilation_options_t, snapshots) == 63);
+    assert!(offset_of!(vm_exec_compilation_options_t, execution_trace_length) == 64);
+    assert!(size_of::<vm_exec_compilation_options_t>() == 72);
+};
+
+/// Checks and converts the options given by a C caller.
+///
+/// Records the last error and returns `None` if they are missing or invalid.
+///
+/// # Safety
+///
+/// `options_ptr` is null, or points to at least the `struct_size` field of the options.
+pub(crate) unsafe fn compilation_options(
+    options_ptr: *const vm_exec_compilation_options_t,
+) -> Option<CompilationOptions> {
+    if options_ptr.is_null() {
+        update_last_error_message(
+            "compilation options ptr is null".to_string(),
+            vm_exec_error_code_t::VM_EXEC_ERROR_CODE_NULL_POINTER,
+        );
+        return None;
+    }
+
+    // Only the size is read before it is checked, the rest may lie past a shorter struct
+    let struct_size = (*options_ptr).struct_size;
+    let expected_size = mem::size_of::<vm_exec_compilation_options_t>();
+    if struct_size as usize != expected_size {
+        update_last_error_message(
+            format!("compilation options size is {struct_size}, expected {expected_size}"),
+            vm_exec_error_code_t::VM_EXEC_ERROR_CODE_INVALID_ARGUMENT,
+        );
+        return None;
+    }
+
+    let options = &*options_ptr;
+    match try_compilation_options(options) {
+        Ok(compilation_options) => Some(compilation_options),
+        Err(message) => {
+            update_last_error_message(
+                message,
+                vm_exec_error_code_t::VM_EXEC_ERROR_CODE_INVALID_ARGUMENT,
+            );
+            None
+        }
+    }
+}
+
+fn try_compilation_options(
+    options: &vm_exec_compilation_options_t,
+) -> Result<CompilationOptions, String> {
+    Ok(CompilationOptions {
+        gas_limit: options.gas_limit,
+        unmetered_locals: options.unmetered_locals,
+        max_memory_grow: options.max_memory_grow,
+        max_memory_grow_delta: options.max_memory_grow_delta,
+        opcode_trace: flag("opcode_trace", options.opcode_trace)?,
+        metering: flag("metering", options.metering)?,
+        runtime_breakpoints: flag("runtime_breakpoints", options.runtime_breakpoints)?,
+        compiler: options.compiler,
+        max_memory_pages: options.max_memory_pages,
+        gas_schedule_version: options.gas_schedule_version,
+        gas_profiling: flag("gas_profiling", options.gas_profiling)?,
+        opcode_histogram: flag("opcode_histogram", options.opcode_histogram)?,
+        execution_trace: flag("execution_trace", options.execution_trace)?,
+        snapshots: flag("snapshots", options.snapshots)?,
+        execution_trace_length: options.execution_trace_length,
+    })
+}
+
+fn flag(name: &str, value: u8) -> Result<bool, String> {
+    match value {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(format!(
+            "compilation option {name} is {value}, expected 0 or 1"
+        )),
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::capi_error::vm_exec_last_error_code;
+
+    fn valid_options() -> vm_exec_compilation_options_t {
+        vm_exec_compilation_options_t {
+            struct_size: mem::size_of::<vm_exec_compilation_options_t>() as u32,
+            gas_limit: 1000,
+            unmetered_locals: 0,
+            max_memory_grow: 0,
+            max_memory_grow_delta: 0,
+            opcode_trace: 0,
+            metering: 1,
+            runtime_breakpoints: 1,
+            compiler: 0,
+            max_memory_pages: 20,
+            gas_schedule_version: 0,
+            gas_profiling: 0,
+            opcode_histogram: 0,
+            execution_trace: 0,
+            snapshots: 0,
+            execution_trace_length: 0,
+        }
+    }
+
+    #[test]
+    fn test_compilation_options() {
+        let options = unsafe { compilation_options(&valid_options()) }.unwrap();
+        assert!(options.metering);
+        assert!(!options.snapshots);
+        assert_eq!(options.gas_limit, 1000);
+
+        let other_size = vm_exec_compilation_options_t {
+            struct_size: 64,
+            ..valid_options()
+        };
+        assert!(unsafe { compilation_options(&other_size) }.is_none());
+        assert_eq!(
+            vm_exec_last_error_code(),
+            vm_exec_error_code_t::VM_EXEC_ERROR_CODE_INVALID_ARGUMENT
+        );
+
+        let bad_flag = vm_exec_compilation_options_t {
+            snapshots: 2,
+            ..valid_options()
+        };
+        assert!(unsafe { compilation_options(&bad_flag) }.is_none());
+        assert_eq!(
+            vm_exec_last_error_code(),
+            vm_exec_error_code_t::VM_EXEC_ERROR_CODE_INVALID_ARGUMENT
+        );
+    }
+}
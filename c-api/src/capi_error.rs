@@ -1,10 +1,163 @@
 //! Read runtime errors.
 
 // use crate::service::with_service;
+use klever_chain_vm_executor::{ExecutorError, ValidationError};
 use libc::{c_char, c_int};
+use std::cell::Cell;
 
 use crate::{service_singleton::with_service, string_copy, string_length};
 
+/// Identifies the kind of the last error.
+///
+/// The values are stable, new kinds of errors only ever get new values.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum vm_exec_error_code_t {
+    /// No error occurred yet.
+    VM_EXEC_ERROR_CODE_NONE = 0,
+
+    /// Any error of the underlying runtime not covered below.
+    VM_EXEC_ERROR_CODE_RUNTIME = 1,
+
+    /// The executor service rejected the operation.
+    VM_EXEC_ERROR_CODE_SERVICE = 2,
+
+    /// A pointer given to the C API was null.
+    VM_EXEC_ERROR_CODE_NULL_POINTER = 3,
+
+    /// An argument given to the C API was invalid.
+    VM_EXEC_ERROR_CODE_INVALID_ARGUMENT = 4,
+
+    /// The contract bytes could not be parsed.
+    VM_EXEC_ERROR_CODE_INVALID_MODULE = 10,
+
+    VM_EXEC_ERROR_CODE_COMPILATION = 11,
+
+    VM_EXEC_ERROR_CODE_NO_MEMORY = 20,
+    VM_EXEC_ERROR_CODE_MULTIPLE_MEMORIES = 21,
+    VM_EXEC_ERROR_CODE_MEMORY_TOO_LARGE = 22,
+    VM_EXEC_ERROR_CODE_TOO_MANY_LOCALS = 23,
+    VM_EXEC_ERROR_CODE_UNSUPPORTED_OPERATOR = 24,
+    VM_EXEC_ERROR_CODE_INVALID_IMPORTS = 25,
+    VM_EXEC_ERROR_CODE_RESERVED_EXPORT_NAME = 26,
+
+    VM_EXEC_ERROR_CODE_INSTANTIATION = 30,
+
+    /// A cached module was rejected.
+    VM_EXEC_ERROR_CODE_CACHE = 31,
+
+    /// An instance snapshot was rejected.
+    VM_EXEC_ERROR_CODE_SNAPSHOT = 32,
+
+    /// The execution was interrupted, see `vm_exec_instance_get_breakpoint_value` for the reason.
+    VM_EXEC_ERROR_CODE_TRAP = 40,
+
+    VM_EXEC_ERROR_CODE_MISSING_EXPORT = 50,
+
+    /// The arguments of a typed call do not match the function signature.
+    VM_EXEC_ERROR_CODE_SIGNATURE_MISMATCH = 51,
+
+    VM_EXEC_ERROR_CODE_MEMORY_OUT_OF_BOUNDS = 60,
+    VM_EXEC_ERROR_CODE_MEMORY_GROW_LIMIT = 61,
+
+    VM_EXEC_ERROR_CODE_METERING_DISABLED = 70,
+    VM_EXEC_ERROR_CODE_RUNTIME_BREAKPOINTS_DISABLED = 71,
+    VM_EXEC_ERROR_CODE_UNKNOWN_BREAKPOINT_VALUE = 72,
+
+    /// A gas schedule was rejected, or the requested one is not registered.
+    VM_EXEC_ERROR_CODE_GAS_SCHEDULE = 73,
+
+    VM_EXEC_ERROR_CODE_GAS_PROFILING_DISABLED = 74,
+    VM_EXEC_ERROR_CODE_OPCODE_HISTOGRAM_DISABLED = 75,
+
+    /// The opcode trace could not be written to its sink.
+    VM_EXEC_ERROR_CODE_OPCODE_TRACE = 76,
+    VM_EXEC_ERROR_CODE_EXECUTION_TRACE_DISABLED = 77,
+    VM_EXEC_ERROR_CODE_SNAPSHOTS_DISABLED = 78,
+}
+
+impl From<&ExecutorError> for vm_exec_error_code_t {
+    fn from(err: &ExecutorError) -> Self {
+        match err {
+            ExecutorError::InvalidModule(_) => Self::VM_EXEC_ERROR_CODE_INVALID_MODULE,
+            ExecutorError::Compilation(_) => Self::VM_EXEC_ERROR_CODE_COMPILATION,
+            ExecutorError::UnknownCompilerBackend(_) => Self::VM_EXEC_ERROR_CODE_INVALID_ARGUMENT,
+            ExecutorError::InvalidExecutionTraceLength(_) => {
+                Self::VM_EXEC_ERROR_CODE_INVALID_ARGUMENT
+            }
+            ExecutorError::Validation(validation_error) => match validation_error {
+                ValidationError::NoMemory => Self::VM_EXEC_ERROR_CODE_NO_MEMORY,
+                ValidationError::MultipleMemories => Self::VM_EXEC_ERROR_CODE_MULTIPLE_MEMORIES,
+                ValidationError::MemoryTooLarge { .. } => Self::VM_EXEC_ERROR_CODE_MEMORY_TOO_LARGE,
+                ValidationError::TooManyLocals { .. } => Self::VM_EXEC_ERROR_CODE_TOO_MANY_LOCALS,
+                ValidationError::UnsupportedOperator(_) => {
+                    Self::VM_EXEC_ERROR_CODE_UNSUPPORTED_OPERATOR
+                }
+                ValidationError::InvalidImports(_) => Self::VM_EXEC_ERROR_CODE_INVALID_IMPORTS,
+                ValidationError::ReservedExportName(_) => {
+                    Self::VM_EXEC_ERROR_CODE_RESERVED_EXPORT_NAME
+                }
+            },
+            ExecutorError::Instantiation(_) => Self::VM_EXEC_ERROR_CODE_INSTANTIATION,
+            ExecutorError::Cache(_) => Self::VM_EXEC_ERROR_CODE_CACHE,
+            ExecutorError::Snapshot(_) => Self::VM_EXEC_ERROR_CODE_SNAPSHOT,
+            ExecutorError::Trap { .. } => Self::VM_EXEC_ERROR_CODE_TRAP,
+            ExecutorError::MissingExport(_) => Self::VM_EXEC_ERROR_CODE_MISSING_EXPORT,
+            ExecutorError::ArgumentCount { .. }
+            | ExecutorError::ArgumentType { .. }
+            | ExecutorError::UnsupportedSignature(_) => Self::VM_EXEC_ERROR_CODE_SIGNATURE_MISMATCH,
+            ExecutorError::MemoryOutOfBounds { .. } => {
+                Self::VM_EXEC_ERROR_CODE_MEMORY_OUT_OF_BOUNDS
+            }
+            ExecutorError::MemoryGrowLimit { .. } => Self::VM_EXEC_ERROR_CODE_MEMORY_GROW_LIMIT,
+            ExecutorError::MeteringDisabled => Self::VM_EXEC_ERROR_CODE_METERING_DISABLED,
+            ExecutorError::RuntimeBreakpointsDisabled => {
+                Self::VM_EXEC_ERROR_CODE_RUNTIME_BREAKPOINTS_DISABLED
+            }
+            ExecutorError::UnknownBreakpointValue(_) => {
+                Self::VM_EXEC_ERROR_CODE_UNKNOWN_BREAKPOINT_VALUE
+            }
+            ExecutorError::GasSchedule(_) => Self::VM_EXEC_ERROR_CODE_GAS_SCHEDULE,
+            ExecutorError::GasProfilingDisabled => Self::VM_EXEC_ERROR_CODE_GAS_PROFILING_DISABLED,
+            ExecutorError::OpcodeHistogramDisabled => {
+                Self::VM_EXEC_ERROR_CODE_OPCODE_HISTOGRAM_DISABLED
+            }
+            ExecutorError::OpcodeTrace(_) => Self::VM_EXEC_ERROR_CODE_OPCODE_TRACE,
+            ExecutorError::ExecutionTraceDisabled => {
+                Self::VM_EXEC_ERROR_CODE_EXECUTION_TRACE_DISABLED
+            }
+            ExecutorError::SnapshotsDisabled => Self::VM_EXEC_ERROR_CODE_SNAPSHOTS_DISABLED,
+            ExecutorError::Runtime(_) => Self::VM_EXEC_ERROR_CODE_RUNTIME,
+            ExecutorError::Service(_) => Self::VM_EXEC_ERROR_CODE_SERVICE,
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR_CODE: Cell<vm_exec_error_code_t> = const { Cell::new(vm_exec_error_code_t::VM_EXEC_ERROR_CODE_NONE) };
+}
+
+/// Records an executor error as the last error, together with its code.
+pub(crate) fn update_last_error(err: &ExecutorError) {
+    update_last_error_message(err.to_string(), err.into());
+}
+
+/// Records an error raised by the C API itself as the last error.
+pub(crate) fn update_last_error_message(message: String, code: vm_exec_error_code_t) {
+    with_service(|service| service.update_last_error_str(message));
+    LAST_ERROR_CODE.with(|last_error_code| last_error_code.set(code));
+}
+
+/// Gets the code of the last error, `VM_EXEC_ERROR_CODE_NONE` if there was none.
+///
+/// The code identifies the kind of the error, the message returned by
+/// `vm_exec_last_error_message` gives the details.
+#[no_mangle]
+pub extern "C" fn vm_exec_last_error_code() -> vm_exec_error_code_t {
+    LAST_ERROR_CODE.with(|last_error_code| last_error_code.get())
+}
+
 /// Gets the length in bytes of the last error if any.
 ///
 /// This can be used to dynamically allocate a buffer with the correct number of
@@ -43,3 +196,24 @@ pub unsafe extern "C" fn vm_exec_last_error_message(
 fn get_last_error_string() -> String {
     with_service(|service| service.get_last_error_string())
 }
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    #[test]
+    fn test_last_error_code() {
+        update_last_error(&ExecutorError::MissingExport("init".to_string()));
+        assert_eq!(
+            vm_exec_last_error_code(),
+            vm_exec_error_code_t::VM_EXEC_ERROR_CODE_MISSING_EXPORT
+        );
+        assert_eq!(
+            with_service(|service| service.get_last_error_string()),
+            "function not found: init"
+        );
+
+        update_last_error(&ValidationError::NoMemory.into());
+        assert_eq!(vm_exec_last_error_code() as u32, 20);
+    }
+}
@@ -0,0 +1,76 @@
+//! Read the execution trace of the last call of an instance.
+
+use crate::{
+    capi_error::update_last_error,
+    capi_instance::{vm_exec_instance_t, CapiInstance},
+    string_copy, string_length,
+};
+use klever_chain_vm_executor::{ExecutionTraceEntry, ExecutorError};
+use libc::{c_char, c_int};
+
+fn execution_trace_string(execution_trace: &[ExecutionTraceEntry]) -> String {
+    let entries: Vec<String> = execution_trace
+        .iter()
+        .map(|entry| format!("{}:{}", entry.function_index, entry.operator_index))
+        .collect();
+    entries.join("|")
+}
+
+fn instance_last_execution_trace(capi_instance: &CapiInstance) -> Result<String, ExecutorError> {
+    capi_instance
+        .content
+        .last_execution_trace()
+        .map(|execution_trace| execution_trace_string(&execution_trace.unwrap_or_default()))
+}
+
+/// Returns the buffer length needed by `vm_exec_instance_last_execution_trace`, `-1` if the trace
+/// cannot be read, e.g. because it is disabled for the given instance.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_instance_last_execution_trace_length(
+    instance_ptr: *const vm_exec_instance_t,
+) -> c_int {
+    let capi_instance =
+        cast_input_const_ptr!(instance_ptr, CapiInstance, "instance ptr is null", -1);
+    match instance_last_execution_trace(capi_instance) {
+        Ok(execution_trace) => string_length(execution_trace),
+        Err(err) => {
+            update_last_error(&err);
+            -1
+        }
+    }
+}
+
+/// Returns the last basic blocks entered by the last call of the given instance, oldest first,
+/// separated by pipes. It is empty unless that call trapped or stopped on a breakpoint.
+///
+/// Entries look like `3:12`, the function index followed by the index of the first operator
+/// of the block.
+///
+/// It is necessary to first call `vm_exec_instance_last_execution_trace_length` and pre-allocate
+/// a buffer of this length.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_instance_last_execution_trace(
+    instance_ptr: *const vm_exec_instance_t,
+    dest_buffer: *mut c_char,
+    dest_buffer_len: c_int,
+) -> c_int {
+    let capi_instance =
+        cast_input_const_ptr!(instance_ptr, CapiInstance, "instance ptr is null", -1);
+    match instance_last_execution_trace(capi_instance) {
+        Ok(execution_trace) => string_copy(execution_trace, dest_buffer, dest_buffer_len),
+        Err(err) => {
+            update_last_error(&err);
+            -1
+        }
+    }
+}
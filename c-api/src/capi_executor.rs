@@ -1,11 +1,15 @@
 //! Instantiate a module, call functions, and read exports.
 
 use crate::{
-    capi_vm_hook_pointers::vm_exec_vm_hook_c_func_pointers, capi_vm_hooks::CapiVMHooks,
-    service_singleton::with_service, vm_exec_result_t,
+    capi_error::{update_last_error, update_last_error_message, vm_exec_error_code_t},
+    capi_vm_hook_pointers::vm_exec_vm_hook_c_func_pointers,
+    capi_vm_hooks::CapiVMHooks,
+    service_singleton::with_service,
+    vm_exec_result_t,
 };
-use libc::c_void;
-use klever_chain_vm_executor::Executor;
+use libc::{c_char, c_void};
+use std::{ffi::CStr, path::PathBuf, slice};
+use klever_chain_vm_executor::{Executor, ImportValidationError, OpcodeTraceSink, ValidationError};
 use klever_chain_vm_executor_wasmer::force_sighandler_reinstall;
 
 #[repr(C)]
@@ -15,6 +19,11 @@ pub struct CapiExecutor {
     pub content: Box<dyn Executor>,
 }
 
+// The VM hooks pointer is owned by the caller, which is responsible for making the hooks
+// safe to call from the threads it uses the executor on.
+unsafe impl Send for CapiVMHooks {}
+unsafe impl Sync for CapiVMHooks {}
+
 /// Creates a new VM executor.
 ///
 /// # Safety
@@ -44,7 +53,7 @@ pub unsafe extern "C" fn vm_exec_new_executor(
             vm_exec_result_t::VM_EXEC_OK
         }
         Err(message) => {
-            with_service(|service| service.update_last_error_str(message.to_string()));
+            update_last_error(&message);
             vm_exec_result_t::VM_EXEC_ERROR
         }
     }
@@ -84,7 +93,113 @@ pub unsafe extern "C" fn vm_exec_executor_set_vm_hooks_ptr(
     match result {
         Ok(()) => vm_exec_result_t::VM_EXEC_OK,
         Err(message) => {
-            with_service(|service| service.update_last_error_str(message.to_string()));
+            update_last_error(&message);
+            vm_exec_result_t::VM_EXEC_ERROR
+        }
+    }
+}
+
+/// Sets the maximum number of compiled modules kept in memory by the executor.
+///
+/// A capacity of zero disables the cache.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_executor_set_module_cache_capacity(
+    executor_ptr: *mut vm_exec_executor_t,
+    capacity: u64,
+) -> vm_exec_result_t {
+    let capi_executor = cast_input_ptr!(executor_ptr, CapiExecutor, "executor ptr is null");
+
+    let result = capi_executor
+        .content
+        .set_module_cache_capacity(capacity as usize);
+    match result {
+        Ok(()) => vm_exec_result_t::VM_EXEC_OK,
+        Err(message) => {
+            update_last_error(&message);
+            vm_exec_result_t::VM_EXEC_ERROR
+        }
+    }
+}
+
+/// Sets the file the contracts compiled with `opcode_trace` are traced to, one JSON entry per line.
+///
+/// If `path_ptr` is null, the trace is kept by each instance instead, which is the default,
+/// see `vm_exec_instance_opcode_trace`.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_executor_set_opcode_trace_file(
+    executor_ptr: *mut vm_exec_executor_t,
+    path_ptr: *const c_char,
+) -> vm_exec_result_t {
+    let capi_executor = cast_input_ptr!(executor_ptr, CapiExecutor, "executor ptr is null");
+
+    let sink = if path_ptr.is_null() {
+        OpcodeTraceSink::Memory
+    } else {
+        match CStr::from_ptr(path_ptr).to_str() {
+            Ok(path) => OpcodeTraceSink::File(PathBuf::from(path)),
+            Err(_) => {
+                update_last_error_message(
+                    "opcode trace path is not valid UTF-8".to_string(),
+                    vm_exec_error_code_t::VM_EXEC_ERROR_CODE_INVALID_ARGUMENT,
+                );
+                return vm_exec_result_t::VM_EXEC_ERROR;
+            }
+        }
+    };
+    match capi_executor.content.set_opcode_trace_sink(sink) {
+        Ok(()) => vm_exec_result_t::VM_EXEC_OK,
+        Err(message) => {
+            update_last_error(&message);
+            vm_exec_result_t::VM_EXEC_ERROR
+        }
+    }
+}
+
+/// Checks the imports of a contract against the VM hooks, without instantiating it.
+///
+/// This function returns `vm_exec_result_t::VM_EXEC_ERROR` if the contract cannot be parsed or
+/// has imports that the VM hooks do not provide. All offending imports are then listed in the
+/// last error message.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_executor_validate_imports(
+    executor_ptr: *mut vm_exec_executor_t,
+    wasm_bytes_ptr: *mut u8,
+    wasm_bytes_len: u32,
+) -> vm_exec_result_t {
+    let capi_executor = cast_input_ptr!(executor_ptr, CapiExecutor, "executor ptr is null");
+
+    if wasm_bytes_ptr.is_null() {
+        update_last_error_message(
+            "wasm bytes ptr is null".to_string(),
+            vm_exec_error_code_t::VM_EXEC_ERROR_CODE_NULL_POINTER,
+        );
+        return vm_exec_result_t::VM_EXEC_ERROR;
+    }
+
+    let wasm_bytes: &[u8] = slice::from_raw_parts(wasm_bytes_ptr, wasm_bytes_len as usize);
+    match capi_executor.content.validate_imports(wasm_bytes) {
+        Ok(issues) if issues.is_empty() => vm_exec_result_t::VM_EXEC_OK,
+        Ok(issues) => {
+            update_last_error(&ValidationError::from(ImportValidationError { issues }).into());
+            vm_exec_result_t::VM_EXEC_ERROR
+        }
+        Err(message) => {
+            update_last_error(&message);
             vm_exec_result_t::VM_EXEC_ERROR
         }
     }
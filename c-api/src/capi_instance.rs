@@ -1,12 +1,13 @@
 //! Instantiate a module, call functions, and read exports.
 
 use crate::{
+    capi_compilation_options::{compilation_options, vm_exec_compilation_options_t},
+    capi_error::{update_last_error, update_last_error_message, vm_exec_error_code_t},
     capi_executor::{vm_exec_executor_t, CapiExecutor},
-    service_singleton::with_service,
-    string_copy, vm_exec_result_t,
+    string_copy, string_length, vm_exec_result_t,
 };
 use libc::{c_char, c_int};
-use klever_chain_vm_executor::{CompilationOptions, Instance};
+use klever_chain_vm_executor::Instance;
 use std::{ffi::CStr, slice};
 
 /// Opaque pointer to a `wasmer_runtime::Instance` value in Rust.
@@ -17,9 +18,6 @@ use std::{ffi::CStr, slice};
 #[repr(C)]
 pub struct vm_exec_instance_t;
 
-#[repr(C)]
-pub struct vm_exec_compilation_options_t;
-
 pub struct CapiInstance {
     pub(crate) content: Box<dyn Instance>,
 }
@@ -43,15 +41,20 @@ pub unsafe extern "C" fn vm_exec_new_instance(
     let capi_executor = cast_input_ptr!(executor_ptr, CapiExecutor, "executor ptr is null");
 
     if wasm_bytes_ptr.is_null() {
-        with_service(|service| service.update_last_error_str("wasm bytes ptr is null".to_string()));
+        update_last_error_message(
+            "wasm bytes ptr is null".to_string(),
+            vm_exec_error_code_t::VM_EXEC_ERROR_CODE_NULL_POINTER,
+        );
         return vm_exec_result_t::VM_EXEC_ERROR;
     }
 
     let wasm_bytes: &[u8] = slice::from_raw_parts(wasm_bytes_ptr, wasm_bytes_len as usize);
-    let compilation_options: &CompilationOptions = &*(options_ptr as *const CompilationOptions);
+    let Some(compilation_options) = compilation_options(options_ptr) else {
+        return vm_exec_result_t::VM_EXEC_ERROR;
+    };
     let instance_result = capi_executor
         .content
-        .new_instance(wasm_bytes, compilation_options);
+        .new_instance(wasm_bytes, &compilation_options);
     match instance_result {
         Ok(instance_box) => {
             let capi_instance = CapiInstance {
@@ -61,7 +64,7 @@ pub unsafe extern "C" fn vm_exec_new_instance(
             vm_exec_result_t::VM_EXEC_OK
         }
         Err(message) => {
-            with_service(|service| service.update_last_error_str(message.to_string()));
+            update_last_error(&message);
             vm_exec_result_t::VM_EXEC_ERROR
         }
     }
@@ -94,7 +97,10 @@ pub unsafe extern "C" fn vm_exec_instance_call(
 
     // unpack the function name
     if func_name_ptr.is_null() {
-        with_service(|service| service.update_last_error_str("name ptr is null".to_string()));
+        update_last_error_message(
+            "name ptr is null".to_string(),
+            vm_exec_error_code_t::VM_EXEC_ERROR_CODE_NULL_POINTER,
+        );
         return vm_exec_result_t::VM_EXEC_ERROR;
     }
     let func_name_c = CStr::from_ptr(func_name_ptr);
@@ -104,7 +110,7 @@ pub unsafe extern "C" fn vm_exec_instance_call(
     match result {
         Ok(()) => vm_exec_result_t::VM_EXEC_OK,
         Err(message) => {
-            with_service(|service| service.update_last_error_str(message));
+            update_last_error(&message);
             vm_exec_result_t::VM_EXEC_ERROR
         }
     }
@@ -126,11 +132,10 @@ pub unsafe extern "C" fn vm_check_signatures(
     if capi_instance.content.check_signatures() {
         vm_exec_result_t::VM_EXEC_OK
     } else {
-        with_service(|service| {
-            service.update_last_error_str(
-                "non-zero number of params or results for at least one endpoint".to_string(),
-            )
-        });
+        update_last_error_message(
+            "non-zero number of params or results for at least one endpoint".to_string(),
+            vm_exec_error_code_t::VM_EXEC_ERROR_CODE_INVALID_MODULE,
+        );
         vm_exec_result_t::VM_EXEC_ERROR
     }
 }
@@ -202,6 +207,57 @@ pub unsafe extern "C" fn vm_exported_function_names(
     string_copy(concat, dest_buffer, dest_buffer_len)
 }
 
+fn opcode_trace_string(capi_instance: &CapiInstance) -> String {
+    let lines: Vec<String> = capi_instance
+        .content
+        .opcode_trace()
+        .iter()
+        .map(|entry| entry.to_json_line())
+        .collect();
+    lines.join("\n")
+}
+
+/// Returns the buffer length needed by `vm_exec_instance_opcode_trace`.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_instance_opcode_trace_length(
+    instance_ptr: *const vm_exec_instance_t,
+) -> c_int {
+    let capi_instance =
+        cast_input_const_ptr!(instance_ptr, CapiInstance, "instance ptr is null", 0);
+    string_length(opcode_trace_string(capi_instance))
+}
+
+/// Returns the opcode trace kept by the instance, one JSON entry per line.
+///
+/// The trace is only kept if the executor traces to memory, see
+/// `vm_exec_executor_set_opcode_trace_file`, it is empty otherwise.
+///
+/// It is necessary to first call `vm_exec_instance_opcode_trace_length` and pre-allocate a buffer of this length.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_instance_opcode_trace(
+    instance_ptr: *const vm_exec_instance_t,
+    dest_buffer: *mut c_char,
+    dest_buffer_len: c_int,
+) -> c_int {
+    let capi_instance =
+        cast_input_const_ptr!(instance_ptr, CapiInstance, "instance ptr is null", 0);
+    string_copy(
+        opcode_trace_string(capi_instance),
+        dest_buffer,
+        dest_buffer_len,
+    )
+}
+
 /// Frees memory for the given `vm_exec_instance_t`.
 ///
 /// Check the `wasmer_instantiate()` function to get a complete
@@ -237,7 +293,31 @@ pub unsafe extern "C" fn vm_exec_instance_reset(
     match result {
         Ok(()) => vm_exec_result_t::VM_EXEC_OK,
         Err(message) => {
-            with_service(|service| service.update_last_error_str(message));
+            update_last_error(&message);
+            vm_exec_result_t::VM_EXEC_ERROR
+        }
+    }
+}
+
+/// Resets an instance like `vm_exec_instance_reset`, but only copies back the memory pages
+/// written since instantiation, by the contract or as reported by
+/// `vm_exec_instance_mark_memory_written`.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_instance_reset_changed_pages(
+    instance_ptr: *mut vm_exec_instance_t,
+) -> vm_exec_result_t {
+    let capi_instance = cast_input_ptr!(instance_ptr, CapiInstance, "instance ptr is null");
+
+    let result = capi_instance.content.reset_changed_pages();
+    match result {
+        Ok(_) => vm_exec_result_t::VM_EXEC_OK,
+        Err(message) => {
+            update_last_error(&message);
             vm_exec_result_t::VM_EXEC_ERROR
         }
     }
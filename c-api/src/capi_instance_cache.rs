@@ -1,11 +1,10 @@
 use std::slice;
 
-use klever_chain_vm_executor::CompilationOptions;
-
 use crate::{
+    capi_compilation_options::{compilation_options, vm_exec_compilation_options_t},
+    capi_error::{update_last_error, update_last_error_message, vm_exec_error_code_t},
     capi_executor::{vm_exec_executor_t, CapiExecutor},
-    capi_instance::{vm_exec_compilation_options_t, vm_exec_instance_t, CapiInstance},
-    service_singleton::with_service,
+    capi_instance::{vm_exec_instance_t, CapiInstance},
     vm_exec_result_t,
 };
 
@@ -32,7 +31,7 @@ pub unsafe extern "C" fn vm_exec_instance_cache(
             vm_exec_result_t::VM_EXEC_OK
         }
         Err(message) => {
-            with_service(|service| service.update_last_error_str(message));
+            update_last_error(&message);
             vm_exec_result_t::VM_EXEC_ERROR
         }
     }
@@ -57,17 +56,20 @@ pub unsafe extern "C" fn vm_exec_instance_from_cache(
     let capi_executor = cast_input_ptr!(executor_ptr, CapiExecutor, "executor ptr is null");
 
     if cache_bytes_ptr.is_null() {
-        with_service(|service| {
-            service.update_last_error_str("cache bytes ptr is null".to_string())
-        });
+        update_last_error_message(
+            "cache bytes ptr is null".to_string(),
+            vm_exec_error_code_t::VM_EXEC_ERROR_CODE_NULL_POINTER,
+        );
         return vm_exec_result_t::VM_EXEC_ERROR;
     }
 
     let cache_bytes: &[u8] = slice::from_raw_parts(cache_bytes_ptr, cache_bytes_len as usize);
-    let compilation_options: &CompilationOptions = &*(options_ptr as *const CompilationOptions);
+    let Some(compilation_options) = compilation_options(options_ptr) else {
+        return vm_exec_result_t::VM_EXEC_ERROR;
+    };
     let instance_result = capi_executor
         .content
-        .new_instance_from_cache(cache_bytes, compilation_options);
+        .new_instance_from_cache(cache_bytes, &compilation_options);
     match instance_result {
         Ok(instance_box) => {
             let capi_instance = CapiInstance {
@@ -77,7 +79,7 @@ pub unsafe extern "C" fn vm_exec_instance_from_cache(
             vm_exec_result_t::VM_EXEC_OK
         }
         Err(message) => {
-            with_service(|service| service.update_last_error_str(message.to_string()));
+            update_last_error(&message);
             vm_exec_result_t::VM_EXEC_ERROR
         }
     }
@@ -0,0 +1,76 @@
+use std::slice;
+
+use klever_chain_vm_executor::InstanceSnapshot;
+
+use crate::{
+    capi_error::{update_last_error, update_last_error_message, vm_exec_error_code_t},
+    capi_instance::{vm_exec_instance_t, CapiInstance},
+    vm_exec_result_t,
+};
+
+/// Captures the memory, the mutable globals and the breakpoint value of an instance.
+///
+/// The snapshot bytes can be stored, and passed to `vm_exec_instance_restore` later.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_instance_snapshot(
+    instance_ptr: *const vm_exec_instance_t,
+    snapshot_bytes_ptr: *mut *const u8,
+    snapshot_bytes_len: *mut u32,
+) -> vm_exec_result_t {
+    let capi_instance = cast_input_const_ptr!(instance_ptr, CapiInstance, "instance ptr is null");
+
+    let result = capi_instance.content.snapshot();
+    match result {
+        Ok(snapshot) => {
+            let bytes = snapshot.to_bytes();
+            *snapshot_bytes_ptr = bytes.as_ptr();
+            *snapshot_bytes_len = bytes.len() as u32;
+            std::mem::forget(bytes);
+            vm_exec_result_t::VM_EXEC_OK
+        }
+        Err(message) => {
+            update_last_error(&message);
+            vm_exec_result_t::VM_EXEC_ERROR
+        }
+    }
+}
+
+/// Returns an instance to the state captured by `vm_exec_instance_snapshot`.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_instance_restore(
+    instance_ptr: *const vm_exec_instance_t,
+    snapshot_bytes_ptr: *const u8,
+    snapshot_bytes_len: u32,
+) -> vm_exec_result_t {
+    let capi_instance = cast_input_const_ptr!(instance_ptr, CapiInstance, "instance ptr is null");
+
+    if snapshot_bytes_ptr.is_null() {
+        update_last_error_message(
+            "snapshot bytes ptr is null".to_string(),
+            vm_exec_error_code_t::VM_EXEC_ERROR_CODE_NULL_POINTER,
+        );
+        return vm_exec_result_t::VM_EXEC_ERROR;
+    }
+
+    let snapshot_bytes = slice::from_raw_parts(snapshot_bytes_ptr, snapshot_bytes_len as usize);
+    let result = InstanceSnapshot::from_bytes(snapshot_bytes)
+        .map_err(Into::into)
+        .and_then(|snapshot| capi_instance.content.restore(&snapshot));
+    match result {
+        Ok(()) => vm_exec_result_t::VM_EXEC_OK,
+        Err(message) => {
+            update_last_error(&message);
+            vm_exec_result_t::VM_EXEC_ERROR
+        }
+    }
+}
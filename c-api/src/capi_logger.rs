@@ -1,6 +1,7 @@
 use klever_chain_vm_executor_wasmer::{set_log_level, u64_to_log_level};
 
-use crate::{basic_types::vm_exec_result_t, service_singleton::with_service};
+use crate::basic_types::vm_exec_result_t;
+use crate::capi_error::{update_last_error_message, vm_exec_error_code_t};
 
 /// Sets the log level.
 #[no_mangle]
@@ -13,7 +14,10 @@ pub extern "C" fn vm_exec_set_log_level(value: u64) -> vm_exec_result_t {
             vm_exec_result_t::VM_EXEC_OK
         }
         Err(message) => {
-            with_service(|service| service.update_last_error_str(message.to_string()));
+            update_last_error_message(
+                message.to_string(),
+                vm_exec_error_code_t::VM_EXEC_ERROR_CODE_INVALID_ARGUMENT,
+            );
             vm_exec_result_t::VM_EXEC_ERROR
         }
     }
@@ -1,10 +1,11 @@
 //! Instantiate a module, call functions, and read exports.
 
 use crate::{
+    capi_error::update_last_error,
     capi_instance::{vm_exec_instance_t, CapiInstance},
-    service_singleton::with_service,
     vm_exec_result_t,
 };
+use klever_chain_vm_executor::{MemLength, MemPtr};
 use std::ptr;
 
 /// Gets the size in bytes of the memory data.
@@ -24,7 +25,7 @@ pub unsafe extern "C" fn vm_exec_instance_memory_data_length(
     match result {
         Ok(length) => length,
         Err(err) => {
-            with_service(|service| service.update_last_error_str(err));
+            update_last_error(&err);
             0
         }
     }
@@ -38,6 +39,8 @@ pub unsafe extern "C" fn vm_exec_instance_memory_data_length(
 /// Note that when the memory grows, it can be reallocated, and thus
 /// the returned pointer can be invalidated.
 ///
+/// Writes through the pointer must be reported with `vm_exec_instance_mark_memory_written`.
+///
 /// # Safety
 ///
 /// C API function, works with raw object pointers.
@@ -56,12 +59,39 @@ pub unsafe extern "C" fn vm_exec_instance_memory_data(
     match result {
         Ok(data) => data,
         Err(err) => {
-            with_service(|service| service.update_last_error_str(err));
+            update_last_error(&err);
             ptr::null_mut()
         }
     }
 }
 
+/// Records that the caller wrote `mem_length` bytes at `mem_ptr`, through the pointer given by
+/// `vm_exec_instance_memory_data`, so that `vm_exec_instance_reset_changed_pages` restores them.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_instance_mark_memory_written(
+    instance_ptr: *mut vm_exec_instance_t,
+    mem_ptr: u32,
+    mem_length: u32,
+) -> vm_exec_result_t {
+    let capi_instance = cast_input_ptr!(instance_ptr, CapiInstance, "instance ptr is null");
+    let result = capi_instance
+        .content
+        .mark_memory_written(mem_ptr as MemPtr, mem_length as MemLength);
+
+    match result {
+        Ok(_) => vm_exec_result_t::VM_EXEC_OK,
+        Err(err) => {
+            update_last_error(&err);
+            vm_exec_result_t::VM_EXEC_ERROR
+        }
+    }
+}
+
 /// Grows a memory by the given number of pages (of 65Kb each).
 ///
 /// The functions return `wasmer_result_t::WASMER_OK` upon success,
@@ -84,7 +114,7 @@ pub unsafe extern "C" fn vm_exec_instance_memory_grow(
     match grow_result {
         Ok(_) => vm_exec_result_t::VM_EXEC_OK,
         Err(grow_error) => {
-            with_service(|service| service.update_last_error_str(grow_error.to_string()));
+            update_last_error(&grow_error);
             vm_exec_result_t::VM_EXEC_ERROR
         }
     }
@@ -1,13 +1,30 @@
+use crate::capi_error::{update_last_error, update_last_error_message, vm_exec_error_code_t};
 use crate::capi_executor::{vm_exec_executor_t, CapiExecutor};
 use crate::capi_instance::{vm_exec_instance_t, CapiInstance};
-use crate::service_singleton::with_service;
-use crate::vm_exec_result_t;
-use klever_chain_vm_executor::OpcodeCost;
+use crate::{string_copy, string_length, vm_exec_result_t};
+use klever_chain_vm_executor::{
+    BulkMemoryCost, ExecutorError, GasProfileEntry, GasSchedule, OpcodeCost, OpcodeHistogram,
+    DEFAULT_GAS_SCHEDULE_VERSION,
+};
+use libc::{c_char, c_int};
+use std::ffi::CStr;
 
 #[allow(non_camel_case_types)]
 #[repr(C)]
 pub struct vm_exec_opcode_cost_t;
 
+/// Opaque pointer to the costs of the bulk memory operators.
+///
+/// The caller lays out 7 `uint32_t` fields, in this order: data_drop, memory_copy,
+/// memory_copy_per_byte, memory_fill, memory_fill_per_byte, memory_init and
+/// memory_init_per_byte. Further costs will come with a new version of the struct and of the
+/// functions that take it.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub struct vm_exec_bulk_memory_cost_v1_t;
+
+const _: () = assert!(std::mem::size_of::<BulkMemoryCost>() == 28);
+
 /// Sets the opcode costs for the given executor.
 ///
 /// This function returns `vm_exec_result_t::WASMER_OK` upon success,
@@ -30,12 +47,175 @@ pub unsafe extern "C" fn vm_exec_set_opcode_costs(
     match result {
         Ok(()) => vm_exec_result_t::VM_EXEC_OK,
         Err(message) => {
-            with_service(|service| service.update_last_error_str(message.to_string()));
+            update_last_error(&message);
             vm_exec_result_t::VM_EXEC_ERROR
         }
     }
 }
 
+/// Sets the costs of the bulk memory operators for the given executor.
+///
+/// Until they are set, contracts that use bulk memory operators are rejected.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_set_bulk_memory_costs_v1(
+    executor_ptr: *mut vm_exec_executor_t,
+    bulk_memory_cost_ptr: *const vm_exec_bulk_memory_cost_v1_t,
+) -> vm_exec_result_t {
+    let capi_executor = cast_input_ptr!(executor_ptr, CapiExecutor, "executor ptr is null");
+    return_if_ptr_null!(bulk_memory_cost_ptr, "bulk memory cost ptr is null");
+    let bulk_memory_cost: &BulkMemoryCost = &*(bulk_memory_cost_ptr as *const BulkMemoryCost);
+
+    let result = capi_executor.content.set_bulk_memory_cost(bulk_memory_cost);
+    match result {
+        Ok(()) => vm_exec_result_t::VM_EXEC_OK,
+        Err(message) => {
+            update_last_error(&message);
+            vm_exec_result_t::VM_EXEC_ERROR
+        }
+    }
+}
+
+/// Registers opcode costs under a new gas schedule version, active from the given epoch on.
+///
+/// Contracts are compiled with a registered schedule by setting its version in the
+/// compilation options. Contracts that use bulk memory operators are rejected under this
+/// schedule, see `vm_exec_executor_register_gas_schedule_with_bulk_memory_v1`.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_executor_register_gas_schedule(
+    executor_ptr: *mut vm_exec_executor_t,
+    name_ptr: *const c_char,
+    version: u32,
+    activation_epoch: u64,
+    opcode_cost_ptr: *const vm_exec_opcode_cost_t,
+) -> vm_exec_result_t {
+    register_gas_schedule(
+        executor_ptr,
+        name_ptr,
+        version,
+        activation_epoch,
+        opcode_cost_ptr,
+        None,
+    )
+}
+
+/// Registers opcode costs and bulk memory operator costs under a new gas schedule version,
+/// active from the given epoch on.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_executor_register_gas_schedule_with_bulk_memory_v1(
+    executor_ptr: *mut vm_exec_executor_t,
+    name_ptr: *const c_char,
+    version: u32,
+    activation_epoch: u64,
+    opcode_cost_ptr: *const vm_exec_opcode_cost_t,
+    bulk_memory_cost_ptr: *const vm_exec_bulk_memory_cost_v1_t,
+) -> vm_exec_result_t {
+    return_if_ptr_null!(bulk_memory_cost_ptr, "bulk memory cost ptr is null");
+    let bulk_memory_cost: &BulkMemoryCost = &*(bulk_memory_cost_ptr as *const BulkMemoryCost);
+    register_gas_schedule(
+        executor_ptr,
+        name_ptr,
+        version,
+        activation_epoch,
+        opcode_cost_ptr,
+        Some(*bulk_memory_cost),
+    )
+}
+
+#[allow(clippy::cast_ptr_alignment)]
+unsafe fn register_gas_schedule(
+    executor_ptr: *mut vm_exec_executor_t,
+    name_ptr: *const c_char,
+    version: u32,
+    activation_epoch: u64,
+    opcode_cost_ptr: *const vm_exec_opcode_cost_t,
+    bulk_memory_cost: Option<BulkMemoryCost>,
+) -> vm_exec_result_t {
+    let capi_executor = cast_input_ptr!(executor_ptr, CapiExecutor, "executor ptr is null");
+    return_if_ptr_null!(opcode_cost_ptr, "opcode cost ptr is null");
+    if name_ptr.is_null() {
+        update_last_error_message(
+            "name ptr is null".to_string(),
+            vm_exec_error_code_t::VM_EXEC_ERROR_CODE_NULL_POINTER,
+        );
+        return vm_exec_result_t::VM_EXEC_ERROR;
+    }
+    let name = CStr::from_ptr(name_ptr).to_string_lossy().into_owned();
+    let opcode_cost: &OpcodeCost = &*(opcode_cost_ptr as *const OpcodeCost);
+
+    let result = capi_executor.content.register_gas_schedule(GasSchedule {
+        name,
+        version,
+        activation_epoch,
+        opcode_cost: opcode_cost.clone(),
+        bulk_memory_cost,
+    });
+    match result {
+        Ok(()) => vm_exec_result_t::VM_EXEC_OK,
+        Err(message) => {
+            update_last_error(&message);
+            vm_exec_result_t::VM_EXEC_ERROR
+        }
+    }
+}
+
+/// Returns the version of the registered gas schedule active at the given epoch.
+///
+/// Returns the default version, 0, if no registered schedule is active yet.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_executor_gas_schedule_version_at_epoch(
+    executor_ptr: *const vm_exec_executor_t,
+    epoch: u64,
+) -> u32 {
+    let capi_executor = cast_input_const_ptr!(
+        executor_ptr,
+        CapiExecutor,
+        "executor ptr is null",
+        DEFAULT_GAS_SCHEDULE_VERSION
+    );
+    capi_executor
+        .content
+        .gas_schedule_version_at_epoch(epoch)
+        .unwrap_or(DEFAULT_GAS_SCHEDULE_VERSION)
+}
+
+/// Returns the version of the gas schedule the given instance was compiled with.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_instance_gas_schedule_version(
+    instance_ptr: *const vm_exec_instance_t,
+) -> u32 {
+    let capi_instance = cast_input_const_ptr!(
+        instance_ptr,
+        CapiInstance,
+        "instance ptr is null",
+        DEFAULT_GAS_SCHEDULE_VERSION
+    );
+    capi_instance.content.gas_schedule_version()
+}
+
 /// Sets the number of points(gas) limit for the given instance.
 ///
 /// This function returns `vm_exec_result_t::WASMER_OK` upon success,
@@ -56,7 +236,7 @@ pub unsafe extern "C" fn vm_exec_instance_set_points_limit(
     match result {
         Ok(()) => vm_exec_result_t::VM_EXEC_OK,
         Err(message) => {
-            with_service(|service| service.update_last_error_str(message));
+            update_last_error(&message);
             vm_exec_result_t::VM_EXEC_ERROR
         }
     }
@@ -82,7 +262,7 @@ pub unsafe extern "C" fn vm_exec_instance_set_points_used(
     match result {
         Ok(()) => vm_exec_result_t::VM_EXEC_OK,
         Err(message) => {
-            with_service(|service| service.update_last_error_str(message));
+            update_last_error(&message);
             vm_exec_result_t::VM_EXEC_ERROR
         }
     }
@@ -104,8 +284,167 @@ pub unsafe extern "C" fn vm_exec_instance_get_points_used(
     match result {
         Ok(points) => points,
         Err(message) => {
-            with_service(|service| service.update_last_error_str(message));
+            update_last_error(&message);
             0
         }
     }
 }
+
+fn gas_profile_string(gas_profile: &[GasProfileEntry]) -> String {
+    let entries: Vec<String> = gas_profile
+        .iter()
+        .map(|entry| {
+            format!(
+                "{}:{}:{}:{}",
+                entry.function_index,
+                entry.name.as_deref().unwrap_or_default(),
+                entry.gas,
+                entry.call_count
+            )
+        })
+        .collect();
+    entries.join("|")
+}
+
+fn instance_gas_profile(capi_instance: &CapiInstance) -> Result<String, ExecutorError> {
+    capi_instance
+        .content
+        .gas_profile()
+        .map(|gas_profile| gas_profile_string(&gas_profile))
+}
+
+/// Returns the buffer length needed by `vm_exec_instance_gas_profile`, `-1` if the profile
+/// cannot be read, e.g. because gas profiling is disabled for the given instance.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_instance_gas_profile_length(
+    instance_ptr: *const vm_exec_instance_t,
+) -> c_int {
+    let capi_instance =
+        cast_input_const_ptr!(instance_ptr, CapiInstance, "instance ptr is null", -1);
+    match instance_gas_profile(capi_instance) {
+        Ok(gas_profile) => string_length(gas_profile),
+        Err(err) => {
+            update_last_error(&err);
+            -1
+        }
+    }
+}
+
+/// Returns the gas profile of the given instance, one entry per function defined by the contract,
+/// separated by pipes.
+///
+/// Entries look like `3:transfer:1200:2`, for the function index, the function name,
+/// the gas used and the call count. The name is empty if the contract has no name section.
+///
+/// It is necessary to first call `vm_exec_instance_gas_profile_length` and pre-allocate a buffer of this length.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_instance_gas_profile(
+    instance_ptr: *const vm_exec_instance_t,
+    dest_buffer: *mut c_char,
+    dest_buffer_len: c_int,
+) -> c_int {
+    let capi_instance =
+        cast_input_const_ptr!(instance_ptr, CapiInstance, "instance ptr is null", -1);
+    match instance_gas_profile(capi_instance) {
+        Ok(gas_profile) => string_copy(gas_profile, dest_buffer, dest_buffer_len),
+        Err(err) => {
+            update_last_error(&err);
+            -1
+        }
+    }
+}
+
+fn opcode_histogram_string(opcode_histogram: &OpcodeHistogram) -> String {
+    let entries: Vec<String> = opcode_histogram
+        .counts
+        .iter()
+        .map(|(opcode_class, count)| format!("{opcode_class}:{count}"))
+        .collect();
+    entries.join("|")
+}
+
+fn instance_opcode_histogram(capi_instance: &CapiInstance) -> Result<String, ExecutorError> {
+    capi_instance
+        .content
+        .opcode_histogram()
+        .map(|opcode_histogram| opcode_histogram_string(&opcode_histogram))
+}
+
+/// Returns the buffer length needed by `vm_exec_instance_opcode_histogram`, `-1` if the histogram
+/// cannot be read, e.g. because it is disabled for the given instance.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_instance_opcode_histogram_length(
+    instance_ptr: *const vm_exec_instance_t,
+) -> c_int {
+    let capi_instance =
+        cast_input_const_ptr!(instance_ptr, CapiInstance, "instance ptr is null", -1);
+    match instance_opcode_histogram(capi_instance) {
+        Ok(opcode_histogram) => string_length(opcode_histogram),
+        Err(err) => {
+            update_last_error(&err);
+            -1
+        }
+    }
+}
+
+/// Returns the number of executed operators of the given instance by class, separated by pipes.
+///
+/// Entries look like `load:120`. The classes that were never executed are left out.
+///
+/// It is necessary to first call `vm_exec_instance_opcode_histogram_length` and pre-allocate a buffer of this length.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_instance_opcode_histogram(
+    instance_ptr: *const vm_exec_instance_t,
+    dest_buffer: *mut c_char,
+    dest_buffer_len: c_int,
+) -> c_int {
+    let capi_instance =
+        cast_input_const_ptr!(instance_ptr, CapiInstance, "instance ptr is null", -1);
+    match instance_opcode_histogram(capi_instance) {
+        Ok(opcode_histogram) => string_copy(opcode_histogram, dest_buffer, dest_buffer_len),
+        Err(err) => {
+            update_last_error(&err);
+            -1
+        }
+    }
+}
+
+/// Sets all the counters of the opcode histogram of the given instance back to zero.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_instance_reset_opcode_histogram(
+    instance_ptr: *const vm_exec_instance_t,
+) -> vm_exec_result_t {
+    let capi_instance = cast_input_const_ptr!(instance_ptr, CapiInstance, "instance ptr is null");
+    match capi_instance.content.reset_opcode_histogram() {
+        Ok(()) => vm_exec_result_t::VM_EXEC_OK,
+        Err(err) => {
+            update_last_error(&err);
+            vm_exec_result_t::VM_EXEC_ERROR
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Inspect a contract without instantiating it.
+
+use crate::{
+    capi_error::update_last_error,
+    capi_executor::{vm_exec_executor_t, CapiExecutor},
+    string_copy, string_length, vm_exec_result_t,
+};
+use klever_chain_vm_executor::ModuleInfo;
+use libc::{c_char, c_int};
+use std::slice;
+
+#[repr(C)]
+pub struct vm_exec_module_info_t;
+
+pub struct CapiModuleInfo {
+    pub(crate) content: ModuleInfo,
+}
+
+/// The scalar facts about a contract.
+///
+/// The memory fields describe the first memory of the contract, they are zero if it has none.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub struct vm_exec_module_summary_t {
+    pub import_count: u32,
+    pub export_count: u32,
+    pub function_count: u32,
+    pub memory_count: u32,
+    pub memory_min_pages: u64,
+    pub memory_max_pages: u64,
+    pub memory_has_max: bool,
+    pub max_memory_pages_allowed: u64,
+    pub memory_within_limits: bool,
+    pub table_count: u32,
+    pub data_segment_count: u32,
+    pub data_segment_bytes: u64,
+    pub has_start_function: bool,
+    pub custom_section_count: u32,
+}
+
+/// The lists of names that can be read from a `vm_exec_module_info_t`.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub enum vm_exec_module_info_list_t {
+    /// Entries look like `env.getNumArguments:function:[] -> [I32]`.
+    VM_EXEC_MODULE_IMPORTS = 1,
+
+    /// Entries look like `init:function`.
+    VM_EXEC_MODULE_EXPORTS = 2,
+
+    VM_EXEC_MODULE_CUSTOM_SECTIONS = 3,
+}
+
+fn module_info_list(module_info: &ModuleInfo, list: vm_exec_module_info_list_t) -> String {
+    let entries: Vec<String> = match list {
+        vm_exec_module_info_list_t::VM_EXEC_MODULE_IMPORTS => module_info
+            .imports
+            .iter()
+            .map(|import| match &import.signature {
+                Some(signature) => format!(
+                    "{}.{}:{}:{}",
+                    import.namespace, import.name, import.kind, signature
+                ),
+                None => format!("{}.{}:{}", import.namespace, import.name, import.kind),
+            })
+            .collect(),
+        vm_exec_module_info_list_t::VM_EXEC_MODULE_EXPORTS => module_info
+            .exports
+            .iter()
+            .map(|export| format!("{}:{}", export.name, export.kind))
+            .collect(),
+        vm_exec_module_info_list_t::VM_EXEC_MODULE_CUSTOM_SECTIONS => {
+            module_info.custom_sections.clone()
+        }
+    };
+    entries.join("|")
+}
+
+/// Gathers facts about a contract, without compiling or instantiating it.
+///
+/// The memory of the contract is checked against `max_memory_pages`, the limit set in the
+/// compilation options it is going to be instantiated with.
+///
+/// The result must be freed with `vm_exec_module_info_destroy`.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_executor_inspect_module(
+    executor_ptr: *mut vm_exec_executor_t,
+    wasm_bytes_ptr: *mut u8,
+    wasm_bytes_len: u32,
+    max_memory_pages: u64,
+    module_info_ptr_ptr: *mut *mut vm_exec_module_info_t,
+) -> vm_exec_result_t {
+    let capi_executor = cast_input_ptr!(executor_ptr, CapiExecutor, "executor ptr is null");
+    return_if_ptr_null!(wasm_bytes_ptr, "wasm bytes ptr is null");
+    return_if_ptr_null!(module_info_ptr_ptr, "module info ptr is null");
+
+    let wasm_bytes: &[u8] = slice::from_raw_parts(wasm_bytes_ptr, wasm_bytes_len as usize);
+    match capi_executor
+        .content
+        .inspect_module(wasm_bytes, max_memory_pages)
+    {
+        Ok(module_info) => {
+            let capi_module_info = CapiModuleInfo {
+                content: module_info,
+            };
+            *module_info_ptr_ptr =
+                Box::into_raw(Box::new(capi_module_info)) as *mut vm_exec_module_info_t;
+            vm_exec_result_t::VM_EXEC_OK
+        }
+        Err(message) => {
+            update_last_error(&message);
+            vm_exec_result_t::VM_EXEC_ERROR
+        }
+    }
+}
+
+/// Fills in the scalar facts about an inspected contract.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_module_info_summary(
+    module_info_ptr: *const vm_exec_module_info_t,
+    summary_ptr: *mut vm_exec_module_summary_t,
+) -> vm_exec_result_t {
+    let capi_module_info =
+        cast_input_const_ptr!(module_info_ptr, CapiModuleInfo, "module info ptr is null");
+    return_if_ptr_null!(summary_ptr, "summary ptr is null");
+
+    let module_info = &capi_module_info.content;
+    let first_memory = module_info.memories.first();
+    *summary_ptr = vm_exec_module_summary_t {
+        import_count: module_info.imports.len() as u32,
+        export_count: module_info.exports.len() as u32,
+        function_count: module_info.function_count as u32,
+        memory_count: module_info.memories.len() as u32,
+        memory_min_pages: first_memory.map_or(0, |memory| memory.min_pages),
+        memory_max_pages: first_memory
+            .and_then(|memory| memory.max_pages)
+            .unwrap_or(0),
+        memory_has_max: first_memory.is_some_and(|memory| memory.max_pages.is_some()),
+        max_memory_pages_allowed: module_info.max_memory_pages_allowed,
+        memory_within_limits: module_info.memory_within_limits(),
+        table_count: module_info.tables.len() as u32,
+        data_segment_count: module_info.data_segment_count as u32,
+        data_segment_bytes: module_info.data_segment_bytes as u64,
+        has_start_function: module_info.has_start_function,
+        custom_section_count: module_info.custom_sections.len() as u32,
+    };
+    vm_exec_result_t::VM_EXEC_OK
+}
+
+/// Returns the buffer length needed by `vm_exec_module_info_list`.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_module_info_list_length(
+    module_info_ptr: *const vm_exec_module_info_t,
+    list: vm_exec_module_info_list_t,
+) -> c_int {
+    let capi_module_info = cast_input_const_ptr!(
+        module_info_ptr,
+        CapiModuleInfo,
+        "module info ptr is null",
+        0
+    );
+    string_length(module_info_list(&capi_module_info.content, list))
+}
+
+/// Returns the entries of one of the lists of an inspected contract, separated by pipes.
+///
+/// It is necessary to first call `vm_exec_module_info_list_length` and pre-allocate a buffer of this length.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_module_info_list(
+    module_info_ptr: *const vm_exec_module_info_t,
+    list: vm_exec_module_info_list_t,
+    dest_buffer: *mut c_char,
+    dest_buffer_len: c_int,
+) -> c_int {
+    let capi_module_info = cast_input_const_ptr!(
+        module_info_ptr,
+        CapiModuleInfo,
+        "module info ptr is null",
+        0
+    );
+    string_copy(
+        module_info_list(&capi_module_info.content, list),
+        dest_buffer,
+        dest_buffer_len,
+    )
+}
+
+/// Frees memory for the given `vm_exec_module_info_t`.
+///
+/// If `module_info_ptr` is a null pointer, this function does nothing.
+///
+/// # Safety
+///
+/// C API function, works with raw object pointers.
+#[allow(clippy::cast_ptr_alignment)]
+#[no_mangle]
+pub unsafe extern "C" fn vm_exec_module_info_destroy(module_info_ptr: *mut vm_exec_module_info_t) {
+    if !module_info_ptr.is_null() {
+        let module_info = Box::from_raw(module_info_ptr as *mut CapiModuleInfo);
+        drop(module_info);
+    }
+}
@@ -3,13 +3,18 @@ mod macros;
 
 mod basic_types;
 pub mod capi_breakpoints;
+pub mod capi_call_report;
+pub mod capi_compilation_options;
 pub mod capi_error;
+pub mod capi_execution_trace;
 pub mod capi_executor;
 pub mod capi_instance;
 pub mod capi_instance_cache;
+pub mod capi_instance_snapshot;
 pub mod capi_logger;
 pub mod capi_memory;
 pub mod capi_metering;
+pub mod capi_module_info;
 pub mod capi_vm_hook_pointers;
 pub mod capi_vm_hooks;
 pub mod service_singleton;
@@ -1,7 +1,10 @@
 macro_rules! return_if_ptr_null {
     ($ptr_var:ident, $err_msg:expr, $err_return_val:expr) => {
         if $ptr_var.is_null() {
-            with_service(|service| service.update_last_error_str($err_msg.to_string()));
+            crate::capi_error::update_last_error_message(
+                $err_msg.to_string(),
+                crate::capi_error::vm_exec_error_code_t::VM_EXEC_ERROR_CODE_NULL_POINTER,
+            );
             return $err_return_val;
         }
     };
@@ -13,7 +16,10 @@ macro_rules! return_if_ptr_null {
 macro_rules! cast_input_ptr {
     ($ptr_var:ident, $expected_ty:ty, $err_msg:expr, $err_return_val:expr) => {
         if $ptr_var.is_null() {
-            with_service(|service| service.update_last_error_str($err_msg.to_string()));
+            crate::capi_error::update_last_error_message(
+                $err_msg.to_string(),
+                crate::capi_error::vm_exec_error_code_t::VM_EXEC_ERROR_CODE_NULL_POINTER,
+            );
             return $err_return_val;
         } else {
             &mut *($ptr_var as *mut $expected_ty)
@@ -32,7 +38,10 @@ macro_rules! cast_input_ptr {
 macro_rules! cast_input_const_ptr {
     ($ptr_var:ident, $expected_ty:ty, $err_msg:expr, $err_return_val:expr) => {
         if $ptr_var.is_null() {
-            with_service(|service| service.update_last_error_str($err_msg.to_string()));
+            crate::capi_error::update_last_error_message(
+                $err_msg.to_string(),
+                crate::capi_error::vm_exec_error_code_t::VM_EXEC_ERROR_CODE_NULL_POINTER,
+            );
             return $err_return_val;
         } else {
             &*($ptr_var as *const $expected_ty)
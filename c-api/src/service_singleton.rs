@@ -2,6 +2,8 @@ use klever_chain_vm_executor::ExecutorService;
 use klever_chain_vm_executor_wasmer::BasicExecutorService;
 use std::cell::RefCell;
 
+// The service holds the last error, which is reported per thread, like `errno`.
+// The executors it creates are not tied to the thread and can be used from any of them.
 thread_local! {
     static SERVICE: RefCell<Box<dyn ExecutorService>> = RefCell::new(Box::new(BasicExecutorService::new()));
 }
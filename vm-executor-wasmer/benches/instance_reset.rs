@@ -0,0 +1,82 @@
+//! Compares the full instance reset with the reset of the changed pages.
+//!
+//! Run with `cargo bench -p klever-chain-vm-executor-wasmer --bench instance_reset`.
+
+use klever_chain_vm_executor::{
+    CompilationOptions, CompilerBackend, ExecutorService, Instance, MemPtr, VMHooksDefault,
+    DEFAULT_EXECUTION_TRACE_LENGTH, DEFAULT_GAS_SCHEDULE_VERSION,
+};
+use klever_chain_vm_executor_wasmer::BasicExecutorService;
+use std::time::{Duration, Instant};
+use wasmer::wat2wasm;
+
+const MEMORY_PAGES: u32 = 16;
+const PAGE_SIZE: MemPtr = 65536;
+const ITERATIONS: u32 = 1000;
+
+const COMPILATION_OPTIONS: CompilationOptions = CompilationOptions {
+    gas_limit: u64::MAX,
+    unmetered_locals: 0,
+    max_memory_grow: 0,
+    max_memory_grow_delta: 0,
+    opcode_trace: false,
+    metering: true,
+    runtime_breakpoints: true,
+    compiler: CompilerBackend::Singlepass as u8,
+    max_memory_pages: MEMORY_PAGES as u64,
+    gas_schedule_version: DEFAULT_GAS_SCHEDULE_VERSION,
+    gas_profiling: false,
+    opcode_histogram: false,
+    execution_trace: false,
+    snapshots: false,
+    execution_trace_length: DEFAULT_EXECUTION_TRACE_LENGTH,
+};
+
+fn new_instance() -> Box<dyn Instance> {
+    let wat = format!(
+        r#"(module
+            (memory (;0;) {MEMORY_PAGES})
+            (export "memory" (memory 0))
+            (data (i32.const 0) "initial data"))"#
+    );
+    let wasm_bytes = wat2wasm(wat.as_bytes()).unwrap();
+    let executor = BasicExecutorService::new()
+        .new_executor(Box::new(VMHooksDefault))
+        .unwrap();
+    executor
+        .new_instance(&wasm_bytes, &COMPILATION_OPTIONS)
+        .unwrap()
+}
+
+fn bench_reset(
+    instance: &dyn Instance,
+    changed_pages: u32,
+    reset: impl Fn(&dyn Instance),
+) -> Duration {
+    let mut total = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        for page in 0..changed_pages as MemPtr {
+            instance.memory_store(page * PAGE_SIZE, b"dirty").unwrap();
+        }
+        let start = Instant::now();
+        reset(instance);
+        total += start.elapsed();
+    }
+    total / ITERATIONS
+}
+
+fn main() {
+    let instance = new_instance();
+    println!("{MEMORY_PAGES} pages of memory, average over {ITERATIONS} resets");
+    for changed_pages in [0, 1, 4, MEMORY_PAGES] {
+        let full = bench_reset(instance.as_ref(), changed_pages, |instance| {
+            instance.reset().unwrap();
+        });
+        let changed = bench_reset(instance.as_ref(), changed_pages, |instance| {
+            instance.reset_changed_pages().unwrap();
+        });
+        println!(
+            "{changed_pages:>3} changed pages: reset {full:>10.2?}, reset_changed_pages {changed:>10.2?}"
+        );
+    }
+}
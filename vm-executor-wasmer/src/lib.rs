@@ -1,19 +1,32 @@
 mod wasmer_breakpoints;
+mod wasmer_cache_artifact;
+mod wasmer_execution_trace;
 mod wasmer_executor;
+mod wasmer_gas_profiling;
 mod wasmer_helpers;
+mod wasmer_import_validation;
 mod wasmer_imports;
+mod wasmer_initial_memory;
 mod wasmer_instance;
+mod wasmer_instance_pool;
 mod wasmer_logger;
 mod wasmer_metering;
 mod wasmer_metering_helpers;
+mod wasmer_module_cache;
+mod wasmer_module_info;
 mod wasmer_opcode_control;
+mod wasmer_opcode_histogram;
 mod wasmer_opcode_trace;
 mod wasmer_protected_globals;
+mod wasmer_reset_tracking;
 mod wasmer_service;
+mod wasmer_snapshot_globals;
+mod wasmer_validation;
 mod wasmer_vm_hooks;
 
 pub use wasmer_executor::*;
 pub use wasmer_instance::*;
+pub use wasmer_instance_pool::*;
 pub use wasmer_logger::*;
 pub use wasmer_metering_helpers::*;
 pub use wasmer_service::*;
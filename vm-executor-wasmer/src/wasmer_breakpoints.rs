@@ -1,6 +1,7 @@
 use std::mem;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
+use klever_chain_vm_executor::ExecutorError;
 use loupe::{MemoryUsage, MemoryUsageTracker};
 use wasmer::wasmparser::{Operator, Type as WpType, TypeOrFuncType as WpTypeOrFuncType};
 use wasmer::{
@@ -10,7 +11,8 @@ use wasmer::{
 use wasmer_types::{GlobalIndex, ModuleInfo};
 
 use crate::wasmer_helpers::{
-    create_global_index, is_control_flow_operator, MiddlewareWithProtectedGlobals,
+    create_global_index, inject_trap_condition, is_control_flow_operator,
+    MiddlewareWithProtectedGlobals,
 };
 
 const BREAKPOINT_VALUE: &str = "breakpoint_value";
@@ -65,9 +67,6 @@ impl Breakpoints {
     }
 }
 
-unsafe impl Send for Breakpoints {}
-unsafe impl Sync for Breakpoints {}
-
 impl MemoryUsage for Breakpoints {
     fn size_of_val(&self, tracker: &mut dyn MemoryUsageTracker) -> usize {
         mem::size_of_val(self) + self.global_index.size_of_val(tracker)
@@ -146,30 +145,45 @@ impl FunctionMiddleware for FunctionBreakpoints {
     }
 }
 
-pub(crate) fn set_breakpoint_value(instance: &Instance, value: u64) -> Result<(), String> {
+/// Injects a breakpoint condition when the runtime breakpoints middleware is present,
+/// otherwise falls back to trapping immediately on the same condition.
+pub(crate) fn inject_breakpoint_condition_or_trap(
+    breakpoints_middleware: &Option<Arc<Breakpoints>>,
+    state: &mut MiddlewareReaderState,
+    breakpoint_value: u64,
+) {
+    match breakpoints_middleware {
+        Some(breakpoints_middleware) => {
+            breakpoints_middleware.inject_breakpoint_condition(state, breakpoint_value)
+        }
+        None => inject_trap_condition(state),
+    }
+}
+
+pub(crate) fn set_breakpoint_value(instance: &Instance, value: u64) -> Result<(), ExecutorError> {
     let result = instance.exports.get_global(BREAKPOINT_VALUE);
     match result {
         Ok(global) => {
             let result = global.set(value.into());
             match result {
                 Ok(_) => Ok(()),
-                Err(err) => Err(err.message()),
+                Err(err) => Err(ExecutorError::Runtime(err.message())),
             }
         }
-        Err(err) => Err(err.to_string()),
+        Err(err) => Err(ExecutorError::Runtime(err.to_string())),
     }
 }
 
-pub(crate) fn get_breakpoint_value(instance: &Instance) -> Result<u64, String> {
+pub(crate) fn get_breakpoint_value(instance: &Instance) -> Result<u64, ExecutorError> {
     let result = instance.exports.get_global(BREAKPOINT_VALUE);
     match result {
         Ok(global) => {
             let result = global.get().try_into();
             match result {
                 Ok(value) => Ok(value),
-                Err(err) => Err(err.to_string()),
+                Err(err) => Err(ExecutorError::Runtime(err.to_string())),
             }
         }
-        Err(err) => Err(err.to_string()),
+        Err(err) => Err(ExecutorError::Runtime(err.to_string())),
     }
 }
@@ -0,0 +1,288 @@
+use crate::MiddlewareSettings;
+use klever_chain_vm_executor::{
+    BulkMemoryCost, CacheArtifactError, CompilationOptions, CompilerBackend, ExecutorError,
+    OpcodeCost,
+};
+use sha2::{Digest, Sha256};
+
+const CACHE_ARTIFACT_MAGIC: &[u8; 4] = b"KVMC";
+const CACHE_ARTIFACT_FORMAT_VERSION: u16 = 7;
+const EXECUTOR_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Wasmer expects the serialized module to be 16-byte aligned, the header is padded accordingly.
+const PAYLOAD_ALIGNMENT: usize = 16;
+
+/// Describes how a cached module was compiled.
+///
+/// The gas limit is recorded for reference only, since it is applied to each instance after
+/// instantiation and does not change the compiled code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct CacheHeader {
+    pub(crate) middleware_settings: MiddlewareSettings,
+    pub(crate) compiler: CompilerBackend,
+    pub(crate) gas_limit: u64,
+    pub(crate) unmetered_locals: u64,
+    pub(crate) max_memory_grow: u64,
+    pub(crate) max_memory_grow_delta: u64,
+    pub(crate) max_memory_pages: u64,
+    pub(crate) opcode_cost_hash: [u8; 32],
+    pub(crate) gas_schedule_version: u32,
+
+    /// Identifies the contract code, so that snapshots are only restored on the same contract.
+    pub(crate) code_hash: [u8; 32],
+}
+
+impl CacheHeader {
+    pub(crate) fn new(
+        wasm_bytes: &[u8],
+        compilation_options: &CompilationOptions,
+        middleware_settings: MiddlewareSettings,
+        opcode_cost: &OpcodeCost,
+        bulk_memory_cost: Option<&BulkMemoryCost>,
+    ) -> Result<Self, ExecutorError> {
+        Ok(Self {
+            middleware_settings,
+            compiler: compilation_options.compiler_backend()?,
+            gas_limit: compilation_options.gas_limit,
+            unmetered_locals: compilation_options.unmetered_locals as u64,
+            max_memory_grow: compilation_options.max_memory_grow as u64,
+            max_memory_grow_delta: compilation_options.max_memory_grow_delta as u64,
+            max_memory_pages: compilation_options.max_memory_pages,
+            opcode_cost_hash: opcode_cost_hash(opcode_cost, bulk_memory_cost),
+            gas_schedule_version: compilation_options.gas_schedule_version,
+            code_hash: Sha256::digest(wasm_bytes).into(),
+        })
+    }
+
+    /// Checks that a module compiled as described by this header can be used by an executor
+    /// configured with the given options and costs.
+    pub(crate) fn check_compatible(
+        &self,
+        compilation_options: &CompilationOptions,
+        opcode_cost: &OpcodeCost,
+        bulk_memory_cost: Option<&BulkMemoryCost>,
+    ) -> Result<(), CacheArtifactError> {
+        if self.compiler as u8 != compilation_options.compiler
+            || self.unmetered_locals != compilation_options.unmetered_locals as u64
+            || self.max_memory_grow != compilation_options.max_memory_grow as u64
+            || self.max_memory_grow_delta != compilation_options.max_memory_grow_delta as u64
+            || self.max_memory_pages != compilation_options.max_memory_pages
+        {
+            return Err(CacheArtifactError::CompilationOptionsMismatch);
+        }
+
+        // The opcode cost only ends up in the compiled code through the metering middleware
+        if !self.middleware_settings.metering {
+            return Ok(());
+        }
+        if self.gas_schedule_version != compilation_options.gas_schedule_version {
+            return Err(CacheArtifactError::GasScheduleMismatch {
+                cached: self.gas_schedule_version,
+                requested: compilation_options.gas_schedule_version,
+            });
+        }
+        if self.opcode_cost_hash != opcode_cost_hash(opcode_cost, bulk_memory_cost) {
+            return Err(CacheArtifactError::OpcodeCostMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Prefixes the serialized module with the header.
+    pub(crate) fn encode_artifact(&self, payload: &[u8]) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(payload.len() + 128);
+        bytes.extend_from_slice(CACHE_ARTIFACT_MAGIC);
+        bytes.extend_from_slice(&CACHE_ARTIFACT_FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(EXECUTOR_VERSION.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(EXECUTOR_VERSION.as_bytes());
+        bytes.push(self.middleware_settings.to_flags());
+        bytes.extend_from_slice(
+            &self
+                .middleware_settings
+                .execution_trace_length
+                .to_le_bytes(),
+        );
+        bytes.push(self.compiler as u8);
+        bytes.extend_from_slice(&self.gas_limit.to_le_bytes());
+        bytes.extend_from_slice(&self.unmetered_locals.to_le_bytes());
+        bytes.extend_from_slice(&self.max_memory_grow.to_le_bytes());
+        bytes.extend_from_slice(&self.max_memory_grow_delta.to_le_bytes());
+        bytes.extend_from_slice(&self.max_memory_pages.to_le_bytes());
+        bytes.extend_from_slice(&self.opcode_cost_hash);
+        bytes.extend_from_slice(&self.gas_schedule_version.to_le_bytes());
+        bytes.extend_from_slice(&self.code_hash);
+        bytes.extend_from_slice(&Sha256::digest(payload));
+        bytes.resize(bytes.len().next_multiple_of(PAYLOAD_ALIGNMENT), 0);
+        bytes.extend_from_slice(payload);
+        bytes
+    }
+
+    /// Splits a cached artifact into its header and the serialized module.
+    ///
+    /// The payload checksum is verified here, so the returned module bytes are exactly the ones
+    /// that were cached.
+    pub(crate) fn decode_artifact(bytes: &[u8]) -> Result<(Self, &[u8]), CacheArtifactError> {
+        let mut reader = ArtifactReader { bytes, pos: 0 };
+
+        if reader.take(CACHE_ARTIFACT_MAGIC.len())? != CACHE_ARTIFACT_MAGIC {
+            return Err(CacheArtifactError::BadMagic);
+        }
+
+        let format_version = reader.read_u16()?;
+        if format_version != CACHE_ARTIFACT_FORMAT_VERSION {
+            return Err(CacheArtifactError::UnsupportedFormatVersion(format_version));
+        }
+
+        let version_len = reader.read_u16()? as usize;
+        let executor_version = reader.take(version_len)?;
+        if executor_version != EXECUTOR_VERSION.as_bytes() {
+            return Err(CacheArtifactError::ExecutorVersionMismatch {
+                expected: EXECUTOR_VERSION.to_string(),
+                found: String::from_utf8_lossy(executor_version).into_owned(),
+            });
+        }
+
+        let flags = reader.read_u8()?;
+        let middleware_settings = MiddlewareSettings::from_flags(flags, reader.read_u32()?)?;
+        let compiler = match reader.read_u8()? {
+            0 => CompilerBackend::Singlepass,
+            1 => CompilerBackend::Cranelift,
+            unknown => return Err(CacheArtifactError::UnknownCompiler(unknown)),
+        };
+        let header = CacheHeader {
+            middleware_settings,
+            compiler,
+            gas_limit: reader.read_u64()?,
+            unmetered_locals: reader.read_u64()?,
+            max_memory_grow: reader.read_u64()?,
+            max_memory_grow_delta: reader.read_u64()?,
+            max_memory_pages: reader.read_u64()?,
+            opcode_cost_hash: reader.read_hash()?,
+            gas_schedule_version: reader.read_u32()?,
+            code_hash: reader.read_hash()?,
+        };
+        let payload_checksum = reader.read_hash()?;
+
+        reader.take(reader.pos.next_multiple_of(PAYLOAD_ALIGNMENT) - reader.pos)?;
+        let payload = &bytes[reader.pos..];
+        if Sha256::digest(payload).as_slice() != payload_checksum {
+            return Err(CacheArtifactError::ChecksumMismatch);
+        }
+
+        Ok((header, payload))
+    }
+}
+
+struct ArtifactReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ArtifactReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], CacheArtifactError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(CacheArtifactError::Truncated)?;
+        let taken = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(taken)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, CacheArtifactError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, CacheArtifactError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, CacheArtifactError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, CacheArtifactError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn read_hash(&mut self) -> Result<[u8; 32], CacheArtifactError> {
+        Ok(self.take(32)?.try_into().unwrap())
+    }
+}
+
+/// Hashes the costs from an explicit encoding, so that the hash stays the same between builds
+/// and compiler versions.
+///
+/// Each opcode is encoded as its name, prefixed by its length as a little-endian `u16`,
+/// followed by its cost as a little-endian `u32`, in the declaration order of `OpcodeCost`.
+/// The bulk memory costs follow, after a byte telling whether they are set.
+fn opcode_cost_hash(
+    opcode_cost: &OpcodeCost,
+    bulk_memory_cost: Option<&BulkMemoryCost>,
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for (name, cost) in opcode_cost.fields() {
+        hasher.update((name.len() as u16).to_le_bytes());
+        hasher.update(name.as_bytes());
+        hasher.update(cost.to_le_bytes());
+    }
+    match bulk_memory_cost {
+        Some(bulk_memory_cost) => {
+            hasher.update([1]);
+            for cost in [
+                bulk_memory_cost.data_drop,
+                bulk_memory_cost.memory_copy,
+                bulk_memory_cost.memory_copy_per_byte,
+                bulk_memory_cost.memory_fill,
+                bulk_memory_cost.memory_fill_per_byte,
+                bulk_memory_cost.memory_init,
+                bulk_memory_cost.memory_init_per_byte,
+            ] {
+                hasher.update(cost.to_le_bytes());
+            }
+        }
+        None => hasher.update([0]),
+    }
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Cached artifacts are shared between nodes, so the hash of given costs must never change.
+    #[test]
+    fn opcode_cost_hash_is_stable() {
+        let schedule = OpcodeCost::default()
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(index, (name, _))| format!("\"{name}\": {}", index + 1))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let opcode_cost = OpcodeCost::from_json(&format!("{{{schedule}}}")).unwrap();
+        let bulk_memory_cost = BulkMemoryCost {
+            data_drop: 1,
+            memory_copy: 2,
+            memory_copy_per_byte: 3,
+            memory_fill: 4,
+            memory_fill_per_byte: 5,
+            memory_init: 6,
+            memory_init_per_byte: 7,
+        };
+
+        let hash: String = opcode_cost_hash(&opcode_cost, Some(&bulk_memory_cost))
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        assert_eq!(
+            hash,
+            "84a8031de3ba9933c8300c33eaabea3e770effc5ae987cd72566c9808b1d0b4e"
+        );
+        assert_ne!(
+            opcode_cost_hash(&opcode_cost, None),
+            opcode_cost_hash(&opcode_cost, Some(&BulkMemoryCost::default()))
+        );
+    }
+}
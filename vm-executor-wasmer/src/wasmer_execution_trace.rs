@@ -0,0 +1,266 @@
+use std::mem;
+use std::sync::Mutex;
+
+use klever_chain_vm_executor::{ExecutionTraceEntry, ExecutorError, MAX_EXECUTION_TRACE_LENGTH};
+use loupe::{MemoryUsage, MemoryUsageTracker};
+use wasmer::wasmparser::{
+    BinaryReader, Operator, Type as WpType, TypeOrFuncType as WpTypeOrFuncType,
+};
+use wasmer::{
+    FunctionMiddleware, Instance, LocalFunctionIndex, MiddlewareReaderState, ModuleMiddleware,
+};
+use wasmer_types::{GlobalIndex, ModuleInfo};
+
+use crate::wasmer_helpers::{
+    create_global_index, is_control_flow_operator, MiddlewareWithProtectedGlobals,
+};
+
+const EXECUTION_TRACE_PREFIX: &str = "execution_trace_";
+const EXECUTION_TRACE_NEXT_SLOT: &str = "execution_trace_next_slot";
+
+/// Marks the slots that hold no block yet.
+const EXECUTION_TRACE_EMPTY_SLOT: u64 = u64::MAX;
+
+const MAX_LENGTH: usize = MAX_EXECUTION_TRACE_LENGTH as usize;
+
+/// The encoded `br_table` dispatching on the next slot, for each trace length.
+///
+/// Injected operators borrow the bytes they were read from, and `br_table` can only be read,
+/// so these live as long as the compiled code.
+static BR_TABLES: [[u8; MAX_LENGTH + 2]; MAX_LENGTH] = encode_br_tables();
+
+/// Encodes `br_table 0 1 .. length-2 length-1` for each length, the last target being the
+/// default. Lengths are at most 128, so every number fits in a single LEB128 byte.
+const fn encode_br_tables() -> [[u8; MAX_LENGTH + 2]; MAX_LENGTH] {
+    let mut br_tables = [[0; MAX_LENGTH + 2]; MAX_LENGTH];
+    let mut length = 1;
+    while length <= MAX_LENGTH {
+        br_tables[length - 1][0] = 0x0e;
+        br_tables[length - 1][1] = (length - 1) as u8;
+        let mut target = 0;
+        while target < length {
+            br_tables[length - 1][2 + target] = target as u8;
+            target += 1;
+        }
+        length += 1;
+    }
+    br_tables
+}
+
+/// Holds the last basic blocks entered, in a ring of globals, one per block kept.
+///
+/// The blocks are recorded by the metering middleware, which sees the operators before any other
+/// middleware injects its own, so that operator indexes refer to the contract as written.
+/// Each block is written to the slot after the previous one, picked with a `br_table`, so
+/// recording a block takes the same few operators whatever the length of the trace.
+#[derive(Debug)]
+pub(crate) struct ExecutionTrace {
+    length: u32,
+    global_indexes: Mutex<Option<ExecutionTraceGlobalIndexes>>,
+}
+
+#[derive(Clone, Debug, MemoryUsage)]
+struct ExecutionTraceGlobalIndexes {
+    slots: Vec<GlobalIndex>,
+    next_slot: GlobalIndex,
+}
+
+impl ExecutionTrace {
+    pub(crate) fn new(length: u32) -> Self {
+        assert!((1..=MAX_EXECUTION_TRACE_LENGTH).contains(&length));
+        Self {
+            length,
+            global_indexes: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn function_execution_trace(
+        &self,
+        local_function_index: LocalFunctionIndex,
+    ) -> FunctionExecutionTrace {
+        let br_table = BinaryReader::new(&BR_TABLES[self.length as usize - 1])
+            .read_operator()
+            .expect("br_table is encoded for every length");
+        FunctionExecutionTrace {
+            global_indexes: self.global_indexes.lock().unwrap().clone().unwrap(),
+            br_table,
+            function_index: local_function_index.as_u32(),
+            operator_index: 0,
+            block_started: true,
+        }
+    }
+}
+
+impl MemoryUsage for ExecutionTrace {
+    fn size_of_val(&self, tracker: &mut dyn MemoryUsageTracker) -> usize {
+        mem::size_of_val(self) + self.global_indexes.size_of_val(tracker)
+            - mem::size_of_val(&self.global_indexes)
+    }
+}
+
+impl ModuleMiddleware for ExecutionTrace {
+    fn generate_function_middleware(
+        &self,
+        _local_function_index: LocalFunctionIndex,
+    ) -> Box<dyn FunctionMiddleware> {
+        Box::new(FunctionExecutionTracing {})
+    }
+
+    fn transform_module_info(&self, module_info: &mut ModuleInfo) {
+        let mut global_indexes = self.global_indexes.lock().unwrap();
+
+        let slots = (0..self.length)
+            .map(|slot| {
+                create_global_index(
+                    module_info,
+                    &execution_trace_name(slot),
+                    EXECUTION_TRACE_EMPTY_SLOT as i64,
+                )
+            })
+            .collect();
+        let next_slot = create_global_index(module_info, EXECUTION_TRACE_NEXT_SLOT, 0);
+        *global_indexes = Some(ExecutionTraceGlobalIndexes { slots, next_slot });
+    }
+}
+
+impl MiddlewareWithProtectedGlobals for ExecutionTrace {
+    fn protected_globals(&self) -> Vec<u32> {
+        let global_indexes = self.global_indexes.lock().unwrap();
+        let global_indexes = global_indexes.as_ref().unwrap();
+        global_indexes
+            .slots
+            .iter()
+            .chain([&global_indexes.next_slot])
+            .map(|global_index| global_index.as_u32())
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+struct FunctionExecutionTracing {}
+
+impl FunctionMiddleware for FunctionExecutionTracing {}
+
+/// Finds the basic blocks of a function, and records each of them when it is entered.
+#[derive(Debug)]
+pub(crate) struct FunctionExecutionTrace {
+    global_indexes: ExecutionTraceGlobalIndexes,
+    br_table: Operator<'static>,
+    function_index: u32,
+    operator_index: u32,
+
+    /// Whether the next operator is the first one of a basic block.
+    block_started: bool,
+}
+
+impl FunctionExecutionTrace {
+    /// Records the block before its first operator, so that a block that traps is recorded too.
+    pub(crate) fn feed(&mut self, operator: &Operator, state: &mut MiddlewareReaderState) {
+        if self.block_started {
+            self.inject_block_record(state);
+            self.block_started = false;
+        }
+        if is_control_flow_operator(operator) {
+            self.block_started = true;
+        }
+        self.operator_index += 1;
+    }
+
+    /// Writes the block to the next slot, then moves to the slot after it.
+    ///
+    /// The `br_table` leaves the block of the next slot, innermost first, and each slot
+    /// branches out of the remaining blocks once written.
+    fn inject_block_record(&self, state: &mut MiddlewareReaderState) {
+        let slots = &self.global_indexes.slots;
+        let next_slot = self.global_indexes.next_slot.as_u32();
+        let block = ((self.function_index as u64) << 32) | self.operator_index as u64;
+
+        for _ in 0..=slots.len() {
+            state.push_operator(Operator::Block {
+                ty: WpTypeOrFuncType::Type(WpType::EmptyBlockType),
+            });
+        }
+        state.extend(&[
+            Operator::GlobalGet {
+                global_index: next_slot,
+            },
+            Operator::I32WrapI64,
+            self.br_table.clone(),
+        ]);
+        for (slot, global_index) in slots.iter().enumerate() {
+            state.extend(&[
+                Operator::End,
+                Operator::I64Const {
+                    value: block as i64,
+                },
+                Operator::GlobalSet {
+                    global_index: global_index.as_u32(),
+                },
+                Operator::Br {
+                    relative_depth: (slots.len() - 1 - slot) as u32,
+                },
+            ]);
+        }
+        state.extend(&[
+            Operator::End,
+            Operator::GlobalGet {
+                global_index: next_slot,
+            },
+            Operator::I64Const { value: 1 },
+            Operator::I64Add,
+            Operator::I64Const {
+                value: slots.len() as i64,
+            },
+            Operator::I64RemU,
+            Operator::GlobalSet {
+                global_index: next_slot,
+            },
+        ]);
+    }
+}
+
+fn execution_trace_name(slot: u32) -> String {
+    format!("{EXECUTION_TRACE_PREFIX}{slot}")
+}
+
+/// Reads the recorded blocks, oldest first.
+pub(crate) fn get_execution_trace(
+    instance: &Instance,
+    length: u32,
+) -> Result<Vec<ExecutionTraceEntry>, ExecutorError> {
+    let next_slot = u64::try_from(get_global(instance, EXECUTION_TRACE_NEXT_SLOT)?.get())
+        .map_err(|err| ExecutorError::Runtime(err.to_string()))?;
+
+    let mut execution_trace = Vec::new();
+    for slot in (0..length as u64).map(|offset| (next_slot + offset) % length as u64) {
+        let slot_global = get_global(instance, &execution_trace_name(slot as u32))?;
+        let block = u64::try_from(slot_global.get())
+            .map_err(|err| ExecutorError::Runtime(err.to_string()))?;
+        if block != EXECUTION_TRACE_EMPTY_SLOT {
+            execution_trace.push(ExecutionTraceEntry {
+                function_index: (block >> 32) as u32,
+                operator_index: block as u32,
+            });
+        }
+    }
+    Ok(execution_trace)
+}
+
+/// Forgets the recorded blocks, so that the trace only holds those of the next call.
+pub(crate) fn reset_execution_trace(instance: &Instance, length: u32) -> Result<(), ExecutorError> {
+    for slot in 0..length {
+        get_global(instance, &execution_trace_name(slot))?
+            .set(EXECUTION_TRACE_EMPTY_SLOT.into())
+            .map_err(|err| ExecutorError::Runtime(err.message()))?;
+    }
+    get_global(instance, EXECUTION_TRACE_NEXT_SLOT)?
+        .set(0u64.into())
+        .map_err(|err| ExecutorError::Runtime(err.message()))
+}
+
+fn get_global<'a>(instance: &'a Instance, name: &str) -> Result<&'a wasmer::Global, ExecutorError> {
+    instance
+        .exports
+        .get_global(name)
+        .map_err(|err| ExecutorError::Runtime(err.to_string()))
+}
@@ -1,12 +1,18 @@
+use crate::wasmer_import_validation::validate_contract_imports;
+use crate::wasmer_module_cache::{ModuleCache, DEFAULT_MODULE_CACHE_CAPACITY};
+use crate::wasmer_module_info::inspect_module;
 use crate::WasmerInstance;
 use log::trace;
 use klever_chain_vm_executor::{
-    CompilationOptions, Executor, ExecutorError, Instance, OpcodeCost, ServiceError, VMHooks,
+    BulkMemoryCost, CompilationOptions, CompileBatchResult, Executor, ExecutorError, GasSchedule,
+    GasScheduleError, ImportIssue, Instance, ModuleCacheStats, ModuleInfo, OpcodeCost,
+    OpcodeTraceSink, ServiceError, VMHooks, DEFAULT_GAS_SCHEDULE_VERSION,
 };
-use std::cell::RefCell;
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::collections::BTreeMap;
 use std::ffi::c_void;
-use std::rc::Rc;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
 
 use wasmer_vm::platform_init;
 
@@ -16,27 +22,42 @@ pub fn force_sighandler_reinstall() {
     }
 }
 
+struct RegisteredGasSchedule {
+    activation_epoch: u64,
+    opcode_cost: Arc<Mutex<OpcodeCost>>,
+    bulk_memory_cost: Option<BulkMemoryCost>,
+}
+
 pub(crate) struct WasmerExecutorData {
-    vm_hooks: Rc<Box<dyn VMHooks>>,
+    vm_hooks: Arc<dyn VMHooks>,
     opcode_cost: Arc<Mutex<OpcodeCost>>,
+    bulk_memory_cost: Option<BulkMemoryCost>,
+    gas_schedules: BTreeMap<u32, RegisteredGasSchedule>,
+    opcode_trace_sink: OpcodeTraceSink,
+    module_cache: ModuleCache,
 }
 
 impl WasmerExecutorData {
     fn new(vm_hooks: Box<dyn VMHooks>) -> Self {
         Self {
-            vm_hooks: Rc::new(vm_hooks),
+            vm_hooks: Arc::from(vm_hooks),
             opcode_cost: Arc::new(Mutex::new(OpcodeCost::default())),
+            bulk_memory_cost: None,
+            gas_schedules: BTreeMap::new(),
+            opcode_trace_sink: OpcodeTraceSink::default(),
+            module_cache: ModuleCache::new(DEFAULT_MODULE_CACHE_CAPACITY),
         }
     }
 
     fn set_vm_hooks_ptr(&mut self, vm_hooks_ptr: *mut c_void) -> Result<(), ExecutorError> {
-        if let Some(vm_hooks) = Rc::get_mut(&mut self.vm_hooks) {
+        if let Some(vm_hooks) = Arc::get_mut(&mut self.vm_hooks) {
             vm_hooks.set_vm_hooks_ptr(vm_hooks_ptr);
             Ok(())
         } else {
-            Err(Box::new(ServiceError::new(
+            Err(ServiceError::new(
                 "WasmerExecutor already set vmhooks, further configuration not allowed",
-            )))
+            )
+            .into())
         }
     }
 
@@ -45,36 +66,167 @@ impl WasmerExecutorData {
         Ok(())
     }
 
-    pub(crate) fn get_vm_hooks(&self) -> Rc<Box<dyn VMHooks>> {
+    fn set_bulk_memory_cost(
+        &mut self,
+        bulk_memory_cost: &BulkMemoryCost,
+    ) -> Result<(), ExecutorError> {
+        self.bulk_memory_cost = Some(*bulk_memory_cost);
+        Ok(())
+    }
+
+    fn register_gas_schedule(&mut self, gas_schedule: GasSchedule) -> Result<(), ExecutorError> {
+        let version = gas_schedule.version;
+        if version == DEFAULT_GAS_SCHEDULE_VERSION {
+            return Err(GasScheduleError::ReservedVersion(version).into());
+        }
+        if self.gas_schedules.contains_key(&version) {
+            return Err(GasScheduleError::DuplicateVersion(version).into());
+        }
+        let same_epoch = self.gas_schedules.iter().find(|(_, registered)| {
+            registered.activation_epoch == gas_schedule.activation_epoch
+        });
+        if let Some((existing, _)) = same_epoch {
+            return Err(GasScheduleError::DuplicateActivationEpoch {
+                epoch: gas_schedule.activation_epoch,
+                existing: *existing,
+            }
+            .into());
+        }
+
+        trace!(
+            "Registering gas schedule {} version {version} from epoch {} ...",
+            gas_schedule.name,
+            gas_schedule.activation_epoch
+        );
+        self.gas_schedules.insert(
+            version,
+            RegisteredGasSchedule {
+                activation_epoch: gas_schedule.activation_epoch,
+                opcode_cost: Arc::new(Mutex::new(gas_schedule.opcode_cost)),
+                bulk_memory_cost: gas_schedule.bulk_memory_cost,
+            },
+        );
+        Ok(())
+    }
+
+    fn gas_schedule_version_at_epoch(&self, epoch: u64) -> Option<u32> {
+        self.gas_schedules
+            .iter()
+            .filter(|(_, registered)| registered.activation_epoch <= epoch)
+            .max_by_key(|(_, registered)| registered.activation_epoch)
+            .map(|(version, _)| *version)
+    }
+
+    pub(crate) fn get_vm_hooks(&self) -> Arc<dyn VMHooks> {
         self.vm_hooks.clone()
     }
 
-    pub(crate) fn get_opcode_cost(&self) -> Arc<Mutex<OpcodeCost>> {
-        self.opcode_cost.clone()
+    pub(crate) fn get_opcode_cost(
+        &self,
+        gas_schedule_version: u32,
+    ) -> Result<Arc<Mutex<OpcodeCost>>, ExecutorError> {
+        if gas_schedule_version == DEFAULT_GAS_SCHEDULE_VERSION {
+            return Ok(self.opcode_cost.clone());
+        }
+        self.gas_schedules
+            .get(&gas_schedule_version)
+            .map(|registered| registered.opcode_cost.clone())
+            .ok_or_else(|| GasScheduleError::UnknownVersion(gas_schedule_version).into())
+    }
+
+    pub(crate) fn get_bulk_memory_cost(
+        &self,
+        gas_schedule_version: u32,
+    ) -> Result<Option<BulkMemoryCost>, ExecutorError> {
+        if gas_schedule_version == DEFAULT_GAS_SCHEDULE_VERSION {
+            return Ok(self.bulk_memory_cost);
+        }
+        self.gas_schedules
+            .get(&gas_schedule_version)
+            .map(|registered| registered.bulk_memory_cost)
+            .ok_or_else(|| GasScheduleError::UnknownVersion(gas_schedule_version).into())
+    }
+
+    pub(crate) fn get_opcode_trace_sink(&self) -> OpcodeTraceSink {
+        self.opcode_trace_sink.clone()
+    }
+
+    pub(crate) fn get_module_cache(&mut self) -> &mut ModuleCache {
+        &mut self.module_cache
     }
 }
 
 pub struct WasmerExecutor {
-    data: Rc<RefCell<WasmerExecutorData>>,
+    data: Arc<RwLock<WasmerExecutorData>>,
+    compile_threads: usize,
+
+    /// Created by the first batch, most executors never compile one.
+    compile_pool: OnceLock<ThreadPool>,
 }
 
 impl WasmerExecutor {
     pub(crate) fn new(vm_hooks: Box<dyn VMHooks>) -> Self {
         Self {
-            data: Rc::new(RefCell::new(WasmerExecutorData::new(vm_hooks))),
+            data: Arc::new(RwLock::new(WasmerExecutorData::new(vm_hooks))),
+            compile_threads: 0,
+            compile_pool: OnceLock::new(),
+        }
+    }
+
+    fn get_compile_pool(&self) -> Result<&ThreadPool, ExecutorError> {
+        if let Some(compile_pool) = self.compile_pool.get() {
+            return Ok(compile_pool);
         }
+
+        let compile_pool = ThreadPoolBuilder::new()
+            .num_threads(self.compile_threads)
+            .thread_name(|index| format!("vm-executor-compile-{index}"))
+            .build()
+            .map_err(|err| ExecutorError::Runtime(err.to_string()))?;
+        Ok(self.compile_pool.get_or_init(|| compile_pool))
     }
 }
 
 impl Executor for WasmerExecutor {
     fn set_vm_hooks_ptr(&mut self, vm_hooks_ptr: *mut c_void) -> Result<(), ExecutorError> {
         trace!("Setting vmhooks ...");
-        self.data.borrow_mut().set_vm_hooks_ptr(vm_hooks_ptr)
+        self.data.write().unwrap().set_vm_hooks_ptr(vm_hooks_ptr)
     }
 
     fn set_opcode_cost(&mut self, opcode_cost: &OpcodeCost) -> Result<(), ExecutorError> {
         trace!("Setting opcode cost...");
-        self.data.borrow_mut().set_opcode_cost(opcode_cost)
+        self.data.write().unwrap().set_opcode_cost(opcode_cost)
+    }
+
+    fn set_bulk_memory_cost(
+        &mut self,
+        bulk_memory_cost: &BulkMemoryCost,
+    ) -> Result<(), ExecutorError> {
+        trace!("Setting bulk memory cost...");
+        self.data
+            .write()
+            .unwrap()
+            .set_bulk_memory_cost(bulk_memory_cost)
+    }
+
+    fn register_gas_schedule(&mut self, gas_schedule: GasSchedule) -> Result<(), ExecutorError> {
+        self.data
+            .write()
+            .unwrap()
+            .register_gas_schedule(gas_schedule)
+    }
+
+    fn gas_schedule_version_at_epoch(&self, epoch: u64) -> Option<u32> {
+        self.data
+            .read()
+            .unwrap()
+            .gas_schedule_version_at_epoch(epoch)
+    }
+
+    fn set_opcode_trace_sink(&mut self, sink: OpcodeTraceSink) -> Result<(), ExecutorError> {
+        trace!("Setting opcode trace sink...");
+        self.data.write().unwrap().opcode_trace_sink = sink;
+        Ok(())
     }
 
     fn new_instance(
@@ -96,4 +248,60 @@ impl Executor for WasmerExecutor {
             compilation_options,
         )
     }
+
+    fn set_module_cache_capacity(&mut self, capacity: usize) -> Result<(), ExecutorError> {
+        trace!("Setting module cache capacity...");
+        self.data
+            .write()
+            .unwrap()
+            .module_cache
+            .set_capacity(capacity);
+        Ok(())
+    }
+
+    fn get_module_cache_stats(&self) -> ModuleCacheStats {
+        self.data.read().unwrap().module_cache.stats()
+    }
+
+    fn set_compile_threads(&mut self, threads: usize) -> Result<(), ExecutorError> {
+        trace!("Setting compile threads...");
+        self.compile_threads = threads;
+        self.compile_pool = OnceLock::new();
+        Ok(())
+    }
+
+    fn compile_batch(
+        &self,
+        contracts: &[(&str, &[u8])],
+        compilation_options: &CompilationOptions,
+    ) -> Result<Vec<CompileBatchResult>, ExecutorError> {
+        trace!("Compiling batch of {} contracts ...", contracts.len());
+        let compile_pool = self.get_compile_pool()?;
+
+        Ok(compile_pool.install(|| {
+            contracts
+                .par_iter()
+                .map(|(id, wasm_bytes)| CompileBatchResult {
+                    id: id.to_string(),
+                    result: WasmerInstance::compile_to_cache(
+                        self.data.clone(),
+                        wasm_bytes,
+                        compilation_options,
+                    ),
+                })
+                .collect()
+        }))
+    }
+
+    fn validate_imports(&self, wasm_bytes: &[u8]) -> Result<Vec<ImportIssue>, ExecutorError> {
+        validate_contract_imports(&self.data.read().unwrap(), wasm_bytes)
+    }
+
+    fn inspect_module(
+        &self,
+        wasm_bytes: &[u8],
+        max_memory_pages: u64,
+    ) -> Result<ModuleInfo, ExecutorError> {
+        inspect_module(wasm_bytes, max_memory_pages)
+    }
 }
@@ -0,0 +1,183 @@
+use std::mem;
+use std::sync::Mutex;
+
+use klever_chain_vm_executor::{ExecutorError, GasProfileEntry};
+use loupe::{MemoryUsage, MemoryUsageTracker};
+use wasmer::wasmparser::Operator;
+use wasmer::{
+    FunctionMiddleware, Instance, LocalFunctionIndex, MiddlewareError, MiddlewareReaderState,
+    ModuleMiddleware,
+};
+use wasmer_types::{GlobalIndex, ModuleInfo};
+
+use crate::wasmer_helpers::{create_global_index, MiddlewareWithProtectedGlobals};
+
+const GAS_PROFILE_POINTS_PREFIX: &str = "gas_profile_points_";
+const GAS_PROFILE_CALLS_PREFIX: &str = "gas_profile_calls_";
+
+#[derive(Clone, Debug, MemoryUsage)]
+struct GasProfilingGlobalIndexes {
+    points_global_index: GlobalIndex,
+    calls_global_index: GlobalIndex,
+}
+
+/// Counts the points used and the calls of each function defined by the contract.
+///
+/// The points are added by the metering middleware, next to the points used increments,
+/// so that the profile follows the gas schedule exactly. This middleware counts the calls.
+#[derive(Debug)]
+pub(crate) struct GasProfiling {
+    global_indexes: Mutex<Option<Vec<GasProfilingGlobalIndexes>>>,
+}
+
+impl GasProfiling {
+    pub(crate) fn new() -> Self {
+        Self {
+            global_indexes: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn get_points_global_index(
+        &self,
+        local_function_index: LocalFunctionIndex,
+    ) -> GlobalIndex {
+        self.get_global_indexes(local_function_index)
+            .points_global_index
+    }
+
+    fn get_global_indexes(
+        &self,
+        local_function_index: LocalFunctionIndex,
+    ) -> GasProfilingGlobalIndexes {
+        self.global_indexes.lock().unwrap().as_ref().unwrap()
+            [local_function_index.as_u32() as usize]
+            .clone()
+    }
+}
+
+impl MemoryUsage for GasProfiling {
+    fn size_of_val(&self, tracker: &mut dyn MemoryUsageTracker) -> usize {
+        mem::size_of_val(self) + self.global_indexes.size_of_val(tracker)
+            - mem::size_of_val(&self.global_indexes)
+    }
+}
+
+impl ModuleMiddleware for GasProfiling {
+    fn generate_function_middleware(
+        &self,
+        local_function_index: LocalFunctionIndex,
+    ) -> Box<dyn FunctionMiddleware> {
+        Box::new(FunctionGasProfiling {
+            calls_global_index: self
+                .get_global_indexes(local_function_index)
+                .calls_global_index,
+            call_counted: false,
+        })
+    }
+
+    fn transform_module_info(&self, module_info: &mut ModuleInfo) {
+        let mut global_indexes = self.global_indexes.lock().unwrap();
+
+        let local_function_count = module_info.functions.len() - module_info.num_imported_functions;
+        *global_indexes = Some(
+            (0..local_function_count)
+                .map(|local_function_index| GasProfilingGlobalIndexes {
+                    points_global_index: create_global_index(
+                        module_info,
+                        &format!("{GAS_PROFILE_POINTS_PREFIX}{local_function_index}"),
+                        0,
+                    ),
+                    calls_global_index: create_global_index(
+                        module_info,
+                        &format!("{GAS_PROFILE_CALLS_PREFIX}{local_function_index}"),
+                        0,
+                    ),
+                })
+                .collect(),
+        );
+    }
+}
+
+impl MiddlewareWithProtectedGlobals for GasProfiling {
+    fn protected_globals(&self) -> Vec<u32> {
+        self.global_indexes
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .iter()
+            .flat_map(|global_indexes| {
+                [
+                    global_indexes.points_global_index.as_u32(),
+                    global_indexes.calls_global_index.as_u32(),
+                ]
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+struct FunctionGasProfiling {
+    calls_global_index: GlobalIndex,
+    call_counted: bool,
+}
+
+impl FunctionMiddleware for FunctionGasProfiling {
+    fn feed<'b>(
+        &mut self,
+        operator: Operator<'b>,
+        state: &mut MiddlewareReaderState<'b>,
+    ) -> Result<(), MiddlewareError> {
+        // The first operator starts the function body, whatever it is
+        if !self.call_counted {
+            state.extend(&[
+                Operator::GlobalGet {
+                    global_index: self.calls_global_index.as_u32(),
+                },
+                Operator::I64Const { value: 1 },
+                Operator::I64Add,
+                Operator::GlobalSet {
+                    global_index: self.calls_global_index.as_u32(),
+                },
+            ]);
+            self.call_counted = true;
+        }
+
+        state.push_operator(operator);
+
+        Ok(())
+    }
+}
+
+/// Reads the profile of each function defined by the contract, in the order of their indexes.
+pub(crate) fn get_gas_profile(instance: &Instance) -> Result<Vec<GasProfileEntry>, ExecutorError> {
+    let module_info = instance.module().info();
+    let local_function_count = module_info.functions.len() - module_info.num_imported_functions;
+
+    (0..local_function_count as u32)
+        .map(|function_index| {
+            let name = module_info
+                .function_names
+                .get(&module_info.func_index(LocalFunctionIndex::from_u32(function_index)))
+                .cloned();
+            Ok(GasProfileEntry {
+                function_index,
+                name,
+                gas: get_counter(instance, GAS_PROFILE_POINTS_PREFIX, function_index)?,
+                call_count: get_counter(instance, GAS_PROFILE_CALLS_PREFIX, function_index)?,
+            })
+        })
+        .collect()
+}
+
+fn get_counter(
+    instance: &Instance,
+    prefix: &str,
+    function_index: u32,
+) -> Result<u64, ExecutorError> {
+    let global = instance
+        .exports
+        .get_global(&format!("{prefix}{function_index}"))
+        .map_err(|err| ExecutorError::Runtime(err.to_string()))?;
+    u64::try_from(global.get()).map_err(|err| ExecutorError::Runtime(err.to_string()))
+}
@@ -1,7 +1,8 @@
-use wasmer::{ExportIndex, GlobalInit, GlobalType, Mutability, Type, wasmparser::Operator};
+use wasmer::wasmparser::{Operator, Type as WpType, TypeOrFuncType as WpTypeOrFuncType};
+use wasmer::{ExportIndex, GlobalInit, GlobalType, MiddlewareReaderState, Mutability, Type};
 use wasmer_types::{GlobalIndex, ModuleInfo};
 
-pub trait MiddlewareWithProtectedGlobals {
+pub trait MiddlewareWithProtectedGlobals: Send + Sync {
     fn protected_globals(&self) -> Vec<u32>;
 }
 
@@ -31,6 +32,17 @@ pub(crate) fn create_global_index(
     global_index
 }
 
+/// Traps if the condition on top of the stack is true.
+pub(crate) fn inject_trap_condition(state: &mut MiddlewareReaderState) {
+    state.extend(&[
+        Operator::If {
+            ty: WpTypeOrFuncType::Type(WpType::EmptyBlockType),
+        },
+        Operator::Unreachable,
+        Operator::End,
+    ]);
+}
+
 pub(crate) fn is_control_flow_operator(operator: &Operator) -> bool {
     matches!(
         operator,
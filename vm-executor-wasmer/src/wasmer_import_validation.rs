@@ -0,0 +1,183 @@
+use crate::wasmer_imports::generate_import_object;
+use crate::wasmer_vm_hooks::VMHooksWrapper;
+use crate::WasmerExecutorData;
+use klever_chain_vm_executor::{ExecutorError, ImportIssue, VM_HOOKS_NAMESPACE};
+use wasmer::wasmparser::{
+    BinaryReaderError, ImportSectionEntryType, Parser, Payload, Type as WpType, TypeDef,
+};
+use wasmer::Universal;
+use wasmer::{Extern, ExternType, FunctionType, ImportObject, Module, Singlepass, Store, Type};
+
+enum ImportedKind {
+    Function(FunctionType),
+    Other(&'static str),
+}
+
+/// A single import of a contract, as declared by its module.
+pub(crate) struct ContractImport {
+    namespace: String,
+    name: String,
+    kind: ImportedKind,
+}
+
+/// Lists the imports of a compiled module.
+pub(crate) fn module_imports(module: &Module) -> Vec<ContractImport> {
+    module
+        .imports()
+        .map(|import| ContractImport {
+            namespace: import.module().to_string(),
+            name: import.name().to_string(),
+            kind: match import.ty() {
+                ExternType::Function(function_type) => {
+                    ImportedKind::Function(function_type.clone())
+                }
+                ExternType::Global(_) => ImportedKind::Other("global"),
+                ExternType::Table(_) => ImportedKind::Other("table"),
+                ExternType::Memory(_) => ImportedKind::Other("memory"),
+            },
+        })
+        .collect()
+}
+
+/// Lists the imports of a contract straight from its bytes, without compiling it.
+pub(crate) fn parse_imports(wasm_bytes: &[u8]) -> Result<Vec<ContractImport>, ExecutorError> {
+    let mut function_types = Vec::new();
+    let mut imports = Vec::new();
+
+    for payload in Parser::new(0).parse_all(wasm_bytes) {
+        match payload.map_err(invalid_module)? {
+            Payload::TypeSection(reader) => {
+                for type_def in reader {
+                    function_types.push(match type_def.map_err(invalid_module)? {
+                        TypeDef::Func(func_type) => Some(FunctionType::new(
+                            convert_types(&func_type.params)?,
+                            convert_types(&func_type.returns)?,
+                        )),
+                        _ => None,
+                    });
+                }
+            }
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    let import = import.map_err(invalid_module)?;
+                    let kind = match import.ty {
+                        ImportSectionEntryType::Function(type_index) => function_types
+                            .get(type_index as usize)
+                            .cloned()
+                            .flatten()
+                            .map(ImportedKind::Function)
+                            .ok_or_else(|| {
+                                ExecutorError::InvalidModule(
+                                    "invalid import function type".to_string(),
+                                )
+                            })?,
+                        ImportSectionEntryType::Table(_) => ImportedKind::Other("table"),
+                        ImportSectionEntryType::Memory(_) => ImportedKind::Other("memory"),
+                        ImportSectionEntryType::Global(_) => ImportedKind::Other("global"),
+                        ImportSectionEntryType::Tag(_) => ImportedKind::Other("tag"),
+                        ImportSectionEntryType::Module(_) => ImportedKind::Other("module"),
+                        ImportSectionEntryType::Instance(_) => ImportedKind::Other("instance"),
+                    };
+                    imports.push(ContractImport {
+                        namespace: import.module.to_string(),
+                        name: import.field.unwrap_or_default().to_string(),
+                        kind,
+                    });
+                }
+            }
+            // imports always come before the code
+            Payload::CodeSectionStart { .. } => break,
+            _ => {}
+        }
+    }
+
+    Ok(imports)
+}
+
+pub(crate) fn convert_types(types: &[WpType]) -> Result<Vec<Type>, ExecutorError> {
+    types
+        .iter()
+        .map(|ty| match ty {
+            WpType::I32 => Ok(Type::I32),
+            WpType::I64 => Ok(Type::I64),
+            WpType::F32 => Ok(Type::F32),
+            WpType::F64 => Ok(Type::F64),
+            WpType::V128 => Ok(Type::V128),
+            WpType::FuncRef => Ok(Type::FuncRef),
+            WpType::ExternRef => Ok(Type::ExternRef),
+            _ => Err(ExecutorError::InvalidModule(
+                "unsupported type in function signature".to_string(),
+            )),
+        })
+        .collect()
+}
+
+pub(crate) fn invalid_module(err: BinaryReaderError) -> ExecutorError {
+    ExecutorError::InvalidModule(err.to_string())
+}
+
+/// Compares the imports of a contract with the generated VM hooks import table.
+pub(crate) fn check_imports(
+    imports: &[ContractImport],
+    import_object: &ImportObject,
+) -> Vec<ImportIssue> {
+    let vm_hooks = import_object.get_namespace_exports(VM_HOOKS_NAMESPACE);
+
+    let mut issues = Vec::new();
+    for import in imports {
+        if import.namespace != VM_HOOKS_NAMESPACE {
+            issues.push(ImportIssue::UnsupportedNamespace {
+                namespace: import.namespace.clone(),
+                name: import.name.clone(),
+            });
+            continue;
+        }
+
+        let found = match &import.kind {
+            ImportedKind::Function(function_type) => function_type,
+            ImportedKind::Other(kind) => {
+                issues.push(ImportIssue::UnsupportedKind {
+                    name: import.name.clone(),
+                    kind: kind.to_string(),
+                });
+                continue;
+            }
+        };
+
+        match vm_hooks
+            .as_ref()
+            .and_then(|exports| exports.get_extern(&import.name))
+        {
+            Some(Extern::Function(function)) => {
+                if function.ty() != found {
+                    issues.push(ImportIssue::SignatureMismatch {
+                        name: import.name.clone(),
+                        expected: function.ty().to_string(),
+                        found: found.to_string(),
+                    });
+                }
+            }
+            _ => issues.push(ImportIssue::UnknownFunction {
+                name: import.name.clone(),
+            }),
+        }
+    }
+    issues
+}
+
+/// Checks the imports of a contract without compiling it.
+pub(crate) fn validate_contract_imports(
+    executor_data: &WasmerExecutorData,
+    wasm_bytes: &[u8],
+) -> Result<Vec<ImportIssue>, ExecutorError> {
+    let imports = parse_imports(wasm_bytes)?;
+
+    // Only the signatures of the generated functions are used, any engine will do
+    let store = Store::new(&Universal::new(Singlepass::default()).engine());
+    let vm_hooks_wrapper = VMHooksWrapper {
+        vm_hooks: executor_data.get_vm_hooks(),
+    };
+    let import_object = generate_import_object(&store, &vm_hooks_wrapper);
+
+    Ok(check_imports(&imports, &import_object))
+}
@@ -0,0 +1,78 @@
+use std::ops::Range;
+
+use wasmer::{Module, WASM_PAGE_SIZE};
+
+static ZERO_PAGE: [u8; WASM_PAGE_SIZE] = [0; WASM_PAGE_SIZE];
+
+/// The linear memory of a module right after instantiation, page by page, as laid out by its
+/// data segments.
+///
+/// Only the pages covered by data segments are kept, the others are known to be all zeroes.
+/// Built once per compiled module, and shared by all its instances.
+pub(crate) struct InitialMemory {
+    pages: Vec<Option<Box<[u8]>>>,
+}
+
+impl InitialMemory {
+    pub(crate) fn new(module: &Module) -> Self {
+        let initial_pages = module
+            .exports()
+            .memories()
+            .next()
+            .map_or(0, |memory| memory.ty().minimum.0 as usize);
+        let mut pages: Vec<Option<Box<[u8]>>> = vec![None; initial_pages];
+
+        // Segments placed by an imported global, or out of bounds, fail validation or
+        // instantiation, so they never make it to an instance
+        let data_initializers = module.artifact().data_initializers();
+        for data_initializer in data_initializers {
+            if data_initializer.location.base.is_some() {
+                continue;
+            }
+            let mut offset = data_initializer.location.offset;
+            let mut data = &data_initializer.data[..];
+            while !data.is_empty() {
+                let Some(page) = pages.get_mut(offset / WASM_PAGE_SIZE) else {
+                    break;
+                };
+                let page = page.get_or_insert_with(|| ZERO_PAGE.into());
+                let page_offset = offset % WASM_PAGE_SIZE;
+                let len = data.len().min(WASM_PAGE_SIZE - page_offset);
+                page[page_offset..page_offset + len].copy_from_slice(&data[..len]);
+                offset += len;
+                data = &data[len..];
+            }
+        }
+
+        Self { pages }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Restores the pages the written range overlaps, and returns how many there were.
+    ///
+    /// Pages past the initial memory are left alone, the memory must not have grown since
+    /// instantiation.
+    pub(crate) fn restore_written_pages(
+        &self,
+        memory: &wasmer::Memory,
+        written: Range<u64>,
+    ) -> usize {
+        let page_size = WASM_PAGE_SIZE as u64;
+        let pages_len = self.pages.len() as u64;
+        let first_page = (written.start / page_size).min(pages_len) as usize;
+        let end_page = written.end.div_ceil(page_size).min(pages_len) as usize;
+
+        let data = unsafe { memory.data_unchecked_mut() };
+        for page_index in first_page..end_page {
+            let page = &mut data[page_index * WASM_PAGE_SIZE..(page_index + 1) * WASM_PAGE_SIZE];
+            match &self.pages[page_index] {
+                Some(initial_page) => page.copy_from_slice(initial_page),
+                None => page.fill(0),
+            }
+        }
+        end_page.saturating_sub(first_page)
+    }
+}
@@ -1,239 +1,1008 @@
+use crate::wasmer_helpers::MiddlewareWithProtectedGlobals;
+use crate::wasmer_cache_artifact::CacheHeader;
+use crate::wasmer_execution_trace::{get_execution_trace, reset_execution_trace, ExecutionTrace};
+use crate::wasmer_gas_profiling::{get_gas_profile, GasProfiling};
+use crate::wasmer_import_validation::{check_imports, module_imports};
+use crate::wasmer_initial_memory::InitialMemory;
+use crate::wasmer_module_cache::ModuleCacheKey;
+use crate::wasmer_opcode_control::reset_memory_grow_count;
+use crate::wasmer_opcode_histogram::{
+    get_opcode_histogram, reset_opcode_histogram, OpcodeCounting,
+};
 use crate::wasmer_opcode_trace::OpcodeTracer;
 use crate::wasmer_protected_globals::ProtectedGlobals;
+use crate::wasmer_reset_tracking::{
+    get_written_range, mark_memory_written, mutable_globals, reset_written_range, ResetTracking,
+};
+use crate::wasmer_snapshot_globals::{snapshot_globals, SnapshotGlobals};
+use crate::wasmer_validation::Validation;
 use crate::{
     wasmer_breakpoints::*, wasmer_imports::generate_import_object, wasmer_metering::*,
     wasmer_opcode_control::OpcodeControl, wasmer_vm_hooks::VMHooksWrapper, WasmerExecutorData,
 };
 use log::trace;
 use klever_chain_vm_executor::{
-    BreakpointValue, CompilationOptions, ExecutorError, Instance, ServiceError,
+    BreakpointValue, BulkMemoryCost, CacheArtifactError, CallOutcome, CallTrap, CompilationOptions,
+    CompilerBackend, ExecutionTraceEntry, ExecutorError, GasProfileEntry, ImportValidationError,
+    Instance, InstanceSnapshot, OpcodeCost, OpcodeHistogram, OpcodeTraceEntry, OpcodeTraceSink,
+    SnapshotError, TrapCode, ValidationError, Value, ValueType, MAX_EXECUTION_TRACE_LENGTH,
 };
-use klever_chain_vm_executor::{MemLength, MemPtr};
+use klever_chain_vm_executor::{run_in_instance, MemLength, MemPtr};
 
-use std::cell::RefCell;
-use std::{rc::Rc, sync::Arc};
+use std::sync::{Arc, Mutex, RwLock};
+use std::{mem, slice};
 use wasmer::Universal;
-use wasmer::{CompilerConfig, Extern, Module, Store};
-use wasmer::{Pages, Singlepass};
+use wasmer::{
+    CompileError, CompilerConfig, ExportType, Extern, FrameInfo, ImportObject, MemoryType, Module,
+    RuntimeError, Store, WasmError,
+};
+use wasmer::{Cranelift, Singlepass};
+
+/// The optional middlewares that were compiled into a module.
+///
+/// They are recorded in the header of the cached module, so that an instance created
+/// from cache behaves exactly like the one that produced the cache.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct MiddlewareSettings {
+    pub(crate) metering: bool,
+    pub(crate) runtime_breakpoints: bool,
+    pub(crate) gas_profiling: bool,
+    pub(crate) opcode_histogram: bool,
+    pub(crate) execution_trace: bool,
+    pub(crate) snapshots: bool,
+
+    /// The number of blocks kept by the execution trace, zero without it.
+    pub(crate) execution_trace_length: u32,
+}
+
+impl MiddlewareSettings {
+    const METERING_FLAG: u8 = 0b0001;
+    const RUNTIME_BREAKPOINTS_FLAG: u8 = 0b0010;
+    const GAS_PROFILING_FLAG: u8 = 0b0100;
+    const OPCODE_HISTOGRAM_FLAG: u8 = 0b1000;
+    const EXECUTION_TRACE_FLAG: u8 = 0b10000;
+    const SNAPSHOTS_FLAG: u8 = 0b100000;
+
+    pub(crate) fn new(compilation_options: &CompilationOptions) -> Result<Self, ExecutorError> {
+        let execution_trace = compilation_options.execution_trace && compilation_options.metering;
+        let execution_trace_length = if execution_trace {
+            compilation_options.checked_execution_trace_length()?
+        } else {
+            0
+        };
+        Ok(Self {
+            metering: compilation_options.metering,
+            runtime_breakpoints: compilation_options.runtime_breakpoints,
+            // The gas profile, the opcode histogram and the execution trace are recorded
+            // by the metering middleware
+            gas_profiling: compilation_options.gas_profiling && compilation_options.metering,
+            opcode_histogram: compilation_options.opcode_histogram && compilation_options.metering,
+            execution_trace,
+            snapshots: compilation_options.snapshots,
+            execution_trace_length,
+        })
+    }
 
-const MAX_MEMORY_PAGES_ALLOWED: Pages = Pages(20);
+    pub(crate) fn to_flags(self) -> u8 {
+        let mut flags = 0;
+        if self.metering {
+            flags |= Self::METERING_FLAG;
+        }
+        if self.runtime_breakpoints {
+            flags |= Self::RUNTIME_BREAKPOINTS_FLAG;
+        }
+        if self.gas_profiling {
+            flags |= Self::GAS_PROFILING_FLAG;
+        }
+        if self.opcode_histogram {
+            flags |= Self::OPCODE_HISTOGRAM_FLAG;
+        }
+        if self.execution_trace {
+            flags |= Self::EXECUTION_TRACE_FLAG;
+        }
+        if self.snapshots {
+            flags |= Self::SNAPSHOTS_FLAG;
+        }
+        flags
+    }
+
+    /// The settings encoded by `to_flags`, the execution trace length being recorded apart.
+    pub(crate) fn from_flags(
+        flags: u8,
+        execution_trace_length: u32,
+    ) -> Result<Self, CacheArtifactError> {
+        let known_flags = Self::METERING_FLAG
+            | Self::RUNTIME_BREAKPOINTS_FLAG
+            | Self::GAS_PROFILING_FLAG
+            | Self::OPCODE_HISTOGRAM_FLAG
+            | Self::EXECUTION_TRACE_FLAG
+            | Self::SNAPSHOTS_FLAG;
+        if flags & !known_flags != 0 {
+            return Err(CacheArtifactError::UnknownMiddlewareSettings(flags));
+        }
+        let execution_trace = flags & Self::EXECUTION_TRACE_FLAG != 0;
+        let valid_execution_trace_length = if execution_trace {
+            (1..=MAX_EXECUTION_TRACE_LENGTH).contains(&execution_trace_length)
+        } else {
+            execution_trace_length == 0
+        };
+        if !valid_execution_trace_length {
+            return Err(CacheArtifactError::InvalidExecutionTraceLength(
+                execution_trace_length,
+            ));
+        }
+
+        Ok(Self {
+            metering: flags & Self::METERING_FLAG != 0,
+            runtime_breakpoints: flags & Self::RUNTIME_BREAKPOINTS_FLAG != 0,
+            gas_profiling: flags & Self::GAS_PROFILING_FLAG != 0,
+            opcode_histogram: flags & Self::OPCODE_HISTOGRAM_FLAG != 0,
+            execution_trace,
+            snapshots: flags & Self::SNAPSHOTS_FLAG != 0,
+            execution_trace_length,
+        })
+    }
+}
 
 pub struct WasmerInstance {
     wasmer_instance: wasmer::Instance,
     memory_name: String,
+    cache_header: CacheHeader,
+
+    /// Shared with the other instances of the module.
+    initial_memory: Arc<InitialMemory>,
+
+    /// The mutable globals with their values after instantiation, `None` if some mutable
+    /// global is not numeric, then only the full reset is possible.
+    initial_globals: Option<Vec<(wasmer::Global, Value)>>,
+
+    /// Only kept when tracing to `OpcodeTraceSink::Memory`.
+    opcode_trace: Vec<OpcodeTraceEntry>,
+
+    /// The execution trace of the last call, if it trapped or stopped on a breakpoint.
+    last_execution_trace: Mutex<Option<Vec<ExecutionTraceEntry>>>,
 }
 
 impl WasmerInstance {
     pub(crate) fn try_new_instance(
-        executor_data: Rc<RefCell<WasmerExecutorData>>,
+        executor_data: Arc<RwLock<WasmerExecutorData>>,
         wasm_bytes: &[u8],
         compilation_options: &CompilationOptions,
     ) -> Result<Box<dyn Instance>, ExecutorError> {
-        // Use Singlepass compiler with the default settings
-        let mut compiler = Singlepass::default();
+        let middleware_settings = MiddlewareSettings::new(compilation_options)?;
+        let opcode_cost = executor_data
+            .read()
+            .unwrap()
+            .get_opcode_cost(compilation_options.gas_schedule_version)?;
+        let bulk_memory_cost = executor_data
+            .read()
+            .unwrap()
+            .get_bulk_memory_cost(compilation_options.gas_schedule_version)?;
 
-        // Push middlewares
-        push_middlewares(&mut compiler, compilation_options, executor_data.clone());
+        let (cache_header, cache_key) = {
+            let opcode_cost = opcode_cost.lock().unwrap();
+            let cache_header = CacheHeader::new(
+                wasm_bytes,
+                compilation_options,
+                middleware_settings,
+                &opcode_cost,
+                bulk_memory_cost.as_ref(),
+            )?;
 
-        // Create the store
-        let store = Store::new(&Universal::new(compiler).engine());
+            // Opcode tracing happens while compiling, so traced modules are never cached
+            let cache_key = if compilation_options.opcode_trace {
+                None
+            } else {
+                Some(ModuleCacheKey::new(
+                    wasm_bytes,
+                    compilation_options,
+                    &opcode_cost,
+                    bulk_memory_cost,
+                ))
+            };
+            (cache_header, cache_key)
+        };
 
-        trace!("Compiling module ...");
-        let module = Module::new(&store, wasm_bytes)?;
+        let cached_module = cache_key
+            .as_ref()
+            .and_then(|key| executor_data.write().unwrap().get_module_cache().get(key));
 
-        // Create an empty import object.
-        trace!("Generating imports ...");
-        let vm_hooks_wrapper = VMHooksWrapper {
-            vm_hooks: executor_data.borrow().get_vm_hooks(),
+        let (module, initial_memory, opcode_trace) = match cached_module {
+            Some((module, initial_memory)) => {
+                trace!("Reusing cached module ...");
+                (module, initial_memory, Vec::new())
+            }
+            None => {
+                let opcode_trace_sink = executor_data.read().unwrap().get_opcode_trace_sink();
+                let (module, opcode_trace) = Self::compile_module(
+                    opcode_cost,
+                    bulk_memory_cost,
+                    &opcode_trace_sink,
+                    wasm_bytes,
+                    compilation_options,
+                    middleware_settings,
+                )?;
+                let initial_memory = Arc::new(InitialMemory::new(&module));
+                if let Some(key) = cache_key {
+                    executor_data.write().unwrap().get_module_cache().insert(
+                        key,
+                        module.clone(),
+                        initial_memory.clone(),
+                    );
+                }
+                (module, initial_memory, opcode_trace)
+            }
         };
-        let import_object = generate_import_object(&store, &vm_hooks_wrapper);
 
-        trace!("Instantiating WasmerInstance ...");
-        let wasmer_instance = wasmer::Instance::new(&module, &import_object)?;
-        set_points_limit(&wasmer_instance, compilation_options.gas_limit)?;
+        Self::try_new_instance_from_module(
+            executor_data,
+            module.store(),
+            &module,
+            initial_memory,
+            compilation_options,
+            cache_header,
+            opcode_trace,
+        )
+    }
 
-        // Check that there is exactly one memory in the smart contract, no more, no less
-        let memories = get_memories(&wasmer_instance);
-        validate_memories(&memories)?;
+    /// Compiles a module and returns its cache bytes, without going through the module cache.
+    ///
+    /// The module is validated like by `try_new_instance`, but not instantiated, so that no
+    /// start function or VM hook runs on the compile threads.
+    pub(crate) fn compile_to_cache(
+        executor_data: Arc<RwLock<WasmerExecutorData>>,
+        wasm_bytes: &[u8],
+        compilation_options: &CompilationOptions,
+    ) -> Result<Vec<u8>, ExecutorError> {
+        let middleware_settings = MiddlewareSettings::new(compilation_options)?;
+        let opcode_cost = executor_data
+            .read()
+            .unwrap()
+            .get_opcode_cost(compilation_options.gas_schedule_version)?;
+        let bulk_memory_cost = executor_data
+            .read()
+            .unwrap()
+            .get_bulk_memory_cost(compilation_options.gas_schedule_version)?;
 
-        // At this point we know that there is exactly one memory
-        let memory = memories[0].1;
-        // Checks that the memory size is not greater than the maximum allowed
-        validate_memory(memory)?;
+        let cache_header = CacheHeader::new(
+            wasm_bytes,
+            compilation_options,
+            middleware_settings,
+            &opcode_cost.lock().unwrap(),
+            bulk_memory_cost.as_ref(),
+        )?;
 
-        trace!("WasmerMemory size: {:#?}", memory.size());
-        let memory_name = memories[0].0.clone();
+        let opcode_trace_sink = executor_data.read().unwrap().get_opcode_trace_sink();
+        let (module, _) = Self::compile_module(
+            opcode_cost,
+            bulk_memory_cost,
+            &opcode_trace_sink,
+            wasm_bytes,
+            compilation_options,
+            middleware_settings,
+        )?;
+        validate_module(&executor_data, module.store(), &module, &cache_header)?;
+        encode_module(&module, &cache_header)
+    }
 
-        Ok(Box::new(WasmerInstance {
-            wasmer_instance,
-            memory_name,
-        }))
+    /// Compiles a module, and returns it with its opcode trace, if kept in memory.
+    fn compile_module(
+        opcode_cost: Arc<Mutex<OpcodeCost>>,
+        bulk_memory_cost: Option<BulkMemoryCost>,
+        opcode_trace_sink: &OpcodeTraceSink,
+        wasm_bytes: &[u8],
+        compilation_options: &CompilationOptions,
+        middleware_settings: MiddlewareSettings,
+    ) -> Result<(Module, Vec<OpcodeTraceEntry>), ExecutorError> {
+        let mut compiler = create_compiler(compilation_options.compiler_backend()?);
+
+        // Create opcode_tracer middleware
+        let opcode_tracer_middleware = if compilation_options.opcode_trace {
+            Some(Arc::new(OpcodeTracer::new(opcode_trace_sink)?))
+        } else {
+            None
+        };
+
+        // Create snapshot_globals middleware
+        let snapshot_globals_middleware = if middleware_settings.snapshots {
+            Some(Arc::new(SnapshotGlobals::new()))
+        } else {
+            None
+        };
+
+        // Push middlewares
+        let validation_middleware = push_middlewares(
+            compiler.as_mut(),
+            compilation_options,
+            middleware_settings,
+            opcode_cost,
+            bulk_memory_cost,
+            opcode_tracer_middleware.clone(),
+            snapshot_globals_middleware.clone(),
+        );
+
+        // Create the store
+        let store = Store::new(&Universal::new(compiler).engine());
+
+        trace!("Compiling module ...");
+        let module = Module::new(&store, wasm_bytes).map_err(|err| {
+            // The validation middleware knows why it rejected the module better than wasmer does
+            match validation_middleware.take_validation_error() {
+                Some(validation_error) => validation_error.into(),
+                None => compile_error(err),
+            }
+        })?;
+        let snapshot_globals_error = snapshot_globals_middleware
+            .and_then(|snapshot_globals| snapshot_globals.take_validation_error());
+        if let Some(validation_error) = snapshot_globals_error {
+            return Err(validation_error.into());
+        }
+
+        let opcode_trace = match opcode_tracer_middleware {
+            Some(opcode_tracer_middleware) => opcode_tracer_middleware.finish()?,
+            None => Vec::new(),
+        };
+        Ok((module, opcode_trace))
     }
 
     pub(crate) fn try_new_instance_from_cache(
-        executor_data: Rc<RefCell<WasmerExecutorData>>,
+        executor_data: Arc<RwLock<WasmerExecutorData>>,
         cache_bytes: &[u8],
         compilation_options: &CompilationOptions,
     ) -> Result<Box<dyn Instance>, ExecutorError> {
-        // Use Singlepass compiler with the default settings
-        let mut compiler = Singlepass::default();
+        let compiler_backend = compilation_options.compiler_backend()?;
+        let (cache_header, module_bytes) = CacheHeader::decode_artifact(cache_bytes)?;
+        let opcode_cost = executor_data
+            .read()
+            .unwrap()
+            .get_opcode_cost(compilation_options.gas_schedule_version)?;
+        let bulk_memory_cost = executor_data
+            .read()
+            .unwrap()
+            .get_bulk_memory_cost(compilation_options.gas_schedule_version)?;
+        cache_header.check_compatible(
+            compilation_options,
+            &opcode_cost.lock().unwrap(),
+            bulk_memory_cost.as_ref(),
+        )?;
+
+        // The middleware settings recorded in the cache take precedence over the given options,
+        // since the middlewares are already compiled into the cached module
+        let middleware_settings = cache_header.middleware_settings;
+
+        let mut compiler = create_compiler(compiler_backend);
 
         // Push middlewares
-        push_middlewares(&mut compiler, compilation_options, executor_data.clone());
+        push_middlewares(
+            compiler.as_mut(),
+            compilation_options,
+            middleware_settings,
+            opcode_cost,
+            bulk_memory_cost,
+            None,
+            middleware_settings
+                .snapshots
+                .then(|| Arc::new(SnapshotGlobals::new())),
+        );
 
         // Create the store
         let store = Store::new(&Universal::new(compiler).engine());
 
         trace!("Deserializing module ...");
-        let module;
-        unsafe {
-            module = Module::deserialize(&store, cache_bytes)?;
-        };
+        let module = deserialize_module(&store, module_bytes)?;
+        let initial_memory = Arc::new(InitialMemory::new(&module));
 
-        // Create an empty import object.
-        trace!("Generating imports ...");
-        let vm_hooks_wrapper = VMHooksWrapper {
-            vm_hooks: executor_data.borrow().get_vm_hooks(),
-        };
-        let import_object = generate_import_object(&store, &vm_hooks_wrapper);
-
-        trace!("Instantiating WasmerInstance ...");
-        let wasmer_instance = wasmer::Instance::new(&module, &import_object)?;
-        set_points_limit(&wasmer_instance, compilation_options.gas_limit)?;
+        Self::try_new_instance_from_module(
+            executor_data,
+            &store,
+            &module,
+            initial_memory,
+            compilation_options,
+            cache_header,
+            Vec::new(),
+        )
+    }
 
-        // Check that there is exactly one memory in the smart contract, no more, no less
-        let memories = get_memories(&wasmer_instance);
-        validate_memories(&memories)?;
+    fn try_new_instance_from_module(
+        executor_data: Arc<RwLock<WasmerExecutorData>>,
+        store: &Store,
+        module: &Module,
+        initial_memory: Arc<InitialMemory>,
+        compilation_options: &CompilationOptions,
+        cache_header: CacheHeader,
+        opcode_trace: Vec<OpcodeTraceEntry>,
+    ) -> Result<Box<dyn Instance>, ExecutorError> {
+        let import_object = validate_module(&executor_data, store, module, &cache_header)?;
 
-        // At this point we know that there is exactly one memory
-        let memory = memories[0].1;
-        // Checks that the memory size is not greater than the maximum allowed
-        validate_memory(memory)?;
+        trace!("Instantiating WasmerInstance ...");
+        let wasmer_instance = wasmer::Instance::new(module, &import_object)
+            .map_err(|err| ExecutorError::Instantiation(err.to_string()))?;
+        if cache_header.middleware_settings.metering {
+            set_points_limit(&wasmer_instance, compilation_options.gas_limit)?;
+        }
 
+        // The module has exactly one memory, checked by validate_module
+        let (memory_name, memory) = wasmer_instance
+            .exports
+            .iter()
+            .memories()
+            .next()
+            .ok_or(ValidationError::NoMemory)?;
         trace!("WasmerMemory size: {:#?}", memory.size());
-        let memory_name = memories[0].0.clone();
+        let memory_name = memory_name.clone();
+        // The globals are only reachable through the exports of the reset_tracking middleware
+        let initial_globals = mutable_globals(&wasmer_instance)
+            .into_iter()
+            .map(|global| Some((global.clone(), numeric_value(&global.get())?)))
+            .collect();
 
         Ok(Box::new(WasmerInstance {
             wasmer_instance,
             memory_name,
+            cache_header,
+            initial_memory,
+            initial_globals,
+            opcode_trace,
+            last_execution_trace: Mutex::new(None),
         }))
     }
 
-    fn get_memory_ref(&self) -> Result<&wasmer::Memory, String> {
+    fn check_metering_enabled(&self) -> Result<(), ExecutorError> {
+        if self.cache_header.middleware_settings.metering {
+            Ok(())
+        } else {
+            Err(ExecutorError::MeteringDisabled)
+        }
+    }
+
+    fn check_gas_profiling_enabled(&self) -> Result<(), ExecutorError> {
+        if self.cache_header.middleware_settings.gas_profiling {
+            Ok(())
+        } else {
+            Err(ExecutorError::GasProfilingDisabled)
+        }
+    }
+
+    fn check_opcode_histogram_enabled(&self) -> Result<(), ExecutorError> {
+        if self.cache_header.middleware_settings.opcode_histogram {
+            Ok(())
+        } else {
+            Err(ExecutorError::OpcodeHistogramDisabled)
+        }
+    }
+
+    fn check_snapshots_enabled(&self) -> Result<(), ExecutorError> {
+        if self.cache_header.middleware_settings.snapshots {
+            Ok(())
+        } else {
+            Err(ExecutorError::SnapshotsDisabled)
+        }
+    }
+
+    fn check_execution_trace_enabled(&self) -> Result<(), ExecutorError> {
+        if self.cache_header.middleware_settings.execution_trace {
+            Ok(())
+        } else {
+            Err(ExecutorError::ExecutionTraceDisabled)
+        }
+    }
+
+    fn check_runtime_breakpoints_enabled(&self) -> Result<(), ExecutorError> {
+        if self.cache_header.middleware_settings.runtime_breakpoints {
+            Ok(())
+        } else {
+            Err(ExecutorError::RuntimeBreakpointsDisabled)
+        }
+    }
+
+    fn get_memory_ref(&self) -> Result<&wasmer::Memory, ExecutorError> {
         let result = self.wasmer_instance.exports.get_memory(&self.memory_name);
         match result {
             Ok(memory) => Ok(memory),
-            Err(err) => Err(err.to_string()),
+            Err(err) => Err(ExecutorError::Runtime(err.to_string())),
+        }
+    }
+
+    fn get_function(&self, func_name: &str) -> Result<&wasmer::Function, ExecutorError> {
+        self.wasmer_instance
+            .exports
+            .get_function(func_name)
+            .map_err(|_| ExecutorError::MissingExport(func_name.to_string()))
+    }
+
+    /// The breakpoint value, or `BreakpointValue::None` if runtime breakpoints are disabled.
+    fn current_breakpoint(&self) -> BreakpointValue {
+        if self.cache_header.middleware_settings.runtime_breakpoints {
+            get_breakpoint_value(&self.wasmer_instance)
+                .ok()
+                .and_then(|value| BreakpointValue::try_from(value).ok())
+                .unwrap_or(BreakpointValue::None)
+        } else {
+            BreakpointValue::None
+        }
+    }
+
+    /// The points used, or zero if metering is disabled.
+    fn current_points_used(&self) -> Result<u64, ExecutorError> {
+        if self.cache_header.middleware_settings.metering {
+            get_points_used(&self.wasmer_instance)
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Clears the execution trace, so that it only holds the blocks of the coming call.
+    fn start_execution_trace(&self) -> Result<(), ExecutorError> {
+        if self.cache_header.middleware_settings.execution_trace {
+            reset_execution_trace(
+                &self.wasmer_instance,
+                self.cache_header.middleware_settings.execution_trace_length,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Keeps the execution trace of a call that trapped or stopped on a breakpoint.
+    fn finish_execution_trace(&self, trapped: bool) -> Result<(), ExecutorError> {
+        if !self.cache_header.middleware_settings.execution_trace {
+            return Ok(());
+        }
+        let execution_trace = if trapped || self.current_breakpoint() != BreakpointValue::None {
+            Some(get_execution_trace(
+                &self.wasmer_instance,
+                self.cache_header.middleware_settings.execution_trace_length,
+            )?)
+        } else {
+            None
+        };
+        *self.last_execution_trace.lock().unwrap() = execution_trace;
+        Ok(())
+    }
+
+    /// Converts a failed call, recovering the breakpoint that stopped the execution, if any.
+    fn trap_error(&self, err: RuntimeError) -> ExecutorError {
+        ExecutorError::Trap {
+            breakpoint: self.current_breakpoint(),
+            message: err.message(),
+        }
+    }
+}
+
+/// Deserializes a module, realigning its bytes first if needed.
+///
+/// Wasmer expects serialized modules to start on a 16-byte boundary. The cache header keeps
+/// that alignment relative to the cache bytes, but the bytes themselves may start anywhere.
+fn deserialize_module(store: &Store, module_bytes: &[u8]) -> Result<Module, ExecutorError> {
+    const MODULE_BYTES_ALIGNMENT: usize = mem::align_of::<u128>();
+
+    if (module_bytes.as_ptr() as usize).is_multiple_of(MODULE_BYTES_ALIGNMENT) {
+        return unsafe { Module::deserialize(store, module_bytes).map_err(deserialize_error) };
+    }
+
+    let mut aligned_buffer = vec![0u128; module_bytes.len().div_ceil(MODULE_BYTES_ALIGNMENT)];
+    let aligned_bytes = unsafe {
+        slice::from_raw_parts_mut(aligned_buffer.as_mut_ptr() as *mut u8, module_bytes.len())
+    };
+    aligned_bytes.copy_from_slice(module_bytes);
+
+    unsafe { Module::deserialize(store, aligned_bytes).map_err(deserialize_error) }
+}
+
+fn deserialize_error(err: wasmer::DeserializeError) -> ExecutorError {
+    CacheArtifactError::Deserialization(err.to_string()).into()
+}
+
+/// Modules that wasmer cannot parse or validate are reported as invalid, anything else as a
+/// compilation failure. Bytes that fail to parse as text format end up as generic errors.
+fn compile_error(err: CompileError) -> ExecutorError {
+    match err {
+        CompileError::Validate(_)
+        | CompileError::Wasm(WasmError::InvalidWebAssembly { .. })
+        | CompileError::Wasm(WasmError::Generic(_)) => {
+            ExecutorError::InvalidModule(err.to_string())
+        }
+        _ => ExecutorError::Compilation(err.to_string()),
+    }
+}
+
+fn call_trap(err: RuntimeError) -> CallTrap {
+    let message = err.message();
+    let backtrace = err.trace().iter().map(format_frame).collect();
+    CallTrap {
+        code: err.to_trap().map(trap_code),
+        message,
+        backtrace,
+    }
+}
+
+/// Formats a frame the way wasmer prints backtraces.
+fn format_frame(frame: &FrameInfo) -> String {
+    let function_name = match frame.function_name() {
+        Some(name) => name.to_string(),
+        None => format!("<func{}>", frame.func_index()),
+    };
+    format!(
+        "{function_name} ({}[{}]:{:#x})",
+        frame.module_name(),
+        frame.func_index(),
+        frame.module_offset()
+    )
+}
+
+fn trap_code(trap_code: wasmer_vm::TrapCode) -> TrapCode {
+    match trap_code {
+        wasmer_vm::TrapCode::UnreachableCodeReached => TrapCode::Unreachable,
+        wasmer_vm::TrapCode::HeapAccessOutOfBounds => TrapCode::MemoryOutOfBounds,
+        wasmer_vm::TrapCode::TableAccessOutOfBounds => TrapCode::TableOutOfBounds,
+        wasmer_vm::TrapCode::IntegerOverflow => TrapCode::IntegerOverflow,
+        wasmer_vm::TrapCode::IntegerDivisionByZero => TrapCode::IntegerDivisionByZero,
+        wasmer_vm::TrapCode::BadConversionToInteger => TrapCode::InvalidConversionToInteger,
+        wasmer_vm::TrapCode::StackOverflow => TrapCode::StackOverflow,
+        wasmer_vm::TrapCode::IndirectCallToNull => TrapCode::IndirectCallToNull,
+        wasmer_vm::TrapCode::BadSignature => TrapCode::IndirectCallSignatureMismatch,
+        _ => TrapCode::Other,
+    }
+}
+
+/// Checks the arguments against the function signature, and converts them.
+fn typed_arguments(
+    func_name: &str,
+    func_type: &wasmer::FunctionType,
+    args: &[Value],
+) -> Result<Vec<wasmer::Val>, ExecutorError> {
+    let params = func_type.params();
+    if params.len() != args.len() {
+        return Err(ExecutorError::ArgumentCount {
+            func_name: func_name.to_string(),
+            expected: params.len(),
+            found: args.len(),
+        });
+    }
+
+    let mut wasmer_args = Vec::with_capacity(args.len());
+    for (index, (param, arg)) in params.iter().zip(args).enumerate() {
+        let expected = value_type(param)
+            .ok_or_else(|| ExecutorError::UnsupportedSignature(func_name.to_string()))?;
+        if expected != arg.value_type() {
+            return Err(ExecutorError::ArgumentType {
+                func_name: func_name.to_string(),
+                index,
+                expected,
+                found: arg.value_type(),
+            });
         }
+        wasmer_args.push(wasmer_value(*arg));
+    }
+    Ok(wasmer_args)
+}
+
+fn value_type(wasmer_type: &wasmer::Type) -> Option<ValueType> {
+    match wasmer_type {
+        wasmer::Type::I32 => Some(ValueType::I32),
+        wasmer::Type::I64 => Some(ValueType::I64),
+        wasmer::Type::F32 => Some(ValueType::F32),
+        wasmer::Type::F64 => Some(ValueType::F64),
+        _ => None,
     }
 }
 
-fn get_memories(wasmer_instance: &wasmer::Instance) -> Vec<(&String, &wasmer::Memory)> {
-    let memories = wasmer_instance
-        .exports
-        .iter()
-        .memories()
-        .collect::<Vec<_>>();
-    memories
+fn wasmer_value(value: Value) -> wasmer::Val {
+    match value {
+        Value::I32(value) => wasmer::Val::I32(value),
+        Value::I64(value) => wasmer::Val::I64(value),
+        Value::F32(value) => wasmer::Val::F32(value),
+        Value::F64(value) => wasmer::Val::F64(value),
+    }
+}
+
+fn typed_value(func_name: &str, wasmer_value: &wasmer::Val) -> Result<Value, ExecutorError> {
+    numeric_value(wasmer_value)
+        .ok_or_else(|| ExecutorError::UnsupportedSignature(func_name.to_string()))
 }
 
-fn validate_memories(memories: &Vec<(&String, &wasmer::Memory)>) -> Result<(), ExecutorError> {
+fn numeric_value(wasmer_value: &wasmer::Val) -> Option<Value> {
+    match wasmer_value {
+        wasmer::Val::I32(value) => Some(Value::I32(*value)),
+        wasmer::Val::I64(value) => Some(Value::I64(*value)),
+        wasmer::Val::F32(value) => Some(Value::F32(*value)),
+        wasmer::Val::F64(value) => Some(Value::F64(*value)),
+        _ => None,
+    }
+}
+
+/// Checks the imports and the memory of a module, everything an instance needs apart from
+/// instantiation itself, and returns the imports to instantiate it with.
+fn validate_module(
+    executor_data: &RwLock<WasmerExecutorData>,
+    store: &Store,
+    module: &Module,
+    cache_header: &CacheHeader,
+) -> Result<ImportObject, ExecutorError> {
+    trace!("Generating imports ...");
+    let vm_hooks_wrapper = VMHooksWrapper {
+        vm_hooks: executor_data.read().unwrap().get_vm_hooks(),
+    };
+    let import_object = generate_import_object(store, &vm_hooks_wrapper);
+
+    trace!("Validating imports ...");
+    let import_issues = check_imports(&module_imports(module), &import_object);
+    if !import_issues.is_empty() {
+        return Err(ValidationError::from(ImportValidationError {
+            issues: import_issues,
+        })
+        .into());
+    }
+
+    // Check that there is exactly one memory in the smart contract, no more, no less
+    let memories = module.exports().memories().collect::<Vec<_>>();
+    validate_memories(&memories)?;
+
+    // Checks that the memory size is not greater than the maximum allowed
+    validate_memory(memories[0].ty(), cache_header.max_memory_pages)?;
+
+    Ok(import_object)
+}
+
+/// Serializes a compiled module, prefixed by its cache header.
+fn encode_module(module: &Module, cache_header: &CacheHeader) -> Result<Vec<u8>, ExecutorError> {
+    match module.serialize() {
+        Ok(module_bytes) => Ok(cache_header.encode_artifact(&module_bytes)),
+        Err(err) => Err(ExecutorError::Runtime(err.to_string())),
+    }
+}
+
+fn validate_memories(memories: &[ExportType<MemoryType>]) -> Result<(), ExecutorError> {
     if memories.is_empty() {
-        return Err(Box::new(ServiceError::new(
-            "no memory declared in smart contract",
-        )));
+        return Err(ValidationError::NoMemory.into());
     }
     if memories.len() > 1 {
-        return Err(Box::new(ServiceError::new(
-            "more than one memory declared in smart contract",
-        )));
+        return Err(ValidationError::MultipleMemories.into());
     }
 
     Ok(())
 }
 
-fn validate_memory(memory: &wasmer::Memory) -> Result<(), ExecutorError> {
-    let memory_type = memory.ty();
-    let max_memory_pages = memory_type.maximum.unwrap_or(memory_type.minimum);
+fn validate_memory(memory_type: &MemoryType, max_memory_pages: u64) -> Result<(), ExecutorError> {
+    let declared_pages = memory_type.maximum.unwrap_or(memory_type.minimum).0 as u64;
 
-    if max_memory_pages > MAX_MEMORY_PAGES_ALLOWED {
+    if declared_pages > max_memory_pages {
         trace!(
             "Memory size exceeds maximum allowed: {:#?} > {:#?}",
-            max_memory_pages,
-            MAX_MEMORY_PAGES_ALLOWED
+            declared_pages,
+            max_memory_pages
         );
-        return Err(Box::new(ServiceError::new(
-            "memory size exceeds maximum allowed",
-        )));
+        return Err(ValidationError::MemoryTooLarge {
+            declared: declared_pages,
+            max: max_memory_pages,
+        }
+        .into());
     }
 
     Ok(())
 }
 
+fn create_compiler(compiler_backend: CompilerBackend) -> Box<dyn CompilerConfig> {
+    match compiler_backend {
+        CompilerBackend::Singlepass => {
+            trace!("Using Singlepass compiler ...");
+            Box::new(Singlepass::default())
+        }
+        CompilerBackend::Cranelift => {
+            trace!("Using Cranelift compiler ...");
+            let mut compiler = Cranelift::default();
+            compiler.canonicalize_nans(true);
+            Box::new(compiler)
+        }
+    }
+}
+
 fn push_middlewares(
-    compiler: &mut Singlepass,
+    compiler: &mut dyn CompilerConfig,
     compilation_options: &CompilationOptions,
-    executor_data: Rc<RefCell<WasmerExecutorData>>,
-) {
+    middleware_settings: MiddlewareSettings,
+    opcode_cost: Arc<Mutex<OpcodeCost>>,
+    bulk_memory_cost: Option<BulkMemoryCost>,
+    opcode_tracer_middleware: Option<Arc<OpcodeTracer>>,
+    snapshot_globals_middleware: Option<Arc<SnapshotGlobals>>,
+) -> Arc<Validation> {
+    let mut protected_middlewares: Vec<Arc<dyn MiddlewareWithProtectedGlobals>> = Vec::new();
+
+    // Create validation middleware
+    let validation_middleware = Arc::new(Validation::new(opcode_cost.clone(), bulk_memory_cost));
+
     // Create breakpoints middleware
-    let breakpoints_middleware = Arc::new(Breakpoints::new());
+    let breakpoints_middleware = if middleware_settings.runtime_breakpoints {
+        let breakpoints_middleware = Arc::new(Breakpoints::new());
+        protected_middlewares.push(breakpoints_middleware.clone());
+        Some(breakpoints_middleware)
+    } else {
+        None
+    };
 
     // Create opcode_control middleware
     let opcode_control_middleware = Arc::new(OpcodeControl::new(
         compilation_options.max_memory_grow,
         compilation_options.max_memory_grow_delta,
+        compilation_options.max_memory_pages,
         breakpoints_middleware.clone(),
     ));
 
+    // Create gas_profiling middleware
+    let gas_profiling_middleware = if middleware_settings.gas_profiling {
+        let gas_profiling_middleware = Arc::new(GasProfiling::new());
+        protected_middlewares.push(gas_profiling_middleware.clone());
+        Some(gas_profiling_middleware)
+    } else {
+        None
+    };
+
+    // Create opcode_counting middleware
+    let opcode_counting_middleware = if middleware_settings.opcode_histogram {
+        let opcode_counting_middleware = Arc::new(OpcodeCounting::new());
+        protected_middlewares.push(opcode_counting_middleware.clone());
+        Some(opcode_counting_middleware)
+    } else {
+        None
+    };
+
+    // Create execution_trace middleware
+    let execution_trace_middleware = if middleware_settings.execution_trace {
+        let execution_trace_middleware = Arc::new(ExecutionTrace::new(
+            middleware_settings.execution_trace_length,
+        ));
+        protected_middlewares.push(execution_trace_middleware.clone());
+        Some(execution_trace_middleware)
+    } else {
+        None
+    };
+
     // Create metering middleware
-    let metering_middleware = Arc::new(Metering::new(
-        compilation_options.gas_limit,
-        compilation_options.unmetered_locals,
-        executor_data.borrow().get_opcode_cost(),
-        breakpoints_middleware.clone(),
-    ));
+    let metering_middleware = if middleware_settings.metering {
+        let metering_middleware = Arc::new(
+            Metering::new(
+                compilation_options.gas_limit,
+                compilation_options.unmetered_locals,
+                opcode_cost,
+                breakpoints_middleware.clone(),
+                gas_profiling_middleware.clone(),
+                opcode_counting_middleware.clone(),
+                execution_trace_middleware.clone(),
+            )
+            .with_bulk_memory_cost(bulk_memory_cost),
+        );
+        protected_middlewares.push(metering_middleware.clone());
+        Some(metering_middleware)
+    } else {
+        None
+    };
+
+    // Create reset_tracking middleware
+    let reset_tracking_middleware = Arc::new(ResetTracking::new());
+    protected_middlewares.push(reset_tracking_middleware.clone());
 
     // Create protected_globals middleware
-    let protected_globals_middleware = Arc::new(ProtectedGlobals::new(vec![
-        breakpoints_middleware.clone(),
-        metering_middleware.clone(),
-    ]));
+    let protected_globals_middleware = Arc::new(ProtectedGlobals::new(protected_middlewares));
 
+    trace!("Adding validation middleware ...");
+    compiler.push_middleware(validation_middleware.clone());
     trace!("Adding protected_globals middleware ...");
     compiler.push_middleware(protected_globals_middleware);
-    trace!("Adding metering middleware ...");
-    compiler.push_middleware(metering_middleware);
+    if let Some(metering_middleware) = metering_middleware {
+        trace!("Adding metering middleware ...");
+        compiler.push_middleware(metering_middleware);
+    }
+    if let Some(gas_profiling_middleware) = gas_profiling_middleware {
+        trace!("Adding gas_profiling middleware ...");
+        compiler.push_middleware(gas_profiling_middleware);
+    }
+    if let Some(opcode_counting_middleware) = opcode_counting_middleware {
+        trace!("Adding opcode_counting middleware ...");
+        compiler.push_middleware(opcode_counting_middleware);
+    }
+    if let Some(execution_trace_middleware) = execution_trace_middleware {
+        trace!("Adding execution_trace middleware ...");
+        compiler.push_middleware(execution_trace_middleware);
+    }
     trace!("Adding opcode_control middleware ...");
     compiler.push_middleware(opcode_control_middleware);
-    trace!("Adding breakpoints middleware ...");
-    compiler.push_middleware(breakpoints_middleware);
+    if let Some(breakpoints_middleware) = breakpoints_middleware {
+        trace!("Adding breakpoints middleware ...");
+        compiler.push_middleware(breakpoints_middleware);
+    }
 
-    if compilation_options.opcode_trace {
-        // Create opcode_tracer middleware
-        let opcode_tracer_middleware = Arc::new(OpcodeTracer::new());
+    if let Some(opcode_tracer_middleware) = opcode_tracer_middleware {
         trace!("Adding opcode_tracer middleware ...");
         compiler.push_middleware(opcode_tracer_middleware);
     }
+
+    trace!("Adding reset_tracking middleware ...");
+    compiler.push_middleware(reset_tracking_middleware);
+
+    if let Some(snapshot_globals_middleware) = snapshot_globals_middleware {
+        trace!("Adding snapshot_globals middleware ...");
+        compiler.push_middleware(snapshot_globals_middleware);
+    }
+
+    validation_middleware
 }
 
 impl Instance for WasmerInstance {
-    fn call(&self, func_name: &str) -> Result<(), String> {
+    fn call(&self, func_name: &str) -> Result<(), ExecutorError> {
         trace!("Rust instance call: {func_name}");
 
-        let func = self
-            .wasmer_instance
-            .exports
-            .get_function(func_name)
-            .map_err(|_| "function not found".to_string())?;
+        let func = self.get_function(func_name)?;
+        self.start_execution_trace()?;
 
-        match func.call(&[]) {
+        let result = run_in_instance(self, || func.call(&[]));
+        self.finish_execution_trace(result.is_err())?;
+        match result {
             Ok(_) => {
                 trace!("Call succeeded: {func_name}");
                 Ok(())
             }
             Err(err) => {
                 trace!("Call failed: {func_name} - {err}");
-                Err(err.to_string())
+                Err(self.trap_error(err))
+            }
+        }
+    }
+
+    fn call_with_report(&self, func_name: &str) -> Result<CallOutcome, ExecutorError> {
+        trace!("Rust instance call with report: {func_name}");
+
+        let func = self.get_function(func_name)?;
+        let points_used_before = self.current_points_used()?;
+        self.start_execution_trace()?;
+
+        let result = run_in_instance(self, || func.call(&[]));
+        self.finish_execution_trace(result.is_err())?;
+        let trap = match result {
+            Ok(_) => {
+                trace!("Call succeeded: {func_name}");
+                None
+            }
+            Err(err) => {
+                trace!("Call failed: {func_name} - {err}");
+                Some(call_trap(err))
+            }
+        };
+
+        Ok(CallOutcome {
+            breakpoint: self.current_breakpoint(),
+            points_used_before,
+            points_used_after: self.current_points_used()?,
+            trap,
+        })
+    }
+
+    fn call_typed(&self, func_name: &str, args: &[Value]) -> Result<Vec<Value>, ExecutorError> {
+        trace!("Rust instance typed call: {func_name}");
+
+        let func = self.get_function(func_name)?;
+        let func_type = func.ty();
+        if func_type
+            .results()
+            .iter()
+            .any(|result| value_type(result).is_none())
+        {
+            return Err(ExecutorError::UnsupportedSignature(func_name.to_string()));
+        }
+        let wasmer_args = typed_arguments(func_name, func_type, args)?;
+        self.start_execution_trace()?;
+
+        let result = run_in_instance(self, || func.call(&wasmer_args));
+        self.finish_execution_trace(result.is_err())?;
+        match result {
+            Ok(results) => {
+                trace!("Call succeeded: {func_name}");
+                results
+                    .iter()
+                    .map(|result| typed_value(func_name, result))
+                    .collect()
+            }
+            Err(err) => {
+                trace!("Call failed: {func_name} - {err}");
+                Err(self.trap_error(err))
             }
         }
     }
@@ -269,19 +1038,22 @@ impl Instance for WasmerInstance {
             .collect()
     }
 
-    fn set_points_limit(&self, limit: u64) -> Result<(), String> {
+    fn set_points_limit(&self, limit: u64) -> Result<(), ExecutorError> {
+        self.check_metering_enabled()?;
         set_points_limit(&self.wasmer_instance, limit)
     }
 
-    fn set_points_used(&self, points: u64) -> Result<(), String> {
+    fn set_points_used(&self, points: u64) -> Result<(), ExecutorError> {
+        self.check_metering_enabled()?;
         set_points_used(&self.wasmer_instance, points)
     }
 
-    fn get_points_used(&self) -> Result<u64, String> {
+    fn get_points_used(&self) -> Result<u64, ExecutorError> {
+        self.check_metering_enabled()?;
         get_points_used(&self.wasmer_instance)
     }
 
-    fn memory_length(&self) -> Result<u64, String> {
+    fn memory_length(&self) -> Result<u64, ExecutorError> {
         let result = self.get_memory_ref();
         match result {
             Ok(memory) => Ok(memory.data_size()),
@@ -289,7 +1061,7 @@ impl Instance for WasmerInstance {
         }
     }
 
-    fn memory_ptr(&self) -> Result<*mut u8, String> {
+    fn memory_ptr(&self) -> Result<*mut u8, ExecutorError> {
         let result = self.get_memory_ref();
         match result {
             Ok(memory) => Ok(memory.data_ptr()),
@@ -302,9 +1074,20 @@ impl Instance for WasmerInstance {
         match result {
             Ok(memory) => unsafe {
                 let mem_data = memory.data_unchecked();
+                let in_bounds = mem_ptr >= 0
+                    && mem_length >= 0
+                    && mem_ptr
+                        .checked_add(mem_length)
+                        .is_some_and(|end| (end as usize) < mem_data.len());
+                if !in_bounds {
+                    return Err(ExecutorError::MemoryOutOfBounds {
+                        offset: mem_ptr as u64,
+                        length: mem_length as u64,
+                    });
+                }
                 Ok(&mem_data[mem_ptr as usize..=(mem_ptr + mem_length) as usize])
             },
-            Err(err) => Err(err.into()),
+            Err(err) => Err(err),
         }
     }
 
@@ -313,41 +1096,239 @@ impl Instance for WasmerInstance {
         match result {
             Ok(memory) => unsafe {
                 let mem_data = memory.data_unchecked_mut();
+                let in_bounds = mem_ptr >= 0
+                    && (mem_ptr as usize)
+                        .checked_add(data.len())
+                        .is_some_and(|end| end <= mem_data.len());
+                if !in_bounds {
+                    return Err(ExecutorError::MemoryOutOfBounds {
+                        offset: mem_ptr as u64,
+                        length: data.len() as u64,
+                    });
+                }
                 mem_data[mem_ptr as usize..mem_ptr as usize + data.len()].copy_from_slice(data);
-                Ok(())
+                let start = mem_ptr as u64;
+                mark_memory_written(&self.wasmer_instance, start..start + data.len() as u64)
             },
-            Err(err) => Err(err.into()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn mark_memory_written(
+        &self,
+        mem_ptr: MemPtr,
+        mem_length: MemLength,
+    ) -> Result<(), ExecutorError> {
+        let memory = self.get_memory_ref()?;
+        let in_bounds = mem_ptr >= 0
+            && mem_length >= 0
+            && mem_ptr
+                .checked_add(mem_length)
+                .is_some_and(|end| end as u64 <= memory.data_size());
+        if !in_bounds {
+            return Err(ExecutorError::MemoryOutOfBounds {
+                offset: mem_ptr as u64,
+                length: mem_length as u64,
+            });
         }
+        let start = mem_ptr as u64;
+        mark_memory_written(&self.wasmer_instance, start..start + mem_length as u64)
     }
 
     fn memory_grow(&self, by_num_pages: u32) -> Result<u32, ExecutorError> {
         let result = self.get_memory_ref();
         match result {
             Ok(memory) => {
-                let pages = memory.grow(wasmer::Pages(by_num_pages))?;
+                let max_memory_pages = self.cache_header.max_memory_pages;
+                let requested_pages = memory.size().0 as u64 + by_num_pages as u64;
+                if requested_pages > max_memory_pages {
+                    return Err(ExecutorError::MemoryGrowLimit {
+                        requested: requested_pages,
+                        max: max_memory_pages,
+                    });
+                }
+
+                let pages = memory
+                    .grow(wasmer::Pages(by_num_pages))
+                    .map_err(|err| ExecutorError::Runtime(err.to_string()))?;
                 Ok(pages.0)
             }
-            Err(err) => Err(err.into()),
+            Err(err) => Err(err),
         }
     }
 
-    fn set_breakpoint_value(&self, value: BreakpointValue) -> Result<(), String> {
+    fn set_breakpoint_value(&self, value: BreakpointValue) -> Result<(), ExecutorError> {
+        self.check_runtime_breakpoints_enabled()?;
         set_breakpoint_value(&self.wasmer_instance, value.as_u64())
     }
 
-    fn get_breakpoint_value(&self) -> Result<BreakpointValue, String> {
-        get_breakpoint_value(&self.wasmer_instance)?.try_into()
+    fn get_breakpoint_value(&self) -> Result<BreakpointValue, ExecutorError> {
+        self.check_runtime_breakpoints_enabled()?;
+        let value = get_breakpoint_value(&self.wasmer_instance)?;
+        BreakpointValue::try_from(value).map_err(|_| ExecutorError::UnknownBreakpointValue(value))
     }
 
-    fn reset(&self) -> Result<(), String> {
-        self.wasmer_instance.reset()
+    fn reset(&self) -> Result<(), ExecutorError> {
+        self.wasmer_instance
+            .reset()
+            .map_err(ExecutorError::Runtime)?;
+
+        // The globals injected by the middlewares keep the state of the previous call
+        reset_memory_grow_count(&self.wasmer_instance)?;
+        reset_written_range(&self.wasmer_instance)?;
+        if self.cache_header.middleware_settings.metering {
+            set_points_used(&self.wasmer_instance, 0)?;
+        }
+        if self.cache_header.middleware_settings.runtime_breakpoints {
+            set_breakpoint_value(&self.wasmer_instance, BREAKPOINT_VALUE_NO_BREAKPOINT)?;
+        }
+        Ok(())
     }
 
-    fn cache(&self) -> Result<Vec<u8>, String> {
-        let module = self.wasmer_instance.module();
-        match module.serialize() {
-            Ok(bytes) => Ok(bytes),
-            Err(err) => Err(err.to_string()),
+    fn reset_changed_pages(&self) -> Result<usize, ExecutorError> {
+        let memory = self.get_memory_ref()?;
+        let initial_globals = match &self.initial_globals {
+            Some(initial_globals) if memory.size().0 as usize == self.initial_memory.len() => {
+                initial_globals
+            }
+            _ => {
+                // Memory cannot shrink, only the full reset brings it back to its initial size
+                self.reset()?;
+                return Ok(self.initial_memory.len());
+            }
+        };
+
+        let restored_pages = match get_written_range(&self.wasmer_instance)? {
+            Some(written) => self.initial_memory.restore_written_pages(memory, written),
+            None => 0,
+        };
+
+        // This includes the globals injected by the middlewares, and so the written range
+        for (global, value) in initial_globals {
+            global
+                .set(wasmer_value(*value))
+                .map_err(|err| ExecutorError::Runtime(err.message()))?;
         }
+        Ok(restored_pages)
+    }
+
+    fn gas_profile(&self) -> Result<Vec<GasProfileEntry>, ExecutorError> {
+        self.check_gas_profiling_enabled()?;
+        get_gas_profile(&self.wasmer_instance)
+    }
+
+    fn opcode_histogram(&self) -> Result<OpcodeHistogram, ExecutorError> {
+        self.check_opcode_histogram_enabled()?;
+        get_opcode_histogram(&self.wasmer_instance)
+    }
+
+    fn reset_opcode_histogram(&self) -> Result<(), ExecutorError> {
+        self.check_opcode_histogram_enabled()?;
+        reset_opcode_histogram(&self.wasmer_instance)
+    }
+
+    fn opcode_trace(&self) -> &[OpcodeTraceEntry] {
+        &self.opcode_trace
+    }
+
+    fn last_execution_trace(&self) -> Result<Option<Vec<ExecutionTraceEntry>>, ExecutorError> {
+        self.check_execution_trace_enabled()?;
+        Ok(self.last_execution_trace.lock().unwrap().clone())
+    }
+
+    fn cache(&self) -> Result<Vec<u8>, ExecutorError> {
+        encode_module(self.wasmer_instance.module(), &self.cache_header)
+    }
+
+    fn gas_schedule_version(&self) -> u32 {
+        self.cache_header.gas_schedule_version
+    }
+
+    fn snapshot(&self) -> Result<InstanceSnapshot, ExecutorError> {
+        self.check_snapshots_enabled()?;
+        let memory = self.get_memory_ref()?;
+        let globals = snapshot_globals(&self.wasmer_instance)
+            .iter()
+            .map(|global| {
+                numeric_value(&global.get()).ok_or_else(|| {
+                    ExecutorError::Runtime("only numeric globals can be snapshotted".to_string())
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(InstanceSnapshot {
+            code_hash: self.cache_header.code_hash,
+            memory: unsafe { memory.data_unchecked().to_vec() },
+            globals,
+            breakpoint_value: self.current_breakpoint(),
+        })
+    }
+
+    fn restore(&self, snapshot: &InstanceSnapshot) -> Result<(), ExecutorError> {
+        self.check_snapshots_enabled()?;
+        if snapshot.code_hash != self.cache_header.code_hash {
+            return Err(SnapshotError::CodeMismatch.into());
+        }
+
+        let globals = snapshot_globals(&self.wasmer_instance);
+        let globals_match = globals.len() == snapshot.globals.len()
+            && globals
+                .iter()
+                .zip(&snapshot.globals)
+                .all(|(global, value)| value_type(&global.ty().ty) == Some(value.value_type()));
+        if !globals_match {
+            return Err(SnapshotError::GlobalsMismatch.into());
+        }
+
+        let page_size = wasmer::WASM_PAGE_SIZE as u64;
+        let snapshot_len = snapshot.memory.len() as u64;
+        if !snapshot_len.is_multiple_of(page_size) {
+            return Err(SnapshotError::UnalignedMemory(snapshot_len).into());
+        }
+
+        let memory = self.get_memory_ref()?;
+        let current_len = memory.data_size();
+        if current_len > snapshot_len {
+            return Err(SnapshotError::MemoryGrown {
+                snapshot: snapshot_len,
+                current: current_len,
+            }
+            .into());
+        }
+        if current_len < snapshot_len {
+            // Restoring on a fresh instance of the same contract
+            let max_memory_pages = self.cache_header.max_memory_pages;
+            let snapshot_pages = snapshot_len / page_size;
+            if snapshot_pages > max_memory_pages {
+                return Err(ExecutorError::MemoryGrowLimit {
+                    requested: snapshot_pages,
+                    max: max_memory_pages,
+                });
+            }
+
+            let missing_pages = (snapshot_len - current_len) / page_size;
+            memory
+                .grow(wasmer::Pages(missing_pages as u32))
+                .map_err(|err| ExecutorError::Runtime(err.to_string()))?;
+
+            let grown_len = memory.data_size();
+            if grown_len != snapshot_len {
+                return Err(SnapshotError::MemoryNotRestored {
+                    snapshot: snapshot_len,
+                    current: grown_len,
+                }
+                .into());
+            }
+        }
+        unsafe { memory.data_unchecked_mut() }.copy_from_slice(&snapshot.memory);
+
+        for (global, value) in globals.iter().zip(&snapshot.globals) {
+            global
+                .set(wasmer_value(*value))
+                .map_err(|err| ExecutorError::Runtime(err.message()))?;
+        }
+
+        // The globals brought back the written range of the snapshot, but all of it was written
+        mark_memory_written(&self.wasmer_instance, 0..snapshot_len)
     }
 }
@@ -0,0 +1,138 @@
+use klever_chain_vm_executor::{ExecutorError, Instance};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Identifies the contract code of the instances in an `InstancePool`.
+pub type CodeHash = [u8; 32];
+
+/// Counters of an instance pool.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InstancePoolStats {
+    /// Number of checkouts served by a pooled instance.
+    pub hits: u64,
+
+    /// Number of checkouts that found no pooled instance for the code.
+    pub misses: u64,
+
+    /// Number of instances dropped at checkin, because the pool was full or the reset failed.
+    pub dropped: u64,
+
+    /// Number of instances currently in the pool.
+    pub len: usize,
+
+    /// Maximum number of instances the pool can hold, over all codes.
+    pub capacity: usize,
+}
+
+/// How an `InstancePool` resets the instances checked in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResetMode {
+    /// Uses `Instance::reset`, which re-initializes the whole memory.
+    #[default]
+    Full,
+
+    /// Uses `Instance::reset_changed_pages`, which only copies back the pages a call changed.
+    ChangedPages,
+}
+
+/// Keeps idle instances for reuse, keyed by the hash of their contract code.
+///
+/// Instances are reset when checked in, so that a checked out instance starts from a clean state.
+/// Instances compiled with different options should not share a pool.
+pub struct InstancePool {
+    max_instances_per_code: usize,
+    max_instances: usize,
+    reset_mode: ResetMode,
+    state: Mutex<InstancePoolState>,
+}
+
+struct InstancePoolState {
+    instances: HashMap<CodeHash, Vec<Box<dyn Instance>>>,
+    stats: InstancePoolStats,
+}
+
+impl InstancePool {
+    pub fn new(max_instances_per_code: usize, max_instances: usize) -> Self {
+        Self {
+            max_instances_per_code,
+            max_instances,
+            reset_mode: ResetMode::default(),
+            state: Mutex::new(InstancePoolState {
+                instances: HashMap::new(),
+                stats: InstancePoolStats {
+                    capacity: max_instances,
+                    ..Default::default()
+                },
+            }),
+        }
+    }
+
+    pub fn set_reset_mode(&mut self, reset_mode: ResetMode) {
+        self.reset_mode = reset_mode;
+    }
+
+    pub fn code_hash(wasm_bytes: &[u8]) -> CodeHash {
+        Sha256::digest(wasm_bytes).into()
+    }
+
+    /// Takes an idle instance of the given code, if there is one.
+    pub fn checkout(&self, code_hash: &CodeHash) -> Option<Box<dyn Instance>> {
+        let mut state = self.state.lock().unwrap();
+
+        let instance = state.instances.get_mut(code_hash).and_then(Vec::pop);
+        if instance.is_some() {
+            state.stats.hits += 1;
+            state.stats.len -= 1;
+            if state.instances[code_hash].is_empty() {
+                state.instances.remove(code_hash);
+            }
+        } else {
+            state.stats.misses += 1;
+        }
+        instance
+    }
+
+    /// Resets an instance and keeps it for reuse, unless the pool is full.
+    ///
+    /// An instance that cannot be reset is dropped, and the reset error is returned.
+    pub fn checkin(
+        &self,
+        code_hash: CodeHash,
+        instance: Box<dyn Instance>,
+    ) -> Result<(), ExecutorError> {
+        // Resetting touches the whole memory, so it is done without holding the lock
+        let reset_result = match self.reset_mode {
+            ResetMode::Full => instance.reset(),
+            ResetMode::ChangedPages => instance.reset_changed_pages().map(|_| ()),
+        };
+
+        let mut state = self.state.lock().unwrap();
+
+        if let Err(err) = reset_result {
+            state.stats.dropped += 1;
+            return Err(err);
+        }
+
+        let code_instances = state.instances.get(&code_hash).map_or(0, Vec::len);
+        if state.stats.len >= self.max_instances || code_instances >= self.max_instances_per_code {
+            state.stats.dropped += 1;
+            return Ok(());
+        }
+
+        state.instances.entry(code_hash).or_default().push(instance);
+        state.stats.len += 1;
+        Ok(())
+    }
+
+    /// Drops all idle instances, e.g. after the opcode costs changed.
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.instances.clear();
+        state.stats.len = 0;
+    }
+
+    pub fn stats(&self) -> InstancePoolStats {
+        self.state.lock().unwrap().stats
+    }
+}
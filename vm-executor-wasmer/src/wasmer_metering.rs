@@ -1,10 +1,15 @@
-use crate::wasmer_breakpoints::{Breakpoints, BREAKPOINT_VALUE_OUT_OF_GAS};
+use crate::wasmer_breakpoints::{
+    inject_breakpoint_condition_or_trap, Breakpoints, BREAKPOINT_VALUE_OUT_OF_GAS,
+};
+use crate::wasmer_execution_trace::{ExecutionTrace, FunctionExecutionTrace};
+use crate::wasmer_gas_profiling::GasProfiling;
 use crate::wasmer_helpers::{
     create_global_index, is_control_flow_operator, MiddlewareWithProtectedGlobals,
 };
+use crate::wasmer_opcode_histogram::{FunctionOpcodeCounts, OpcodeCounting};
 use crate::{get_local_cost, get_opcode_cost};
 use loupe::{MemoryUsage, MemoryUsageTracker};
-use klever_chain_vm_executor::OpcodeCost;
+use klever_chain_vm_executor::{BulkMemoryCost, ExecutorError, OpcodeCost};
 use std::mem;
 use std::sync::{Arc, Mutex};
 use wasmer::wasmparser::Operator;
@@ -16,12 +21,13 @@ use wasmer_types::{GlobalIndex, ModuleInfo};
 
 const METERING_POINTS_LIMIT: &str = "metering_points_limit";
 const METERING_POINTS_USED: &str = "metering_points_used";
-const MAX_LOCAL_COUNT: u32 = 4000;
+const METERING_OPERAND_BACKUP: &str = "metering_operand_backup";
 
 #[derive(Clone, Debug, MemoryUsage)]
 struct MeteringGlobalIndexes {
     points_limit_global_index: GlobalIndex,
     points_used_global_index: GlobalIndex,
+    operand_backup_global_index: GlobalIndex,
 }
 
 #[derive(Debug)]
@@ -29,7 +35,11 @@ pub(crate) struct Metering {
     points_limit: u64,
     unmetered_locals: usize,
     opcode_cost: Arc<Mutex<OpcodeCost>>,
-    breakpoints_middleware: Arc<Breakpoints>,
+    bulk_memory_cost: Option<BulkMemoryCost>,
+    breakpoints_middleware: Option<Arc<Breakpoints>>,
+    gas_profiling_middleware: Option<Arc<GasProfiling>>,
+    opcode_counting_middleware: Option<Arc<OpcodeCounting>>,
+    execution_trace_middleware: Option<Arc<ExecutionTrace>>,
     global_indexes: Mutex<Option<MeteringGlobalIndexes>>,
 }
 
@@ -38,17 +48,34 @@ impl Metering {
         points_limit: u64,
         unmetered_locals: usize,
         opcode_cost: Arc<Mutex<OpcodeCost>>,
-        breakpoints_middleware: Arc<Breakpoints>,
+        breakpoints_middleware: Option<Arc<Breakpoints>>,
+        gas_profiling_middleware: Option<Arc<GasProfiling>>,
+        opcode_counting_middleware: Option<Arc<OpcodeCounting>>,
+        execution_trace_middleware: Option<Arc<ExecutionTrace>>,
     ) -> Self {
         Self {
             points_limit,
             unmetered_locals,
             opcode_cost,
+            bulk_memory_cost: None,
             breakpoints_middleware,
+            gas_profiling_middleware,
+            opcode_counting_middleware,
+            execution_trace_middleware,
             global_indexes: Mutex::new(None),
         }
     }
 
+    /// Meters the bulk memory operators, which the validation middleware rejects without their
+    /// costs.
+    pub(crate) fn with_bulk_memory_cost(
+        mut self,
+        bulk_memory_cost: Option<BulkMemoryCost>,
+    ) -> Self {
+        self.bulk_memory_cost = bulk_memory_cost;
+        self
+    }
+
     fn get_points_limit_global_index(&self) -> GlobalIndex {
         self.global_indexes
             .lock()
@@ -66,10 +93,16 @@ impl Metering {
             .unwrap()
             .points_used_global_index
     }
-}
 
-unsafe impl Send for Metering {}
-unsafe impl Sync for Metering {}
+    fn get_operand_backup_global_index(&self) -> GlobalIndex {
+        self.global_indexes
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .operand_backup_global_index
+    }
+}
 
 impl MemoryUsage for Metering {
     fn size_of_val(&self, tracker: &mut dyn MemoryUsageTracker) -> usize {
@@ -81,14 +114,29 @@ impl MemoryUsage for Metering {
 impl ModuleMiddleware for Metering {
     fn generate_function_middleware(
         &self,
-        _local_function_index: LocalFunctionIndex,
+        local_function_index: LocalFunctionIndex,
     ) -> Box<dyn FunctionMiddleware> {
         Box::new(FunctionMetering {
             accumulated_cost: Default::default(),
             unmetered_locals: self.unmetered_locals,
             opcode_cost: self.opcode_cost.clone(),
+            bulk_memory_cost: self.bulk_memory_cost,
             breakpoints_middleware: self.breakpoints_middleware.clone(),
             global_indexes: self.global_indexes.lock().unwrap().clone().unwrap(),
+            profile_points_global_index: self
+                .gas_profiling_middleware
+                .as_ref()
+                .map(|gas_profiling| gas_profiling.get_points_global_index(local_function_index)),
+            opcode_counts: self
+                .opcode_counting_middleware
+                .as_ref()
+                .map(|opcode_counting| opcode_counting.function_opcode_counts()),
+            execution_trace: self
+                .execution_trace_middleware
+                .as_ref()
+                .map(|execution_trace| {
+                    execution_trace.function_execution_trace(local_function_index)
+                }),
         })
     }
 
@@ -104,6 +152,11 @@ impl ModuleMiddleware for Metering {
                 points_limit,
             ),
             points_used_global_index: create_global_index(module_info, METERING_POINTS_USED, 0),
+            operand_backup_global_index: create_global_index(
+                module_info,
+                METERING_OPERAND_BACKUP,
+                0,
+            ),
         });
     }
 }
@@ -113,6 +166,7 @@ impl MiddlewareWithProtectedGlobals for Metering {
         vec![
             self.get_points_limit_global_index().as_u32(),
             self.get_points_used_global_index().as_u32(),
+            self.get_operand_backup_global_index().as_u32(),
         ]
     }
 }
@@ -122,8 +176,18 @@ struct FunctionMetering {
     accumulated_cost: u64,
     unmetered_locals: usize,
     opcode_cost: Arc<Mutex<OpcodeCost>>,
-    breakpoints_middleware: Arc<Breakpoints>,
+    bulk_memory_cost: Option<BulkMemoryCost>,
+    breakpoints_middleware: Option<Arc<Breakpoints>>,
     global_indexes: MeteringGlobalIndexes,
+
+    /// The global counting the points used by this function, if gas profiling is enabled.
+    profile_points_global_index: Option<GlobalIndex>,
+
+    /// The operators of the current block, if the opcode histogram is enabled.
+    opcode_counts: Option<FunctionOpcodeCounts>,
+
+    /// Records the basic blocks of this function, if the execution trace is enabled.
+    execution_trace: Option<FunctionExecutionTrace>,
 }
 
 impl FunctionMetering {
@@ -140,6 +204,95 @@ impl FunctionMetering {
                 global_index: self.global_indexes.points_used_global_index.as_u32(),
             },
         ]);
+
+        if let Some(profile_points_global_index) = self.profile_points_global_index {
+            state.extend(&[
+                Operator::GlobalGet {
+                    global_index: profile_points_global_index.as_u32(),
+                },
+                Operator::I64Const {
+                    value: self.accumulated_cost as i64,
+                },
+                Operator::I64Add,
+                Operator::GlobalSet {
+                    global_index: profile_points_global_index.as_u32(),
+                },
+            ]);
+        }
+    }
+
+    /// Charges the accumulated cost together with the length dependent cost of
+    /// a bulk memory operator, whose length operand is on top of the stack.
+    fn inject_points_used_increment_by_length(
+        &self,
+        state: &mut MiddlewareReaderState,
+        cost_per_byte: u32,
+    ) {
+        // Backup the length operand in order to duplicate it: once for the cost
+        // and again for the operator itself.
+        // The backup global is an i64, while the operand is an i32.
+        state.extend(&[
+            Operator::I64ExtendI32U,
+            Operator::GlobalSet {
+                global_index: self.global_indexes.operand_backup_global_index.as_u32(),
+            },
+            Operator::GlobalGet {
+                global_index: self.global_indexes.points_used_global_index.as_u32(),
+            },
+            Operator::I64Const {
+                value: self.accumulated_cost as i64,
+            },
+            Operator::I64Add,
+            Operator::GlobalGet {
+                global_index: self.global_indexes.operand_backup_global_index.as_u32(),
+            },
+            Operator::I64Const {
+                value: cost_per_byte as i64,
+            },
+            Operator::I64Mul,
+            Operator::I64Add,
+            Operator::GlobalSet {
+                global_index: self.global_indexes.points_used_global_index.as_u32(),
+            },
+        ]);
+
+        if let Some(profile_points_global_index) = self.profile_points_global_index {
+            state.extend(&[
+                Operator::GlobalGet {
+                    global_index: profile_points_global_index.as_u32(),
+                },
+                Operator::I64Const {
+                    value: self.accumulated_cost as i64,
+                },
+                Operator::I64Add,
+                Operator::GlobalGet {
+                    global_index: self.global_indexes.operand_backup_global_index.as_u32(),
+                },
+                Operator::I64Const {
+                    value: cost_per_byte as i64,
+                },
+                Operator::I64Mul,
+                Operator::I64Add,
+                Operator::GlobalSet {
+                    global_index: profile_points_global_index.as_u32(),
+                },
+            ]);
+        }
+    }
+
+    fn inject_opcode_counters_increment(&mut self, state: &mut MiddlewareReaderState) {
+        if let Some(opcode_counts) = &mut self.opcode_counts {
+            opcode_counts.inject_counters_increment(state);
+        }
+    }
+
+    fn inject_operand_restore(&self, state: &mut MiddlewareReaderState) {
+        state.extend(&[
+            Operator::GlobalGet {
+                global_index: self.global_indexes.operand_backup_global_index.as_u32(),
+            },
+            Operator::I32WrapI64,
+        ]);
     }
 
     fn inject_out_of_gas_check(&self, state: &mut MiddlewareReaderState) {
@@ -152,8 +305,11 @@ impl FunctionMetering {
             },
             Operator::I64GeU,
         ]);
-        self.breakpoints_middleware
-            .inject_breakpoint_condition(state, BREAKPOINT_VALUE_OUT_OF_GAS);
+        inject_breakpoint_condition_or_trap(
+            &self.breakpoints_middleware,
+            state,
+            BREAKPOINT_VALUE_OUT_OF_GAS,
+        );
     }
 }
 
@@ -166,19 +322,33 @@ impl FunctionMiddleware for FunctionMetering {
         // Get the cost of the current operator, and add it to the accumulator.
         // This needs to be done before the metering logic, to prevent operators like `Call` from escaping metering in some
         // corner cases.
-        let option = get_opcode_cost(&operator, &self.opcode_cost.lock().unwrap());
-        match option {
-            Some(cost) => self.accumulated_cost += cost as u64,
-            None => {
-                return Err(MiddlewareError::new(
-                    "metering_middleware",
-                    format!("Unsupported operator: {operator:?}"),
-                ))
-            }
+        // The operators without a cost were already rejected by the validation middleware.
+        let cost = get_opcode_cost(&operator, &self.opcode_cost.lock().unwrap())
+            .or_else(|| get_bulk_memory_cost(&operator, self.bulk_memory_cost.as_ref()?))
+            .unwrap_or_default();
+        let cost_per_byte = self.bulk_memory_cost.as_ref().and_then(|bulk_memory_cost| {
+            get_bulk_memory_cost_per_byte(&operator, bulk_memory_cost)
+        });
+        self.accumulated_cost += cost as u64;
+        if let Some(opcode_counts) = &mut self.opcode_counts {
+            opcode_counts.count(&operator);
+        }
+        if let Some(execution_trace) = &mut self.execution_trace {
+            execution_trace.feed(&operator, state);
         }
 
-        if is_control_flow_operator(&operator) {
+        // The length of bulk memory operators is only known at runtime, and the gas
+        // must be checked before they run, so that they cannot work for free.
+        if let Some(cost_per_byte) = cost_per_byte {
+            self.inject_points_used_increment_by_length(state, cost_per_byte);
+            self.inject_opcode_counters_increment(state);
+            self.inject_out_of_gas_check(state);
+            self.inject_operand_restore(state);
+
+            self.accumulated_cost = 0;
+        } else if is_control_flow_operator(&operator) {
             self.inject_points_used_increment(state);
+            self.inject_opcode_counters_increment(state);
             self.inject_out_of_gas_check(state);
 
             self.accumulated_cost = 0;
@@ -190,8 +360,6 @@ impl FunctionMiddleware for FunctionMetering {
     }
 
     fn feed_local_count(&mut self, count: u32) -> Result<(), MiddlewareError> {
-        check_local_count_exceeded(count)?;
-
         let unmetered_locals = self.unmetered_locals as u32;
         if count > unmetered_locals {
             let metered_locals = count - unmetered_locals;
@@ -204,55 +372,64 @@ impl FunctionMiddleware for FunctionMetering {
     }
 }
 
-pub(crate) fn set_points_limit(instance: &Instance, limit: u64) -> Result<(), String> {
+/// The bulk memory operators are not part of the generated `get_opcode_cost`.
+fn get_bulk_memory_cost(op: &Operator, bulk_memory_cost: &BulkMemoryCost) -> Option<u32> {
+    match op {
+        Operator::DataDrop { .. } => Some(bulk_memory_cost.data_drop),
+        Operator::MemoryCopy { .. } => Some(bulk_memory_cost.memory_copy),
+        Operator::MemoryFill { .. } => Some(bulk_memory_cost.memory_fill),
+        Operator::MemoryInit { .. } => Some(bulk_memory_cost.memory_init),
+        _ => None,
+    }
+}
+
+fn get_bulk_memory_cost_per_byte(op: &Operator, bulk_memory_cost: &BulkMemoryCost) -> Option<u32> {
+    match op {
+        Operator::MemoryCopy { .. } => Some(bulk_memory_cost.memory_copy_per_byte),
+        Operator::MemoryFill { .. } => Some(bulk_memory_cost.memory_fill_per_byte),
+        Operator::MemoryInit { .. } => Some(bulk_memory_cost.memory_init_per_byte),
+        _ => None,
+    }
+}
+
+pub(crate) fn set_points_limit(instance: &Instance, limit: u64) -> Result<(), ExecutorError> {
     let result = instance.exports.get_global(METERING_POINTS_LIMIT);
     match result {
         Ok(global) => {
             let result = global.set(limit.into());
             match result {
                 Ok(_) => Ok(()),
-                Err(err) => Err(err.message()),
+                Err(err) => Err(ExecutorError::Runtime(err.message())),
             }
         }
-        Err(err) => Err(err.to_string()),
+        Err(err) => Err(ExecutorError::Runtime(err.to_string())),
     }
 }
 
-pub(crate) fn set_points_used(instance: &Instance, points: u64) -> Result<(), String> {
+pub(crate) fn set_points_used(instance: &Instance, points: u64) -> Result<(), ExecutorError> {
     let result = instance.exports.get_global(METERING_POINTS_USED);
     match result {
         Ok(global) => {
             let result = global.set(points.into());
             match result {
                 Ok(_) => Ok(()),
-                Err(err) => Err(err.message()),
+                Err(err) => Err(ExecutorError::Runtime(err.message())),
             }
         }
-        Err(err) => Err(err.to_string()),
+        Err(err) => Err(ExecutorError::Runtime(err.to_string())),
     }
 }
 
-pub(crate) fn get_points_used(instance: &Instance) -> Result<u64, String> {
+pub(crate) fn get_points_used(instance: &Instance) -> Result<u64, ExecutorError> {
     let result = instance.exports.get_global(METERING_POINTS_USED);
     match result {
         Ok(global) => {
             let result = global.get().try_into();
             match result {
                 Ok(points) => Ok(points),
-                Err(err) => Err(err.to_string()),
+                Err(err) => Err(ExecutorError::Runtime(err.to_string())),
             }
         }
-        Err(err) => Err(err.to_string()),
+        Err(err) => Err(ExecutorError::Runtime(err.to_string())),
     }
 }
-
-fn check_local_count_exceeded(count: u32) -> Result<(), MiddlewareError> {
-    if count > MAX_LOCAL_COUNT {
-        return Err(MiddlewareError::new(
-            "metering_middleware",
-            format!("maximum number of locals({MAX_LOCAL_COUNT}) exceeded({count})"),
-        ));
-    }
-
-    Ok(())
-}
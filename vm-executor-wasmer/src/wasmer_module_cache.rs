@@ -0,0 +1,134 @@
+use crate::wasmer_initial_memory::InitialMemory;
+use klever_chain_vm_executor::{BulkMemoryCost, CompilationOptions, ModuleCacheStats, OpcodeCost};
+use lru::LruCache;
+use sha2::{Digest, Sha256};
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use wasmer::Module;
+
+pub(crate) const DEFAULT_MODULE_CACHE_CAPACITY: usize = 64;
+
+/// Identifies a compiled module.
+///
+/// Only the options that change the generated code take part in the key. The gas limit,
+/// for example, is applied to each instance after instantiation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct ModuleCacheKey {
+    code_hash: [u8; 32],
+    unmetered_locals: usize,
+    max_memory_grow: usize,
+    max_memory_grow_delta: usize,
+    max_memory_pages: u64,
+    metering: bool,
+    runtime_breakpoints: bool,
+    gas_profiling: bool,
+    opcode_histogram: bool,
+    execution_trace: bool,
+    execution_trace_length: u32,
+    snapshots: bool,
+    compiler: u8,
+    opcode_cost: OpcodeCost,
+    bulk_memory_cost: Option<BulkMemoryCost>,
+}
+
+impl ModuleCacheKey {
+    pub(crate) fn new(
+        wasm_bytes: &[u8],
+        compilation_options: &CompilationOptions,
+        opcode_cost: &OpcodeCost,
+        bulk_memory_cost: Option<BulkMemoryCost>,
+    ) -> Self {
+        Self {
+            code_hash: Sha256::digest(wasm_bytes).into(),
+            unmetered_locals: compilation_options.unmetered_locals,
+            max_memory_grow: compilation_options.max_memory_grow,
+            max_memory_grow_delta: compilation_options.max_memory_grow_delta,
+            max_memory_pages: compilation_options.max_memory_pages,
+            metering: compilation_options.metering,
+            runtime_breakpoints: compilation_options.runtime_breakpoints,
+            gas_profiling: compilation_options.gas_profiling,
+            opcode_histogram: compilation_options.opcode_histogram,
+            execution_trace: compilation_options.execution_trace,
+            execution_trace_length: compilation_options.execution_trace_length,
+            snapshots: compilation_options.snapshots,
+            compiler: compilation_options.compiler,
+            opcode_cost: opcode_cost.clone(),
+            bulk_memory_cost,
+        }
+    }
+}
+
+/// Bounded LRU of the modules compiled by an executor.
+///
+/// The initial memory of each module is kept with it, so that its instances share it.
+pub(crate) struct ModuleCache {
+    modules: LruCache<ModuleCacheKey, (Module, Arc<InitialMemory>)>,
+    capacity: usize,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl ModuleCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            modules: LruCache::new(lru_capacity(capacity)),
+            capacity,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+
+    pub(crate) fn get(&mut self, key: &ModuleCacheKey) -> Option<(Module, Arc<InitialMemory>)> {
+        let module = self.modules.get(key).cloned();
+        if module.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        module
+    }
+
+    pub(crate) fn insert(
+        &mut self,
+        key: ModuleCacheKey,
+        module: Module,
+        initial_memory: Arc<InitialMemory>,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if let Some((evicted_key, _)) = self.modules.push(key.clone(), (module, initial_memory)) {
+            if evicted_key != key {
+                self.evictions += 1;
+            }
+        }
+    }
+
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        let len_before = self.modules.len();
+        if capacity == 0 {
+            self.modules.clear();
+        }
+        self.modules.resize(lru_capacity(capacity));
+        self.evictions += (len_before - self.modules.len()) as u64;
+        self.capacity = capacity;
+    }
+
+    pub(crate) fn stats(&self) -> ModuleCacheStats {
+        ModuleCacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+            len: self.modules.len(),
+            capacity: self.capacity,
+        }
+    }
+}
+
+/// The LRU cannot be empty, a zero capacity is handled by never inserting.
+fn lru_capacity(capacity: usize) -> NonZeroUsize {
+    NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN)
+}
@@ -0,0 +1,134 @@
+use crate::wasmer_import_validation::{convert_types, invalid_module};
+use klever_chain_vm_executor::{
+    ExecutorError, ModuleExport, ModuleImport, ModuleInfo, ModuleItemKind, ModuleMemory,
+    ModuleTable,
+};
+use wasmer::wasmparser::{
+    ExternalKind, ImportSectionEntryType, MemoryType, Parser, Payload, TableType, TypeDef,
+};
+use wasmer::FunctionType;
+
+/// Gathers the facts about a contract straight from its bytes.
+pub(crate) fn inspect_module(
+    wasm_bytes: &[u8],
+    max_memory_pages: u64,
+) -> Result<ModuleInfo, ExecutorError> {
+    let mut info = ModuleInfo {
+        max_memory_pages_allowed: max_memory_pages,
+        ..ModuleInfo::default()
+    };
+    let mut function_types = Vec::new();
+
+    for payload in Parser::new(0).parse_all(wasm_bytes) {
+        match payload.map_err(invalid_module)? {
+            Payload::TypeSection(reader) => {
+                for type_def in reader {
+                    function_types.push(match type_def.map_err(invalid_module)? {
+                        TypeDef::Func(func_type) => Some(FunctionType::new(
+                            convert_types(&func_type.params)?,
+                            convert_types(&func_type.returns)?,
+                        )),
+                        _ => None,
+                    });
+                }
+            }
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    let import = import.map_err(invalid_module)?;
+                    let (kind, signature) = match import.ty {
+                        ImportSectionEntryType::Function(type_index) => (
+                            ModuleItemKind::Function,
+                            function_types
+                                .get(type_index as usize)
+                                .cloned()
+                                .flatten()
+                                .map(|function_type| function_type.to_string()),
+                        ),
+                        ImportSectionEntryType::Table(table_type) => {
+                            info.tables.push(module_table(table_type, true));
+                            (ModuleItemKind::Table, None)
+                        }
+                        ImportSectionEntryType::Memory(memory_type) => {
+                            info.memories.push(module_memory(memory_type, true));
+                            (ModuleItemKind::Memory, None)
+                        }
+                        ImportSectionEntryType::Global(_) => (ModuleItemKind::Global, None),
+                        _ => (ModuleItemKind::Other, None),
+                    };
+                    info.imports.push(ModuleImport {
+                        namespace: import.module.to_string(),
+                        name: import.field.unwrap_or_default().to_string(),
+                        kind,
+                        signature,
+                    });
+                }
+            }
+            Payload::FunctionSection(reader) => {
+                info.function_count = reader.get_count() as usize;
+            }
+            Payload::TableSection(reader) => {
+                for table_type in reader {
+                    info.tables
+                        .push(module_table(table_type.map_err(invalid_module)?, false));
+                }
+            }
+            Payload::MemorySection(reader) => {
+                for memory_type in reader {
+                    info.memories
+                        .push(module_memory(memory_type.map_err(invalid_module)?, false));
+                }
+            }
+            Payload::ExportSection(reader) => {
+                for export in reader {
+                    let export = export.map_err(invalid_module)?;
+                    info.exports.push(ModuleExport {
+                        name: export.field.to_string(),
+                        kind: module_item_kind(export.kind),
+                        index: export.index,
+                    });
+                }
+            }
+            Payload::StartSection { .. } => {
+                info.has_start_function = true;
+            }
+            Payload::DataSection(reader) => {
+                for data in reader {
+                    info.data_segment_count += 1;
+                    info.data_segment_bytes += data.map_err(invalid_module)?.data.len();
+                }
+            }
+            Payload::CustomSection { name, .. } => {
+                info.custom_sections.push(name.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    Ok(info)
+}
+
+fn module_item_kind(kind: ExternalKind) -> ModuleItemKind {
+    match kind {
+        ExternalKind::Function => ModuleItemKind::Function,
+        ExternalKind::Table => ModuleItemKind::Table,
+        ExternalKind::Memory => ModuleItemKind::Memory,
+        ExternalKind::Global => ModuleItemKind::Global,
+        _ => ModuleItemKind::Other,
+    }
+}
+
+fn module_memory(memory_type: MemoryType, imported: bool) -> ModuleMemory {
+    ModuleMemory {
+        min_pages: memory_type.initial,
+        max_pages: memory_type.maximum,
+        imported,
+    }
+}
+
+fn module_table(table_type: TableType, imported: bool) -> ModuleTable {
+    ModuleTable {
+        min_size: table_type.initial,
+        max_size: table_type.maximum,
+        imported,
+    }
+}
@@ -3,15 +3,18 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+use klever_chain_vm_executor::ExecutorError;
 use loupe::{MemoryUsage, MemoryUsageTracker};
 use wasmer::{
-    wasmparser::Operator, FunctionMiddleware, LocalFunctionIndex, MiddlewareError,
+    wasmparser::Operator, FunctionMiddleware, Instance, LocalFunctionIndex, MiddlewareError,
     MiddlewareReaderState, ModuleMiddleware,
 };
 use wasmer_types::{GlobalIndex, ModuleInfo};
 
 use crate::{
-    wasmer_breakpoints::{Breakpoints, BREAKPOINT_VALUE_MEMORY_LIMIT},
+    wasmer_breakpoints::{
+        inject_breakpoint_condition_or_trap, Breakpoints, BREAKPOINT_VALUE_MEMORY_LIMIT,
+    },
     wasmer_helpers::{create_global_index, MiddlewareWithProtectedGlobals},
 };
 
@@ -28,7 +31,8 @@ struct OpcodeControlGlobalIndexes {
 pub(crate) struct OpcodeControl {
     max_memory_grow: usize,
     max_memory_grow_delta: usize,
-    breakpoints_middleware: Arc<Breakpoints>,
+    max_memory_pages: u64,
+    breakpoints_middleware: Option<Arc<Breakpoints>>,
     global_indexes: Mutex<Option<OpcodeControlGlobalIndexes>>,
 }
 
@@ -36,11 +40,13 @@ impl OpcodeControl {
     pub(crate) fn new(
         max_memory_grow: usize,
         max_memory_grow_delta: usize,
-        breakpoints_middleware: Arc<Breakpoints>,
+        max_memory_pages: u64,
+        breakpoints_middleware: Option<Arc<Breakpoints>>,
     ) -> Self {
         Self {
             max_memory_grow,
             max_memory_grow_delta,
+            max_memory_pages,
             breakpoints_middleware,
             global_indexes: Mutex::new(None),
         }
@@ -65,9 +71,6 @@ impl OpcodeControl {
     }
 }
 
-unsafe impl Send for OpcodeControl {}
-unsafe impl Sync for OpcodeControl {}
-
 impl MemoryUsage for OpcodeControl {
     fn size_of_val(&self, tracker: &mut dyn MemoryUsageTracker) -> usize {
         mem::size_of_val(self) + self.global_indexes.size_of_val(tracker)
@@ -83,6 +86,7 @@ impl ModuleMiddleware for OpcodeControl {
         Box::new(FunctionOpcodeControl {
             max_memory_grow: self.max_memory_grow,
             max_memory_grow_delta: self.max_memory_grow_delta,
+            max_memory_pages: self.max_memory_pages,
             breakpoints_middleware: self.breakpoints_middleware.clone(),
             global_indexes: self.global_indexes.lock().unwrap().clone().unwrap(),
         })
@@ -119,7 +123,8 @@ impl MiddlewareWithProtectedGlobals for OpcodeControl {
 struct FunctionOpcodeControl {
     max_memory_grow: usize,
     max_memory_grow_delta: usize,
-    breakpoints_middleware: Arc<Breakpoints>,
+    max_memory_pages: u64,
+    breakpoints_middleware: Option<Arc<Breakpoints>>,
     global_indexes: OpcodeControlGlobalIndexes,
 }
 
@@ -134,8 +139,11 @@ impl FunctionOpcodeControl {
             },
             Operator::I64GeU,
         ]);
-        self.breakpoints_middleware
-            .inject_breakpoint_condition(state, BREAKPOINT_VALUE_MEMORY_LIMIT);
+        inject_breakpoint_condition_or_trap(
+            &self.breakpoints_middleware,
+            state,
+            BREAKPOINT_VALUE_MEMORY_LIMIT,
+        );
     }
 
     fn inject_memory_grow_count_increment(&self, state: &mut MiddlewareReaderState) {
@@ -162,27 +170,64 @@ impl FunctionOpcodeControl {
             Operator::I64GtU,
         ]);
 
-        self.breakpoints_middleware
-            .inject_breakpoint_condition(state, BREAKPOINT_VALUE_MEMORY_LIMIT);
+        inject_breakpoint_condition_or_trap(
+            &self.breakpoints_middleware,
+            state,
+            BREAKPOINT_VALUE_MEMORY_LIMIT,
+        );
+    }
+
+    fn inject_memory_pages_limit_check(
+        &self,
+        state: &mut MiddlewareReaderState,
+        mem: u32,
+        mem_byte: u8,
+    ) {
+        state.extend(&[
+            Operator::MemorySize { mem, mem_byte },
+            Operator::I64ExtendI32U,
+            Operator::GlobalGet {
+                global_index: self.global_indexes.operand_backup_global_index.as_u32(),
+            },
+            Operator::I64Add,
+            Operator::I64Const {
+                value: self.max_memory_pages as i64,
+            },
+            Operator::I64GtU,
+        ]);
+
+        inject_breakpoint_condition_or_trap(
+            &self.breakpoints_middleware,
+            state,
+            BREAKPOINT_VALUE_MEMORY_LIMIT,
+        );
     }
 
-    fn inject_memory_grow_check(&self, state: &mut MiddlewareReaderState) {
+    fn inject_memory_grow_check(&self, state: &mut MiddlewareReaderState, mem: u32, mem_byte: u8) {
         self.inject_memory_grow_limit_check(state);
         self.inject_memory_grow_count_increment(state);
 
         // Backup the top of the stack (the parameter for memory.grow) in order to
-        // duplicate it: once for the comparison against max_memory_grow_delta and
-        // again for memory.grow itself, assuming the comparison passes.
-        state.extend(&[Operator::GlobalSet {
-            global_index: self.global_indexes.operand_backup_global_index.as_u32(),
-        }]);
+        // duplicate it: once for each comparison against the limits and
+        // again for memory.grow itself, assuming the comparisons pass.
+        // The backup global is an i64, while the parameter is an i32.
+        state.extend(&[
+            Operator::I64ExtendI32U,
+            Operator::GlobalSet {
+                global_index: self.global_indexes.operand_backup_global_index.as_u32(),
+            },
+        ]);
 
         self.inject_memory_grow_delta_limit_check(state);
+        self.inject_memory_pages_limit_check(state, mem, mem_byte);
 
         // Bring back the backed-up operand for memory.grow.
-        state.extend(&[Operator::GlobalGet {
-            global_index: self.global_indexes.operand_backup_global_index.as_u32(),
-        }]);
+        state.extend(&[
+            Operator::GlobalGet {
+                global_index: self.global_indexes.operand_backup_global_index.as_u32(),
+            },
+            Operator::I32WrapI64,
+        ]);
     }
 }
 
@@ -192,8 +237,8 @@ impl FunctionMiddleware for FunctionOpcodeControl {
         operator: Operator<'b>,
         state: &mut MiddlewareReaderState<'b>,
     ) -> Result<(), MiddlewareError> {
-        if matches!(operator, Operator::MemoryGrow { .. }) {
-            self.inject_memory_grow_check(state);
+        if let Operator::MemoryGrow { mem, mem_byte } = operator {
+            self.inject_memory_grow_check(state, mem, mem_byte);
         }
 
         state.push_operator(operator);
@@ -201,3 +246,19 @@ impl FunctionMiddleware for FunctionOpcodeControl {
         Ok(())
     }
 }
+
+pub(crate) fn reset_memory_grow_count(instance: &Instance) -> Result<(), ExecutorError> {
+    let result = instance
+        .exports
+        .get_global(OPCODE_CONTROL_MEMORY_GROW_COUNT);
+    match result {
+        Ok(global) => {
+            let result = global.set(0u64.into());
+            match result {
+                Ok(_) => Ok(()),
+                Err(err) => Err(ExecutorError::Runtime(err.message())),
+            }
+        }
+        Err(err) => Err(ExecutorError::Runtime(err.to_string())),
+    }
+}
@@ -0,0 +1,292 @@
+use std::mem;
+use std::sync::Mutex;
+
+use klever_chain_vm_executor::{ExecutorError, OpcodeClass, OpcodeHistogram};
+use loupe::{MemoryUsage, MemoryUsageTracker};
+use wasmer::wasmparser::Operator;
+use wasmer::{
+    FunctionMiddleware, Instance, LocalFunctionIndex, MiddlewareReaderState, ModuleMiddleware,
+};
+use wasmer_types::{GlobalIndex, ModuleInfo};
+
+use crate::wasmer_helpers::{create_global_index, MiddlewareWithProtectedGlobals};
+
+const OPCODE_HISTOGRAM_PREFIX: &str = "opcode_histogram_";
+
+const OPCODE_CLASS_COUNT: usize = OpcodeClass::ALL.len();
+
+/// Holds one counter of executed operators per opcode class.
+///
+/// The operators are counted by the metering middleware, which sees them before any other
+/// middleware injects its own, and adds the counts of each basic block together with its cost.
+/// The injected increments are not metered, so counting does not change the gas used.
+#[derive(Debug)]
+pub(crate) struct OpcodeCounting {
+    global_indexes: Mutex<Option<Vec<GlobalIndex>>>,
+}
+
+impl OpcodeCounting {
+    pub(crate) fn new() -> Self {
+        Self {
+            global_indexes: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn function_opcode_counts(&self) -> FunctionOpcodeCounts {
+        FunctionOpcodeCounts {
+            global_indexes: self.global_indexes.lock().unwrap().clone().unwrap(),
+            counts: [0; OPCODE_CLASS_COUNT],
+        }
+    }
+}
+
+impl MemoryUsage for OpcodeCounting {
+    fn size_of_val(&self, tracker: &mut dyn MemoryUsageTracker) -> usize {
+        mem::size_of_val(self) + self.global_indexes.size_of_val(tracker)
+            - mem::size_of_val(&self.global_indexes)
+    }
+}
+
+impl ModuleMiddleware for OpcodeCounting {
+    fn generate_function_middleware(
+        &self,
+        _local_function_index: LocalFunctionIndex,
+    ) -> Box<dyn FunctionMiddleware> {
+        Box::new(FunctionOpcodeCounting {})
+    }
+
+    fn transform_module_info(&self, module_info: &mut ModuleInfo) {
+        let mut global_indexes = self.global_indexes.lock().unwrap();
+
+        *global_indexes = Some(
+            OpcodeClass::ALL
+                .iter()
+                .map(|opcode_class| {
+                    create_global_index(module_info, &opcode_histogram_name(*opcode_class), 0)
+                })
+                .collect(),
+        );
+    }
+}
+
+impl MiddlewareWithProtectedGlobals for OpcodeCounting {
+    fn protected_globals(&self) -> Vec<u32> {
+        self.global_indexes
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|global_index| global_index.as_u32())
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+struct FunctionOpcodeCounting {}
+
+impl FunctionMiddleware for FunctionOpcodeCounting {}
+
+/// The operators of the current basic block of a function, by class.
+#[derive(Debug)]
+pub(crate) struct FunctionOpcodeCounts {
+    global_indexes: Vec<GlobalIndex>,
+    counts: [u64; OPCODE_CLASS_COUNT],
+}
+
+impl FunctionOpcodeCounts {
+    pub(crate) fn count(&mut self, operator: &Operator) {
+        self.counts[opcode_class(operator) as usize] += 1;
+    }
+
+    /// Adds the counts of the current basic block to the counters, and starts a new block.
+    pub(crate) fn inject_counters_increment(&mut self, state: &mut MiddlewareReaderState) {
+        for (global_index, count) in self.global_indexes.iter().zip(&mut self.counts) {
+            if *count == 0 {
+                continue;
+            }
+            state.extend(&[
+                Operator::GlobalGet {
+                    global_index: global_index.as_u32(),
+                },
+                Operator::I64Const {
+                    value: *count as i64,
+                },
+                Operator::I64Add,
+                Operator::GlobalSet {
+                    global_index: global_index.as_u32(),
+                },
+            ]);
+            *count = 0;
+        }
+    }
+}
+
+fn opcode_histogram_name(opcode_class: OpcodeClass) -> String {
+    format!("{OPCODE_HISTOGRAM_PREFIX}{opcode_class}")
+}
+
+pub(crate) fn get_opcode_histogram(instance: &Instance) -> Result<OpcodeHistogram, ExecutorError> {
+    let mut opcode_histogram = OpcodeHistogram::default();
+    for opcode_class in OpcodeClass::ALL {
+        let count = u64::try_from(get_counter(instance, opcode_class)?.get())
+            .map_err(|err| ExecutorError::Runtime(err.to_string()))?;
+        if count > 0 {
+            opcode_histogram.counts.insert(opcode_class, count);
+        }
+    }
+    Ok(opcode_histogram)
+}
+
+pub(crate) fn reset_opcode_histogram(instance: &Instance) -> Result<(), ExecutorError> {
+    for opcode_class in OpcodeClass::ALL {
+        get_counter(instance, opcode_class)?
+            .set(0u64.into())
+            .map_err(|err| ExecutorError::Runtime(err.message()))?;
+    }
+    Ok(())
+}
+
+fn get_counter(
+    instance: &Instance,
+    opcode_class: OpcodeClass,
+) -> Result<&wasmer::Global, ExecutorError> {
+    instance
+        .exports
+        .get_global(&opcode_histogram_name(opcode_class))
+        .map_err(|err| ExecutorError::Runtime(err.to_string()))
+}
+
+fn opcode_class(operator: &Operator) -> OpcodeClass {
+    match operator {
+        Operator::Unreachable
+        | Operator::Nop
+        | Operator::Block { .. }
+        | Operator::Loop { .. }
+        | Operator::If { .. }
+        | Operator::Else
+        | Operator::End
+        | Operator::Br { .. }
+        | Operator::BrIf { .. }
+        | Operator::BrTable { .. }
+        | Operator::Return => OpcodeClass::ControlFlow,
+
+        Operator::Call { .. } | Operator::CallIndirect { .. } => OpcodeClass::Call,
+
+        Operator::LocalGet { .. } | Operator::LocalSet { .. } | Operator::LocalTee { .. } => {
+            OpcodeClass::Local
+        }
+
+        Operator::GlobalGet { .. } | Operator::GlobalSet { .. } => OpcodeClass::Global,
+
+        Operator::I32Load { .. }
+        | Operator::I64Load { .. }
+        | Operator::F32Load { .. }
+        | Operator::F64Load { .. }
+        | Operator::I32Load8S { .. }
+        | Operator::I32Load8U { .. }
+        | Operator::I32Load16S { .. }
+        | Operator::I32Load16U { .. }
+        | Operator::I64Load8S { .. }
+        | Operator::I64Load8U { .. }
+        | Operator::I64Load16S { .. }
+        | Operator::I64Load16U { .. }
+        | Operator::I64Load32S { .. }
+        | Operator::I64Load32U { .. } => OpcodeClass::Load,
+
+        Operator::I32Store { .. }
+        | Operator::I64Store { .. }
+        | Operator::F32Store { .. }
+        | Operator::F64Store { .. }
+        | Operator::I32Store8 { .. }
+        | Operator::I32Store16 { .. }
+        | Operator::I64Store8 { .. }
+        | Operator::I64Store16 { .. }
+        | Operator::I64Store32 { .. } => OpcodeClass::Store,
+
+        Operator::MemorySize { .. } | Operator::MemoryGrow { .. } => OpcodeClass::Memory,
+
+        Operator::MemoryInit { .. }
+        | Operator::DataDrop { .. }
+        | Operator::MemoryCopy { .. }
+        | Operator::MemoryFill { .. } => OpcodeClass::BulkMemory,
+
+        Operator::I32Const { .. }
+        | Operator::I64Const { .. }
+        | Operator::F32Const { .. }
+        | Operator::F64Const { .. } => OpcodeClass::Constant,
+
+        Operator::I32Clz
+        | Operator::I32Ctz
+        | Operator::I32Popcnt
+        | Operator::I32Add
+        | Operator::I32Sub
+        | Operator::I32Mul
+        | Operator::I32DivS
+        | Operator::I32DivU
+        | Operator::I32RemS
+        | Operator::I32RemU
+        | Operator::I32And
+        | Operator::I32Or
+        | Operator::I32Xor
+        | Operator::I32Shl
+        | Operator::I32ShrS
+        | Operator::I32ShrU
+        | Operator::I32Rotl
+        | Operator::I32Rotr
+        | Operator::I64Clz
+        | Operator::I64Ctz
+        | Operator::I64Popcnt
+        | Operator::I64Add
+        | Operator::I64Sub
+        | Operator::I64Mul
+        | Operator::I64DivS
+        | Operator::I64DivU
+        | Operator::I64RemS
+        | Operator::I64RemU
+        | Operator::I64And
+        | Operator::I64Or
+        | Operator::I64Xor
+        | Operator::I64Shl
+        | Operator::I64ShrS
+        | Operator::I64ShrU
+        | Operator::I64Rotl
+        | Operator::I64Rotr => OpcodeClass::Arithmetic,
+
+        Operator::I32Eqz
+        | Operator::I32Eq
+        | Operator::I32Ne
+        | Operator::I32LtS
+        | Operator::I32LtU
+        | Operator::I32GtS
+        | Operator::I32GtU
+        | Operator::I32LeS
+        | Operator::I32LeU
+        | Operator::I32GeS
+        | Operator::I32GeU
+        | Operator::I64Eqz
+        | Operator::I64Eq
+        | Operator::I64Ne
+        | Operator::I64LtS
+        | Operator::I64LtU
+        | Operator::I64GtS
+        | Operator::I64GtU
+        | Operator::I64LeS
+        | Operator::I64LeU
+        | Operator::I64GeS
+        | Operator::I64GeU => OpcodeClass::Comparison,
+
+        Operator::I32WrapI64
+        | Operator::I64ExtendI32S
+        | Operator::I64ExtendI32U
+        | Operator::I32Extend8S
+        | Operator::I32Extend16S
+        | Operator::I64Extend8S
+        | Operator::I64Extend16S
+        | Operator::I64Extend32S => OpcodeClass::Conversion,
+
+        Operator::Drop | Operator::Select | Operator::TypedSelect { .. } => OpcodeClass::Parametric,
+
+        _ => OpcodeClass::Other,
+    }
+}
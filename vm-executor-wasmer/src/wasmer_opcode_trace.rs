@@ -1,7 +1,12 @@
+use klever_chain_vm_executor::{
+    ExecutorError, OpcodeTraceCallback, OpcodeTraceEntry, OpcodeTraceSink,
+};
 use loupe::{MemoryUsage, MemoryUsageTracker};
-use std::fs::{self, File};
-use std::io::Write;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::mem;
+use std::sync::{Arc, Mutex};
 use wasmer::wasmparser::Operator;
 use wasmer::{
     FunctionMiddleware, LocalFunctionIndex, MiddlewareError, MiddlewareReaderState,
@@ -9,20 +14,58 @@ use wasmer::{
 };
 use wasmer_types::ModuleInfo;
 
-const OPCODE_TRACE_PATH: &str = "opcode.trace2";
+enum OpcodeTraceOutput {
+    File(BufWriter<File>),
+    Memory(Vec<OpcodeTraceEntry>),
+    Callback(OpcodeTraceCallback),
+}
+
+impl fmt::Debug for OpcodeTraceOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpcodeTraceOutput::File(_) => write!(f, "File"),
+            OpcodeTraceOutput::Memory(entries) => write!(f, "Memory({} entries)", entries.len()),
+            OpcodeTraceOutput::Callback(_) => write!(f, "Callback"),
+        }
+    }
+}
 
 #[derive(Debug)]
-pub(crate) struct OpcodeTracer {}
+pub(crate) struct OpcodeTracer {
+    output: Arc<Mutex<OpcodeTraceOutput>>,
+}
 
 impl OpcodeTracer {
-    pub(crate) fn new() -> Self {
-        File::create(OPCODE_TRACE_PATH).unwrap();
-        Self {}
+    pub(crate) fn new(sink: &OpcodeTraceSink) -> Result<Self, ExecutorError> {
+        let output = match sink {
+            OpcodeTraceSink::File(path) => {
+                let file = File::create(path).map_err(|err| {
+                    ExecutorError::OpcodeTrace(format!("{}: {err}", path.display()))
+                })?;
+                OpcodeTraceOutput::File(BufWriter::new(file))
+            }
+            OpcodeTraceSink::Memory => OpcodeTraceOutput::Memory(Vec::new()),
+            OpcodeTraceSink::Callback(callback) => OpcodeTraceOutput::Callback(callback.clone()),
+        };
+        Ok(Self {
+            output: Arc::new(Mutex::new(output)),
+        })
     }
-}
 
-unsafe impl Send for OpcodeTracer {}
-unsafe impl Sync for OpcodeTracer {}
+    /// Flushes the trace once the module is compiled, and returns the entries kept in memory.
+    pub(crate) fn finish(&self) -> Result<Vec<OpcodeTraceEntry>, ExecutorError> {
+        match &mut *self.output.lock().unwrap() {
+            OpcodeTraceOutput::File(writer) => {
+                writer
+                    .flush()
+                    .map_err(|err| ExecutorError::OpcodeTrace(err.to_string()))?;
+                Ok(Vec::new())
+            }
+            OpcodeTraceOutput::Memory(entries) => Ok(mem::take(entries)),
+            OpcodeTraceOutput::Callback(_) => Ok(Vec::new()),
+        }
+    }
+}
 
 impl MemoryUsage for OpcodeTracer {
     fn size_of_val(&self, _tracker: &mut dyn MemoryUsageTracker) -> usize {
@@ -35,13 +78,8 @@ impl ModuleMiddleware for OpcodeTracer {
         &self,
         local_function_index: LocalFunctionIndex,
     ) -> Box<dyn FunctionMiddleware> {
-        let file = fs::OpenOptions::new()
-            .append(true)
-            .open(OPCODE_TRACE_PATH)
-            .unwrap();
-
         Box::new(FunctionOpcodeTracer {
-            output_file: file,
+            output: self.output.clone(),
             local_function_index: local_function_index.as_u32(),
             counter: 0,
         })
@@ -52,27 +90,32 @@ impl ModuleMiddleware for OpcodeTracer {
 
 #[derive(Debug)]
 struct FunctionOpcodeTracer {
-    output_file: File,
+    output: Arc<Mutex<OpcodeTraceOutput>>,
     local_function_index: u32,
     counter: u32,
 }
 
 impl FunctionOpcodeTracer {
-    fn trace_operator(&mut self, operator: &Operator) {
-        self.output_file
-            .write_all(
-                format!(
-                    "[fn: {:08b}({}), operator: {:08b}({})]\t{:?}\n",
-                    self.local_function_index,
-                    self.local_function_index,
-                    self.counter,
-                    self.counter,
-                    operator
-                )
-                .as_bytes(),
-            )
-            .unwrap();
+    fn trace_operator(&mut self, operator: &Operator) -> Result<(), MiddlewareError> {
+        let entry = OpcodeTraceEntry {
+            function_index: self.local_function_index,
+            operator_index: self.counter,
+            operator: operator_name(operator),
+        };
         self.counter += 1;
+
+        match &mut *self.output.lock().unwrap() {
+            OpcodeTraceOutput::File(writer) => writeln!(writer, "{}", entry.to_json_line())
+                .map_err(|err| MiddlewareError::new("opcode_tracer", err.to_string())),
+            OpcodeTraceOutput::Memory(entries) => {
+                entries.push(entry);
+                Ok(())
+            }
+            OpcodeTraceOutput::Callback(callback) => {
+                callback(&entry);
+                Ok(())
+            }
+        }
     }
 }
 
@@ -82,10 +125,19 @@ impl FunctionMiddleware for FunctionOpcodeTracer {
         operator: Operator<'b>,
         state: &mut MiddlewareReaderState<'b>,
     ) -> Result<(), MiddlewareError> {
-        self.trace_operator(&operator);
+        self.trace_operator(&operator)?;
 
         state.push_operator(operator);
 
         Ok(())
     }
 }
+
+/// The name of the operator variant, without its immediates.
+fn operator_name(operator: &Operator) -> String {
+    let debug = format!("{operator:?}");
+    match debug.find([' ', '{', '(']) {
+        Some(end) => debug[..end].to_string(),
+        None => debug,
+    }
+}
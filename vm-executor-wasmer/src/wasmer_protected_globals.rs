@@ -30,9 +30,6 @@ impl ProtectedGlobals {
     }
 }
 
-unsafe impl Send for ProtectedGlobals {}
-unsafe impl Sync for ProtectedGlobals {}
-
 impl MemoryUsage for ProtectedGlobals {
     fn size_of_val(&self, _tracker: &mut dyn MemoryUsageTracker) -> usize {
         mem::size_of_val(self)
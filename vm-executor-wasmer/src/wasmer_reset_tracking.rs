@@ -0,0 +1,452 @@
+use std::{mem, ops::Range, sync::Mutex};
+
+use klever_chain_vm_executor::ExecutorError;
+use loupe::{MemoryUsage, MemoryUsageTracker};
+use wasmer::{
+    wasmparser::Operator, ExportIndex, FunctionMiddleware, Global, Instance, LocalFunctionIndex,
+    MiddlewareError, MiddlewareReaderState, ModuleMiddleware, Mutability,
+};
+use wasmer_types::{GlobalIndex, ModuleInfo};
+
+use crate::wasmer_helpers::{create_global_index, MiddlewareWithProtectedGlobals};
+
+const RESET_TRACKING_WRITTEN_START: &str = "reset_tracking_written_start";
+const RESET_TRACKING_WRITTEN_END: &str = "reset_tracking_written_end";
+const RESET_TRACKING_ADDRESS_BACKUP: &str = "reset_tracking_address_backup";
+const RESET_TRACKING_OPERAND_BACKUP: &str = "reset_tracking_operand_backup";
+const RESET_TRACKING_LENGTH_BACKUP: &str = "reset_tracking_length_backup";
+const RESET_GLOBAL_PREFIX: &str = "reset_global_";
+
+// Nothing is written while the start of the written range is past its end
+const NOTHING_WRITTEN_START: u64 = u64::MAX;
+const NOTHING_WRITTEN_END: u64 = 0;
+
+#[derive(Clone, Debug, MemoryUsage)]
+struct ResetTrackingGlobalIndexes {
+    written_start_global_index: GlobalIndex,
+    written_end_global_index: GlobalIndex,
+    address_backup_global_index: GlobalIndex,
+    operand_backup_global_index: GlobalIndex,
+    length_backup_global_index: GlobalIndex,
+}
+
+/// Records what the calls change, so that `Instance::reset_changed_pages` only restores that.
+///
+/// The range of memory written by the stores and the bulk memory operators is kept in globals,
+/// which the host widens when it writes to memory itself. The mutable globals of the contract
+/// are exported, since wasmer only gives access to exported ones.
+///
+/// Pushed after the middlewares that count or trace operators, so that the injected operators
+/// are neither metered nor traced.
+#[derive(Debug)]
+pub(crate) struct ResetTracking {
+    global_indexes: Mutex<Option<ResetTrackingGlobalIndexes>>,
+}
+
+impl ResetTracking {
+    pub(crate) fn new() -> Self {
+        Self {
+            global_indexes: Mutex::new(None),
+        }
+    }
+
+    fn get_global_indexes(&self) -> ResetTrackingGlobalIndexes {
+        self.global_indexes.lock().unwrap().clone().unwrap()
+    }
+}
+
+impl MemoryUsage for ResetTracking {
+    fn size_of_val(&self, tracker: &mut dyn MemoryUsageTracker) -> usize {
+        mem::size_of_val(self) + self.global_indexes.size_of_val(tracker)
+            - mem::size_of_val(&self.global_indexes)
+    }
+}
+
+impl ModuleMiddleware for ResetTracking {
+    fn generate_function_middleware(
+        &self,
+        _local_function_index: LocalFunctionIndex,
+    ) -> Box<dyn FunctionMiddleware> {
+        Box::new(FunctionResetTracking {
+            global_indexes: self.get_global_indexes(),
+        })
+    }
+
+    fn transform_module_info(&self, module_info: &mut ModuleInfo) {
+        let unexported_globals: Vec<GlobalIndex> = module_info
+            .globals
+            .iter()
+            .filter(|(global_index, global_type)| {
+                global_type.mutability == Mutability::Var
+                    && !module_info
+                        .exports
+                        .values()
+                        .any(|export| *export == ExportIndex::Global(*global_index))
+            })
+            .map(|(global_index, _)| global_index)
+            .collect();
+        for global_index in unexported_globals {
+            let name = unused_export_name(module_info, global_index);
+            module_info
+                .exports
+                .insert(name, ExportIndex::Global(global_index));
+        }
+
+        let mut global_indexes = self.global_indexes.lock().unwrap();
+
+        *global_indexes = Some(ResetTrackingGlobalIndexes {
+            written_start_global_index: create_global_index(
+                module_info,
+                RESET_TRACKING_WRITTEN_START,
+                NOTHING_WRITTEN_START as i64,
+            ),
+            written_end_global_index: create_global_index(
+                module_info,
+                RESET_TRACKING_WRITTEN_END,
+                NOTHING_WRITTEN_END as i64,
+            ),
+            address_backup_global_index: create_global_index(
+                module_info,
+                RESET_TRACKING_ADDRESS_BACKUP,
+                0,
+            ),
+            operand_backup_global_index: create_global_index(
+                module_info,
+                RESET_TRACKING_OPERAND_BACKUP,
+                0,
+            ),
+            length_backup_global_index: create_global_index(
+                module_info,
+                RESET_TRACKING_LENGTH_BACKUP,
+                0,
+            ),
+        });
+    }
+}
+
+impl MiddlewareWithProtectedGlobals for ResetTracking {
+    fn protected_globals(&self) -> Vec<u32> {
+        let global_indexes = self.get_global_indexes();
+        vec![
+            global_indexes.written_start_global_index.as_u32(),
+            global_indexes.written_end_global_index.as_u32(),
+            global_indexes.address_backup_global_index.as_u32(),
+            global_indexes.operand_backup_global_index.as_u32(),
+            global_indexes.length_backup_global_index.as_u32(),
+        ]
+    }
+}
+
+/// Any name is a valid export, so no prefix can be reserved without rejecting some contracts.
+fn unused_export_name(module_info: &ModuleInfo, global_index: GlobalIndex) -> String {
+    let mut name = format!("{RESET_GLOBAL_PREFIX}{}", global_index.as_u32());
+    while module_info.exports.contains_key(&name) {
+        name.push('_');
+    }
+    name
+}
+
+/// The type of the value of a store, which is backed up in an i64 global.
+#[derive(Clone, Copy)]
+enum StoredType {
+    I32,
+    I64,
+    F32,
+    F64,
+}
+
+#[derive(Debug)]
+struct FunctionResetTracking {
+    global_indexes: ResetTrackingGlobalIndexes,
+}
+
+impl FunctionResetTracking {
+    /// Widens the written range to the range computed by `start` and `end`.
+    fn inject_written_range_update<'b>(
+        &self,
+        state: &mut MiddlewareReaderState<'b>,
+        start: &[Operator<'b>],
+        end: &[Operator<'b>],
+    ) {
+        inject_bound_update(
+            state,
+            self.global_indexes.written_start_global_index.as_u32(),
+            start,
+            Operator::I64LtU,
+        );
+        inject_bound_update(
+            state,
+            self.global_indexes.written_end_global_index.as_u32(),
+            end,
+            Operator::I64GtU,
+        );
+    }
+
+    fn inject_store_tracking(
+        &self,
+        state: &mut MiddlewareReaderState,
+        offset: u64,
+        size: u64,
+        stored_type: StoredType,
+    ) {
+        let address_backup = self.global_indexes.address_backup_global_index.as_u32();
+        let operand_backup = self.global_indexes.operand_backup_global_index.as_u32();
+
+        // Backup the value, then the address below it, which is needed for the written range
+        inject_to_i64(state, stored_type);
+        state.extend(&[
+            Operator::GlobalSet {
+                global_index: operand_backup,
+            },
+            Operator::I64ExtendI32U,
+            Operator::GlobalSet {
+                global_index: address_backup,
+            },
+        ]);
+
+        self.inject_written_range_update(
+            state,
+            &[
+                Operator::GlobalGet {
+                    global_index: address_backup,
+                },
+                Operator::I64Const {
+                    value: offset as i64,
+                },
+                Operator::I64Add,
+            ],
+            &[
+                Operator::GlobalGet {
+                    global_index: address_backup,
+                },
+                Operator::I64Const {
+                    value: (offset + size) as i64,
+                },
+                Operator::I64Add,
+            ],
+        );
+
+        // Bring back the backed-up operands for the store
+        state.extend(&[
+            Operator::GlobalGet {
+                global_index: address_backup,
+            },
+            Operator::I32WrapI64,
+            Operator::GlobalGet {
+                global_index: operand_backup,
+            },
+        ]);
+        inject_from_i64(state, stored_type);
+    }
+
+    /// The bulk memory operators all take a destination, another i32 and a length.
+    fn inject_bulk_memory_tracking(&self, state: &mut MiddlewareReaderState) {
+        let address_backup = self.global_indexes.address_backup_global_index.as_u32();
+        let operand_backup = self.global_indexes.operand_backup_global_index.as_u32();
+        let length_backup = self.global_indexes.length_backup_global_index.as_u32();
+
+        state.extend(&[
+            Operator::I64ExtendI32U,
+            Operator::GlobalSet {
+                global_index: length_backup,
+            },
+            Operator::I64ExtendI32U,
+            Operator::GlobalSet {
+                global_index: operand_backup,
+            },
+            Operator::I64ExtendI32U,
+            Operator::GlobalSet {
+                global_index: address_backup,
+            },
+        ]);
+
+        self.inject_written_range_update(
+            state,
+            &[Operator::GlobalGet {
+                global_index: address_backup,
+            }],
+            &[
+                Operator::GlobalGet {
+                    global_index: address_backup,
+                },
+                Operator::GlobalGet {
+                    global_index: length_backup,
+                },
+                Operator::I64Add,
+            ],
+        );
+
+        state.extend(&[
+            Operator::GlobalGet {
+                global_index: address_backup,
+            },
+            Operator::I32WrapI64,
+            Operator::GlobalGet {
+                global_index: operand_backup,
+            },
+            Operator::I32WrapI64,
+            Operator::GlobalGet {
+                global_index: length_backup,
+            },
+            Operator::I32WrapI64,
+        ]);
+    }
+}
+
+impl FunctionMiddleware for FunctionResetTracking {
+    fn feed<'b>(
+        &mut self,
+        operator: Operator<'b>,
+        state: &mut MiddlewareReaderState<'b>,
+    ) -> Result<(), MiddlewareError> {
+        match operator {
+            Operator::I32Store { memarg } => {
+                self.inject_store_tracking(state, memarg.offset, 4, StoredType::I32)
+            }
+            Operator::F32Store { memarg } => {
+                self.inject_store_tracking(state, memarg.offset, 4, StoredType::F32)
+            }
+            Operator::I64Store { memarg } => {
+                self.inject_store_tracking(state, memarg.offset, 8, StoredType::I64)
+            }
+            Operator::F64Store { memarg } => {
+                self.inject_store_tracking(state, memarg.offset, 8, StoredType::F64)
+            }
+            Operator::I32Store8 { memarg } => {
+                self.inject_store_tracking(state, memarg.offset, 1, StoredType::I32)
+            }
+            Operator::I32Store16 { memarg } => {
+                self.inject_store_tracking(state, memarg.offset, 2, StoredType::I32)
+            }
+            Operator::I64Store8 { memarg } => {
+                self.inject_store_tracking(state, memarg.offset, 1, StoredType::I64)
+            }
+            Operator::I64Store16 { memarg } => {
+                self.inject_store_tracking(state, memarg.offset, 2, StoredType::I64)
+            }
+            Operator::I64Store32 { memarg } => {
+                self.inject_store_tracking(state, memarg.offset, 4, StoredType::I64)
+            }
+            Operator::MemoryCopy { .. }
+            | Operator::MemoryFill { .. }
+            | Operator::MemoryInit { .. } => self.inject_bulk_memory_tracking(state),
+            _ => {}
+        }
+
+        state.push_operator(operator);
+
+        Ok(())
+    }
+}
+
+/// Replaces the bound with `value` if `value <keep_value> bound`.
+fn inject_bound_update<'b>(
+    state: &mut MiddlewareReaderState<'b>,
+    bound_global_index: u32,
+    value: &[Operator<'b>],
+    keep_value: Operator<'b>,
+) {
+    state.extend(value);
+    state.extend(&[Operator::GlobalGet {
+        global_index: bound_global_index,
+    }]);
+    state.extend(value);
+    state.extend(&[
+        Operator::GlobalGet {
+            global_index: bound_global_index,
+        },
+        keep_value,
+        Operator::Select,
+        Operator::GlobalSet {
+            global_index: bound_global_index,
+        },
+    ]);
+}
+
+fn inject_to_i64(state: &mut MiddlewareReaderState, stored_type: StoredType) {
+    match stored_type {
+        StoredType::I32 => state.extend(&[Operator::I64ExtendI32U]),
+        StoredType::I64 => {}
+        StoredType::F32 => state.extend(&[Operator::I32ReinterpretF32, Operator::I64ExtendI32U]),
+        StoredType::F64 => state.extend(&[Operator::I64ReinterpretF64]),
+    }
+}
+
+fn inject_from_i64(state: &mut MiddlewareReaderState, stored_type: StoredType) {
+    match stored_type {
+        StoredType::I32 => state.extend(&[Operator::I32WrapI64]),
+        StoredType::I64 => {}
+        StoredType::F32 => state.extend(&[Operator::I32WrapI64, Operator::F32ReinterpretI32]),
+        StoredType::F64 => state.extend(&[Operator::F64ReinterpretI64]),
+    }
+}
+
+fn get_u64_global(instance: &Instance, name: &str) -> Result<u64, ExecutorError> {
+    let global = instance
+        .exports
+        .get_global(name)
+        .map_err(|err| ExecutorError::Runtime(err.to_string()))?;
+    global
+        .get()
+        .try_into()
+        .map_err(|err: &str| ExecutorError::Runtime(err.to_string()))
+}
+
+fn set_u64_global(instance: &Instance, name: &str, value: u64) -> Result<(), ExecutorError> {
+    let global = instance
+        .exports
+        .get_global(name)
+        .map_err(|err| ExecutorError::Runtime(err.to_string()))?;
+    global
+        .set(value.into())
+        .map_err(|err| ExecutorError::Runtime(err.message()))
+}
+
+/// Returns the range of memory written since instantiation or the last reset, if any.
+///
+/// The range can extend past the memory, after a write that trapped out of bounds.
+pub(crate) fn get_written_range(instance: &Instance) -> Result<Option<Range<u64>>, ExecutorError> {
+    let start = get_u64_global(instance, RESET_TRACKING_WRITTEN_START)?;
+    let end = get_u64_global(instance, RESET_TRACKING_WRITTEN_END)?;
+    Ok((start < end).then_some(start..end))
+}
+
+/// Widens the written range with a write made by the host.
+pub(crate) fn mark_memory_written(
+    instance: &Instance,
+    written: Range<u64>,
+) -> Result<(), ExecutorError> {
+    if written.is_empty() {
+        return Ok(());
+    }
+    let start = get_u64_global(instance, RESET_TRACKING_WRITTEN_START)?;
+    let end = get_u64_global(instance, RESET_TRACKING_WRITTEN_END)?;
+    set_u64_global(
+        instance,
+        RESET_TRACKING_WRITTEN_START,
+        start.min(written.start),
+    )?;
+    set_u64_global(instance, RESET_TRACKING_WRITTEN_END, end.max(written.end))
+}
+
+pub(crate) fn reset_written_range(instance: &Instance) -> Result<(), ExecutorError> {
+    set_u64_global(
+        instance,
+        RESET_TRACKING_WRITTEN_START,
+        NOTHING_WRITTEN_START,
+    )?;
+    set_u64_global(instance, RESET_TRACKING_WRITTEN_END, NOTHING_WRITTEN_END)
+}
+
+/// Returns the mutable globals of an instance, both declared by the contract and injected by
+/// the middlewares, each once.
+pub(crate) fn mutable_globals(instance: &Instance) -> Vec<Global> {
+    let mut globals: Vec<Global> = Vec::new();
+    for (_, global) in instance.exports.iter().globals() {
+        let is_new = !globals.iter().any(|known| known.same(global));
+        if global.ty().mutability == Mutability::Var && is_new {
+            globals.push(global.clone());
+        }
+    }
+    globals
+}
@@ -0,0 +1,97 @@
+use std::mem;
+use std::sync::Mutex;
+
+use klever_chain_vm_executor::ValidationError;
+use loupe::{MemoryUsage, MemoryUsageTracker};
+use wasmer::{ExportIndex, FunctionMiddleware, LocalFunctionIndex, ModuleMiddleware, Mutability};
+use wasmer_types::ModuleInfo;
+
+const SNAPSHOT_GLOBAL_PREFIX: &str = "snapshot_global_";
+
+/// Exports all mutable globals, so that instance snapshots can read and write them.
+///
+/// Contracts usually keep their globals private, and wasmer only gives access to exported ones.
+/// Pushed last, so that the globals injected by the other middlewares are exported as well.
+///
+/// Only pushed when snapshots are enabled, since the exports take names from the contract.
+#[derive(Debug)]
+pub(crate) struct SnapshotGlobals {
+    validation_error: Mutex<Option<ValidationError>>,
+}
+
+impl SnapshotGlobals {
+    pub(crate) fn new() -> Self {
+        Self {
+            validation_error: Mutex::new(None),
+        }
+    }
+
+    /// Returns the reason the last compilation was rejected by this middleware, if any.
+    ///
+    /// Wasmer does not let `transform_module_info` fail, so the module compiles anyway,
+    /// and must be discarded if there is an error.
+    pub(crate) fn take_validation_error(&self) -> Option<ValidationError> {
+        self.validation_error.lock().unwrap().take()
+    }
+}
+
+impl MemoryUsage for SnapshotGlobals {
+    fn size_of_val(&self, _tracker: &mut dyn MemoryUsageTracker) -> usize {
+        mem::size_of_val(self)
+    }
+}
+
+impl ModuleMiddleware for SnapshotGlobals {
+    fn generate_function_middleware(
+        &self,
+        _local_function_index: LocalFunctionIndex,
+    ) -> Box<dyn FunctionMiddleware> {
+        Box::new(FunctionSnapshotGlobals {})
+    }
+
+    fn transform_module_info(&self, module_info: &mut ModuleInfo) {
+        // Exporting under a contract's own name would silently replace its export
+        let reserved_export = module_info
+            .exports
+            .keys()
+            .find(|name| name.starts_with(SNAPSHOT_GLOBAL_PREFIX));
+        if let Some(name) = reserved_export {
+            *self.validation_error.lock().unwrap() =
+                Some(ValidationError::ReservedExportName(name.clone()));
+            return;
+        }
+
+        for (global_index, global_type) in module_info.globals.iter() {
+            if global_type.mutability == Mutability::Var {
+                module_info.exports.insert(
+                    snapshot_global_name(global_index.as_u32()),
+                    ExportIndex::Global(global_index),
+                );
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FunctionSnapshotGlobals {}
+
+impl FunctionMiddleware for FunctionSnapshotGlobals {}
+
+fn snapshot_global_name(global_index: u32) -> String {
+    format!("{SNAPSHOT_GLOBAL_PREFIX}{global_index}")
+}
+
+/// Returns the exported mutable globals of an instance, in the order of their indexes.
+pub(crate) fn snapshot_globals(instance: &wasmer::Instance) -> Vec<&wasmer::Global> {
+    let mut globals: Vec<(u32, &wasmer::Global)> = instance
+        .exports
+        .iter()
+        .globals()
+        .filter_map(|(name, global)| {
+            let global_index = name.strip_prefix(SNAPSHOT_GLOBAL_PREFIX)?.parse().ok()?;
+            Some((global_index, global))
+        })
+        .collect();
+    globals.sort_by_key(|(global_index, _)| *global_index);
+    globals.into_iter().map(|(_, global)| global).collect()
+}
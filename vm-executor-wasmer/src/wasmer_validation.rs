@@ -0,0 +1,123 @@
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+use klever_chain_vm_executor::{BulkMemoryCost, OpcodeCost, ValidationError};
+use loupe::{MemoryUsage, MemoryUsageTracker};
+use wasmer::wasmparser::Operator;
+use wasmer::{
+    FunctionMiddleware, LocalFunctionIndex, MiddlewareError, MiddlewareReaderState,
+    ModuleMiddleware,
+};
+use wasmer_types::ModuleInfo;
+
+use crate::get_opcode_cost;
+
+const MAX_LOCAL_COUNT: u32 = 4000;
+
+/// Rejects the contracts that use too many locals, or operators without a cost.
+///
+/// Always pushed, and first, so that it sees the operators as written, and that the contracts
+/// accepted do not depend on whether metering is enabled.
+#[derive(Debug)]
+pub(crate) struct Validation {
+    opcode_cost: Arc<Mutex<OpcodeCost>>,
+    bulk_memory_cost: Option<BulkMemoryCost>,
+    validation_error: Arc<Mutex<Option<ValidationError>>>,
+}
+
+impl Validation {
+    /// The bulk memory operators are rejected without their costs.
+    pub(crate) fn new(
+        opcode_cost: Arc<Mutex<OpcodeCost>>,
+        bulk_memory_cost: Option<BulkMemoryCost>,
+    ) -> Self {
+        Self {
+            opcode_cost,
+            bulk_memory_cost,
+            validation_error: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Returns the reason the last compilation was rejected by this middleware, if any.
+    pub(crate) fn take_validation_error(&self) -> Option<ValidationError> {
+        self.validation_error.lock().unwrap().take()
+    }
+}
+
+impl MemoryUsage for Validation {
+    fn size_of_val(&self, _tracker: &mut dyn MemoryUsageTracker) -> usize {
+        mem::size_of_val(self)
+    }
+}
+
+impl ModuleMiddleware for Validation {
+    fn generate_function_middleware(
+        &self,
+        _local_function_index: LocalFunctionIndex,
+    ) -> Box<dyn FunctionMiddleware> {
+        Box::new(FunctionValidation {
+            opcode_cost: self.opcode_cost.clone(),
+            bulk_memory_cost: self.bulk_memory_cost.is_some(),
+            validation_error: self.validation_error.clone(),
+        })
+    }
+
+    fn transform_module_info(&self, _module_info: &mut ModuleInfo) {}
+}
+
+#[derive(Debug)]
+struct FunctionValidation {
+    opcode_cost: Arc<Mutex<OpcodeCost>>,
+
+    /// Whether the bulk memory operators have costs.
+    bulk_memory_cost: bool,
+    validation_error: Arc<Mutex<Option<ValidationError>>>,
+}
+
+impl FunctionValidation {
+    /// Records the first validation error of the module, so that it can be reported as such.
+    fn reject(&self, error: ValidationError) -> MiddlewareError {
+        let message = error.to_string();
+        self.validation_error.lock().unwrap().get_or_insert(error);
+        MiddlewareError::new("validation_middleware", message)
+    }
+
+    fn is_supported(&self, operator: &Operator) -> bool {
+        match operator {
+            Operator::DataDrop { .. }
+            | Operator::MemoryCopy { .. }
+            | Operator::MemoryFill { .. }
+            | Operator::MemoryInit { .. } => self.bulk_memory_cost,
+            _ => get_opcode_cost(operator, &self.opcode_cost.lock().unwrap()).is_some(),
+        }
+    }
+}
+
+impl FunctionMiddleware for FunctionValidation {
+    fn feed<'b>(
+        &mut self,
+        operator: Operator<'b>,
+        state: &mut MiddlewareReaderState<'b>,
+    ) -> Result<(), MiddlewareError> {
+        if !self.is_supported(&operator) {
+            return Err(self.reject(ValidationError::UnsupportedOperator(format!(
+                "{operator:?}"
+            ))));
+        }
+
+        state.push_operator(operator);
+
+        Ok(())
+    }
+
+    fn feed_local_count(&mut self, count: u32) -> Result<(), MiddlewareError> {
+        if count > MAX_LOCAL_COUNT {
+            return Err(self.reject(ValidationError::TooManyLocals {
+                count,
+                max: MAX_LOCAL_COUNT,
+            }));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use klever_chain_vm_executor::{
+    BreakpointValue, BulkMemoryCost, CompilationOptions, ExecutorError, Instance, ValidationError,
+};
+use wasmer::wat2wasm;
+
+const BULK_MEMORY_COST: BulkMemoryCost = BulkMemoryCost {
+    data_drop: 1,
+    memory_copy: 10,
+    memory_copy_per_byte: 3,
+    memory_fill: 10,
+    memory_fill_per_byte: 2,
+    memory_init: 10,
+    memory_init_per_byte: 4,
+};
+
+fn bulk_memory_instance(gas_limit: u64, runtime_breakpoints: bool) -> Box<dyn Instance> {
+    let mut executor = common::test_executor();
+    executor
+        .set_opcode_cost(&common::test_opcode_cost())
+        .unwrap();
+    executor.set_bulk_memory_cost(&BULK_MEMORY_COST).unwrap();
+
+    let options = CompilationOptions {
+        gas_limit,
+        ..common::test_compilation_options(true, runtime_breakpoints)
+    };
+    let wasm_bytes = wat2wasm(common::BULK_MEMORY_WAT.as_bytes()).unwrap();
+    executor.new_instance(&wasm_bytes, &options).unwrap()
+}
+
+fn points_used_by(instance: &dyn Instance, func_name: &str) -> u64 {
+    instance.set_points_used(0).unwrap();
+    instance.call(func_name).unwrap();
+    instance.get_points_used().unwrap()
+}
+
+#[test]
+fn gas_grows_with_length() {
+    let instance = bulk_memory_instance(1_000_000, true);
+
+    let small = points_used_by(instance.as_ref(), "fillSmall");
+    let large = points_used_by(instance.as_ref(), "fillLarge");
+    assert_eq!(large - small, 990 * 2);
+    assert!(small >= 10 + 10 * 2);
+}
+
+#[test]
+fn bulk_memory_operators_run() {
+    let instance = bulk_memory_instance(1_000_000, true);
+
+    instance.call("fillSmall").unwrap();
+    instance.call("copy").unwrap();
+    assert_eq!(&instance.memory_load(100, 10).unwrap()[..10], &[7; 10]);
+
+    let copy_points = points_used_by(instance.as_ref(), "copy");
+    assert!(copy_points >= 10 + 10 * 3);
+
+    let init_points = points_used_by(instance.as_ref(), "init");
+    assert!(init_points > 10 + 5 * 4);
+    assert_eq!(&instance.memory_load(200, 5).unwrap()[..5], b"hello");
+}
+
+#[test]
+fn out_of_gas_before_the_operator_runs() {
+    let instance = bulk_memory_instance(100, true);
+
+    let result = instance.call("fillLarge");
+    assert!(matches!(
+        result,
+        Err(ExecutorError::Trap {
+            breakpoint: BreakpointValue::OutOfGas,
+            ..
+        })
+    ));
+    assert_eq!(instance.memory_load(0, 1).unwrap()[0], 0);
+
+    let instance = bulk_memory_instance(100, false);
+    assert!(instance.call("fillLarge").is_err());
+    assert_eq!(instance.memory_load(0, 1).unwrap()[0], 0);
+}
+
+#[test]
+fn rejected_without_bulk_memory_cost() {
+    let mut executor = common::test_executor();
+    executor
+        .set_opcode_cost(&common::test_opcode_cost())
+        .unwrap();
+
+    let wasm_bytes = wat2wasm(common::BULK_MEMORY_WAT.as_bytes()).unwrap();
+    let result = executor.new_instance(&wasm_bytes, &common::test_compilation_options(true, true));
+    assert!(matches!(
+        result,
+        Err(ExecutorError::Validation(
+            ValidationError::UnsupportedOperator(_)
+        ))
+    ));
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use klever_chain_vm_executor::{
+    CacheArtifactError, CompilationOptions, Executor, ExecutorError, OpcodeCost,
+};
+
+fn cache_bytes(options: &CompilationOptions) -> Vec<u8> {
+    common::test_instance_with_options(common::ADDER_WAT, options)
+        .cache()
+        .unwrap()
+}
+
+fn rejection(
+    executor: &dyn Executor,
+    cache_bytes: &[u8],
+    options: &CompilationOptions,
+) -> CacheArtifactError {
+    match executor.new_instance_from_cache(cache_bytes, options) {
+        Ok(_) => panic!("cache artifact should have been rejected"),
+        Err(ExecutorError::Cache(err)) => err,
+        Err(err) => panic!("unexpected error: {err}"),
+    }
+}
+
+#[test]
+fn artifact_round_trip() {
+    let options = common::test_compilation_options(true, true);
+    let bytes = cache_bytes(&options);
+
+    let instance = common::test_executor()
+        .new_instance_from_cache(&bytes, &options)
+        .unwrap();
+    assert!(instance.call("callBack").is_ok());
+
+    // caching again keeps the original header
+    assert_eq!(instance.cache().unwrap(), bytes);
+}
+
+#[test]
+fn unaligned_artifact_is_accepted() {
+    let options = common::test_compilation_options(true, true);
+    let bytes = cache_bytes(&options);
+
+    let mut shifted = vec![0u8; bytes.len() + 1];
+    shifted[1..].copy_from_slice(&bytes);
+    let instance = common::test_executor()
+        .new_instance_from_cache(&shifted[1..], &options)
+        .unwrap();
+    assert!(instance.call("callBack").is_ok());
+}
+
+#[test]
+fn gas_limit_does_not_invalidate_artifact() {
+    let options = common::test_compilation_options(true, true);
+    let bytes = cache_bytes(&options);
+
+    let other_gas_limit = CompilationOptions {
+        gas_limit: 7,
+        ..common::test_compilation_options(true, true)
+    };
+    assert!(common::test_executor()
+        .new_instance_from_cache(&bytes, &other_gas_limit)
+        .is_ok());
+}
+
+#[test]
+fn malformed_artifact_is_rejected() {
+    let options = common::test_compilation_options(true, true);
+    let bytes = cache_bytes(&options);
+    let executor = common::test_executor();
+
+    assert_eq!(
+        rejection(executor.as_ref(), &[], &options),
+        CacheArtifactError::Truncated
+    );
+    assert_eq!(
+        rejection(executor.as_ref(), &bytes[..40], &options),
+        CacheArtifactError::Truncated
+    );
+
+    let mut bad_magic = bytes.clone();
+    bad_magic[0] ^= 0xff;
+    assert_eq!(
+        rejection(executor.as_ref(), &bad_magic, &options),
+        CacheArtifactError::BadMagic
+    );
+
+    let mut future_format = bytes.clone();
+    future_format[4..6].copy_from_slice(&99u16.to_le_bytes());
+    assert_eq!(
+        rejection(executor.as_ref(), &future_format, &options),
+        CacheArtifactError::UnsupportedFormatVersion(99)
+    );
+
+    let mut other_version = bytes.clone();
+    other_version[8] = b'X';
+    assert!(matches!(
+        rejection(executor.as_ref(), &other_version, &options),
+        CacheArtifactError::ExecutorVersionMismatch { .. }
+    ));
+
+    let mut corrupt_payload = bytes.clone();
+    *corrupt_payload.last_mut().unwrap() ^= 0xff;
+    assert_eq!(
+        rejection(executor.as_ref(), &corrupt_payload, &options),
+        CacheArtifactError::ChecksumMismatch
+    );
+}
+
+#[test]
+fn artifact_from_other_configuration_is_rejected() {
+    let options = common::test_compilation_options(true, true);
+    let bytes = cache_bytes(&options);
+
+    let other_locals = CompilationOptions {
+        unmetered_locals: 3,
+        ..common::test_compilation_options(true, true)
+    };
+    assert_eq!(
+        rejection(common::test_executor().as_ref(), &bytes, &other_locals),
+        CacheArtifactError::CompilationOptionsMismatch
+    );
+
+    let mut executor = common::test_executor();
+    executor
+        .set_opcode_cost(&OpcodeCost {
+            opcode_call: 7,
+            ..OpcodeCost::default()
+        })
+        .unwrap();
+    assert_eq!(
+        rejection(executor.as_ref(), &bytes, &options),
+        CacheArtifactError::OpcodeCostMismatch
+    );
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use klever_chain_vm_executor::{BreakpointValue, ExecutorError, TrapCode};
+use wasmer::wat2wasm;
+
+#[test]
+fn successful_call_reports_points() {
+    let mut executor = common::test_executor();
+    executor
+        .set_opcode_cost(&common::test_opcode_cost())
+        .unwrap();
+
+    let options = common::test_compilation_options(true, true);
+    let wasm_bytes = wat2wasm(common::EMPTY_SC_WAT.as_bytes()).unwrap();
+    let instance = executor.new_instance(&wasm_bytes, &options).unwrap();
+
+    let first = instance.call_with_report("init").unwrap();
+    assert!(first.is_success());
+    assert_eq!(first.breakpoint, BreakpointValue::None);
+    assert_eq!(first.points_used_before, 0);
+    assert!(first.points_used() > 0);
+
+    let second = instance.call_with_report("init").unwrap();
+    assert_eq!(second.points_used_before, first.points_used_after);
+    assert_eq!(second.points_used(), first.points_used());
+}
+
+#[test]
+fn out_of_gas_is_reported() {
+    let mut executor = common::test_executor();
+    executor
+        .set_opcode_cost(&common::test_opcode_cost())
+        .unwrap();
+
+    let options = common::test_compilation_options(true, true);
+    let wasm_bytes = wat2wasm(common::INFINITE_LOOP_WAT.as_bytes()).unwrap();
+    let instance = executor.new_instance(&wasm_bytes, &options).unwrap();
+
+    let outcome = instance.call_with_report("infiniteLoop").unwrap();
+    assert!(!outcome.is_success());
+    assert_eq!(outcome.breakpoint, BreakpointValue::OutOfGas);
+    assert!(outcome.points_used_after >= options.gas_limit);
+    assert_eq!(outcome.trap.unwrap().code, Some(TrapCode::Unreachable));
+}
+
+#[test]
+fn trap_codes_and_backtrace() {
+    let instance = common::test_instance(common::TRAPS_WAT);
+
+    let trap = |func_name| {
+        let outcome = instance.call_with_report(func_name).unwrap();
+        assert_eq!(outcome.breakpoint, BreakpointValue::None);
+        assert_eq!(outcome.points_used_after, 0);
+        outcome.trap.unwrap()
+    };
+
+    assert_eq!(
+        trap("divideByZero").code,
+        Some(TrapCode::IntegerDivisionByZero)
+    );
+    assert_eq!(trap("outOfBounds").code, Some(TrapCode::MemoryOutOfBounds));
+
+    let nested = trap("nestedUnreachable");
+    assert_eq!(nested.code, Some(TrapCode::Unreachable));
+    assert_eq!(nested.backtrace.len(), 2);
+    assert!(nested.backtrace[0].starts_with("unreachable "));
+    assert!(nested.backtrace[1].starts_with("nestedUnreachable "));
+}
+
+#[test]
+fn missing_function_is_an_error() {
+    let instance = common::test_instance(common::EMPTY_SC_WAT);
+    assert!(matches!(
+        instance.call_with_report("missingFunction"),
+        Err(ExecutorError::MissingExport(_))
+    ));
+}
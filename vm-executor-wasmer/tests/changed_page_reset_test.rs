@@ -0,0 +1,143 @@
+mod common;
+
+use klever_chain_vm_executor::{BulkMemoryCost, CompilationOptions, MemPtr, Value};
+use wasmer::wat2wasm;
+
+const DATA_SEGMENT_OFFSET: MemPtr = 1048576;
+
+fn reset_options() -> CompilationOptions {
+    CompilationOptions {
+        max_memory_grow: 10,
+        max_memory_grow_delta: 10,
+        ..common::test_compilation_options(true, true)
+    }
+}
+
+#[test]
+fn only_changed_pages_are_restored() {
+    let instance = common::test_instance_with_options(common::COUNTER_WAT, &reset_options());
+    assert_eq!(instance.reset_changed_pages().unwrap(), 0);
+
+    instance.call("increment").unwrap();
+    instance.call("increment").unwrap();
+    assert_eq!(instance.reset_changed_pages().unwrap(), 1);
+    assert_eq!(instance.memory_load(16, 1).unwrap()[0], 0);
+    assert_eq!(instance.get_points_used().unwrap(), 0);
+    assert_eq!(
+        instance.call_typed("getCounter", &[]).unwrap(),
+        vec![Value::I64(0)]
+    );
+
+    instance.memory_store(0, &[1]).unwrap();
+    instance.memory_store(65536, &[1]).unwrap();
+    assert_eq!(instance.reset_changed_pages().unwrap(), 2);
+    assert_eq!(instance.reset_changed_pages().unwrap(), 0);
+}
+
+#[test]
+fn stores_and_bulk_memory_operators_are_tracked() {
+    let mut executor = common::test_executor();
+    executor
+        .set_bulk_memory_cost(&BulkMemoryCost::default())
+        .unwrap();
+    let wasm_bytes = wat2wasm(
+        br#"
+        (module
+            (type (;0;) (func))
+            (func $storeWithOffset (type 0)
+              i32.const 8
+              i64.const -1
+              i64.store32 offset=65536)
+            (func $fill (type 0)
+              i32.const 196600
+              i32.const 7
+              i32.const 16
+              memory.fill)
+            (memory (;0;) 4)
+            (export "memory" (memory 0))
+            (export "storeWithOffset" (func $storeWithOffset))
+            (export "fill" (func $fill)))
+        "#,
+    )
+    .unwrap();
+    let instance = executor
+        .new_instance(&wasm_bytes, &reset_options())
+        .unwrap();
+
+    instance.call("storeWithOffset").unwrap();
+    assert_eq!(instance.reset_changed_pages().unwrap(), 1);
+    assert_eq!(instance.memory_load(65544, 4).unwrap()[..4], [0; 4]);
+
+    // The fill crosses from the third page to the fourth
+    instance.call("fill").unwrap();
+    assert_eq!(instance.reset_changed_pages().unwrap(), 2);
+    assert_eq!(instance.memory_load(196600, 16).unwrap()[..16], [0; 16]);
+}
+
+#[test]
+fn only_reported_host_writes_are_restored() {
+    let instance = common::test_instance_with_options(common::COUNTER_WAT, &reset_options());
+    let memory_ptr = instance.memory_ptr().unwrap();
+
+    // Pages are not compared, so a write that is not reported is not restored
+    unsafe { *memory_ptr.add(65536) = 1 };
+    assert_eq!(instance.reset_changed_pages().unwrap(), 0);
+    assert_eq!(instance.memory_load(65536, 1).unwrap()[0], 1);
+
+    instance.mark_memory_written(65536, 1).unwrap();
+    assert_eq!(instance.reset_changed_pages().unwrap(), 1);
+    assert_eq!(instance.memory_load(65536, 1).unwrap()[0], 0);
+
+    assert!(instance.mark_memory_written(2 * 65536, 1).is_err());
+}
+
+#[test]
+fn data_segments_are_restored() {
+    let instance = common::test_instance_with_options(common::ADDER_WAT, &reset_options());
+
+    instance
+        .memory_store(DATA_SEGMENT_OFFSET, b"right number")
+        .unwrap();
+    assert_eq!(instance.reset_changed_pages().unwrap(), 1);
+    assert_eq!(
+        &instance.memory_load(DATA_SEGMENT_OFFSET, 12).unwrap()[..12],
+        b"wrong number"
+    );
+}
+
+#[test]
+fn grown_memory_falls_back_to_full_reset() {
+    let instance = common::test_instance_with_options(common::COUNTER_WAT, &reset_options());
+
+    instance.memory_grow(1).unwrap();
+    assert_eq!(instance.reset_changed_pages().unwrap(), 2);
+}
+
+#[test]
+fn instances_of_a_cached_module_share_the_data_segments() {
+    let executor = common::test_executor();
+    let wasm_bytes = wat2wasm(common::ADDER_WAT.as_bytes()).unwrap();
+
+    // The second instance reuses the module compiled for the first
+    let first = executor
+        .new_instance(&wasm_bytes, &reset_options())
+        .unwrap();
+    let second = executor
+        .new_instance(&wasm_bytes, &reset_options())
+        .unwrap();
+
+    first
+        .memory_store(DATA_SEGMENT_OFFSET, b"first number")
+        .unwrap();
+    second
+        .memory_store(DATA_SEGMENT_OFFSET, b"other number")
+        .unwrap();
+    assert_eq!(first.reset_changed_pages().unwrap(), 1);
+    assert_eq!(second.reset_changed_pages().unwrap(), 1);
+    for instance in [first, second] {
+        assert_eq!(
+            &instance.memory_load(DATA_SEGMENT_OFFSET, 12).unwrap()[..12],
+            b"wrong number"
+        );
+    }
+}
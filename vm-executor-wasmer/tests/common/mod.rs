@@ -1,7 +1,11 @@
+#![allow(dead_code, unused_imports)]
+
 mod test_instance;
+mod test_opcode_cost;
 mod test_wat;
 mod test_wat_bad;
 
 pub use test_instance::*;
+pub use test_opcode_cost::*;
 pub use test_wat::*;
 pub use test_wat_bad::*;
@@ -1,4 +1,7 @@
-use klever_chain_vm_executor::{CompilationOptions, ExecutorService, Instance, VMHooksDefault};
+use klever_chain_vm_executor::{
+    CompilationOptions, CompilerBackend, Executor, ExecutorService, Instance, VMHooksDefault,
+    DEFAULT_EXECUTION_TRACE_LENGTH, DEFAULT_GAS_SCHEDULE_VERSION, DEFAULT_MAX_MEMORY_PAGES,
+};
 use klever_chain_vm_executor_wasmer::BasicExecutorService;
 use wasmer::wat2wasm;
 
@@ -10,13 +13,40 @@ const DUMMY_COMPILATION_OPTIONS: CompilationOptions = CompilationOptions {
     opcode_trace: false,
     metering: false,
     runtime_breakpoints: false,
+    compiler: CompilerBackend::Singlepass as u8,
+    max_memory_pages: DEFAULT_MAX_MEMORY_PAGES,
+    gas_schedule_version: DEFAULT_GAS_SCHEDULE_VERSION,
+    gas_profiling: false,
+    opcode_histogram: false,
+    execution_trace: false,
+    snapshots: false,
+    execution_trace_length: DEFAULT_EXECUTION_TRACE_LENGTH,
 };
 
+pub fn test_compilation_options(metering: bool, runtime_breakpoints: bool) -> CompilationOptions {
+    CompilationOptions {
+        gas_limit: 1000,
+        metering,
+        runtime_breakpoints,
+        ..DUMMY_COMPILATION_OPTIONS
+    }
+}
+
+pub fn test_executor() -> Box<dyn Executor> {
+    let service = BasicExecutorService::new();
+    service.new_executor(Box::new(VMHooksDefault)).unwrap()
+}
+
 pub fn test_instance(wat: &str) -> Box<dyn Instance> {
+    test_instance_with_options(wat, &DUMMY_COMPILATION_OPTIONS)
+}
+
+pub fn test_instance_with_options(
+    wat: &str,
+    compilation_options: &CompilationOptions,
+) -> Box<dyn Instance> {
     let wasm_bytes = wat2wasm(wat.as_bytes()).unwrap();
-    let service = BasicExecutorService::new();
-    let executor = service.new_executor(Box::new(VMHooksDefault)).unwrap();
-    executor
-        .new_instance(&wasm_bytes, &DUMMY_COMPILATION_OPTIONS)
+    test_executor()
+        .new_instance(&wasm_bytes, compilation_options)
         .unwrap()
 }
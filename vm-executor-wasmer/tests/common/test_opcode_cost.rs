@@ -0,0 +1,28 @@
+use klever_chain_vm_executor::OpcodeCost;
+
+/// Non-zero costs for the operators used by the test WATs, so that metering is observable.
+pub fn test_opcode_cost() -> OpcodeCost {
+    OpcodeCost {
+        opcode_block: 1,
+        opcode_br: 2,
+        opcode_brif: 3,
+        opcode_call: 5,
+        opcode_drop: 1,
+        opcode_end: 1,
+        opcode_if: 2,
+        opcode_loop: 2,
+        opcode_nop: 1,
+        opcode_return: 2,
+        opcode_unreachable: 1,
+        opcode_localget: 1,
+        opcode_localset: 1,
+        opcode_localtee: 1,
+        opcode_localallocate: 4,
+        opcode_i32const: 1,
+        opcode_i32add: 1,
+        opcode_i32ne: 1,
+        opcode_i32load: 3,
+        opcode_i32store: 3,
+        ..Default::default()
+    }
+}
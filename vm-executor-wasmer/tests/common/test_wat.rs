@@ -162,3 +162,305 @@ pub const ADDER_WAT: &str = r#"
     (data $.rodata (i32.const 1048576) "wrong number of argumentssum")
     (data $.data (i32.const 1048604) "\9c\ff\ff\ff"))
 "#;
+
+/// Never returns, so it always runs out of gas when metered.
+pub const INFINITE_LOOP_WAT: &str = r#"
+(module
+    (type (;0;) (func))
+    (func $infiniteLoop (type 0)
+      loop  ;; label = @1
+        br 0 (;@1;)
+      end)
+    (memory (;0;) 2)
+    (export "memory" (memory 0))
+    (export "infiniteLoop" (func $infiniteLoop)))
+"#;
+
+pub const MODULE_INFO_WAT: &str = r#"
+(module
+    (type (;0;) (func))
+    (type (;1;) (func (result i32)))
+    (import "env" "getNumArguments" (func (;0;) (type 1)))
+    (func $start (type 0))
+    (func $init (type 0))
+    (table (;0;) 2 4 funcref)
+    (memory (;0;) 2 30)
+    (data (i32.const 0) "abc")
+    (data (i32.const 16) "hello")
+    (start $start)
+    (export "memory" (memory 0))
+    (export "init" (func $init)))
+"#;
+
+pub const MEMORY_GROW_WAT: &str = r#"
+(module
+    (type (;0;) (func))
+    (func $growByTwo (type 0)
+      i32.const 2
+      memory.grow
+      drop)
+    (func $growByThree (type 0)
+      i32.const 3
+      memory.grow
+      drop)
+    (memory (;0;) 2)
+    (export "memory" (memory 0))
+    (export "growByTwo" (func $growByTwo))
+    (export "growByThree" (func $growByThree)))
+"#;
+
+pub const TRAPS_WAT: &str = r#"
+(module
+    (type (;0;) (func))
+    (func $divideByZero (type 0)
+      i32.const 1
+      i32.const 0
+      i32.div_u
+      drop)
+    (func $outOfBounds (type 0)
+      i32.const -1
+      i32.load
+      drop)
+    (func $nestedUnreachable (type 0)
+      call $unreachable)
+    (func $unreachable (type 0)
+      unreachable)
+    (memory (;0;) 2)
+    (export "memory" (memory 0))
+    (export "divideByZero" (func $divideByZero))
+    (export "outOfBounds" (func $outOfBounds))
+    (export "nestedUnreachable" (func $nestedUnreachable)))
+"#;
+
+pub const TYPED_HELPERS_WAT: &str = r#"
+(module
+    (type (;0;) (func))
+    (type (;1;) (func (param i32 i32) (result i32)))
+    (type (;2;) (func (param i64 f32 f64) (result f64 i64)))
+    (func $init (type 0))
+    (func $add (type 1) (param i32 i32) (result i32)
+      local.get 0
+      local.get 1
+      i32.add)
+    (func $forward (type 2) (param i64 f32 f64) (result f64 i64)
+      local.get 2
+      local.get 0
+      i64.const 1
+      i64.add)
+    (memory (;0;) 2)
+    (export "memory" (memory 0))
+    (export "init" (func $init))
+    (export "add" (func $add))
+    (export "forward" (func $forward)))
+"#;
+
+pub const COUNTER_WAT: &str = r#"
+(module
+    (type (;0;) (func))
+    (type (;1;) (func (result i64)))
+    (global $counter (mut i64) (i64.const 0))
+    (func $increment (type 0)
+      global.get $counter
+      i64.const 1
+      i64.add
+      global.set $counter
+      i32.const 16
+      global.get $counter
+      i64.store)
+    (func $getCounter (type 1) (result i64)
+      global.get $counter)
+    (memory (;0;) 2)
+    (export "memory" (memory 0))
+    (export "increment" (func $increment))
+    (export "getCounter" (func $getCounter)))
+"#;
+
+pub const BULK_MEMORY_WAT: &str = r#"
+(module
+    (type (;0;) (func))
+    (func $fillSmall (type 0)
+      i32.const 0
+      i32.const 7
+      i32.const 10
+      memory.fill)
+    (func $fillLarge (type 0)
+      i32.const 0
+      i32.const 7
+      i32.const 1000
+      memory.fill)
+    (func $copy (type 0)
+      i32.const 100
+      i32.const 0
+      i32.const 10
+      memory.copy)
+    (func $init (type 0)
+      i32.const 200
+      i32.const 0
+      i32.const 5
+      memory.init 0
+      data.drop 0)
+    (memory (;0;) 2)
+    (data "hello")
+    (export "memory" (memory 0))
+    (export "fillSmall" (func $fillSmall))
+    (export "fillLarge" (func $fillLarge))
+    (export "copy" (func $copy))
+    (export "init" (func $init)))
+"#;
+
+pub const GAS_PROFILE_WAT: &str = r#"
+(module
+    (type (;0;) (func))
+    (func $cheap (type 0)
+      nop)
+    (func $expensive (type 0)
+      (local i32)
+      i32.const 3
+      local.set 0
+      loop
+        call $cheap
+        local.get 0
+        i32.const -1
+        i32.add
+        local.tee 0
+        br_if 0
+      end)
+    (func $main (type 0)
+      call $expensive
+      call $cheap)
+    (memory (;0;) 1)
+    (export "memory" (memory 0))
+    (export "main" (func $main)))
+"#;
+
+pub const EXECUTION_TRACE_WAT: &str = r#"
+(module
+    (type (;0;) (func))
+    (func $long_trap (type 0)
+      (local i32)
+      loop
+        local.get 0
+        i32.const 1
+        i32.add
+        local.tee 0
+        i32.const 20
+        i32.lt_u
+        br_if 0
+      end
+      unreachable)
+    (func $short_trap (type 0)
+      unreachable)
+    (func $complete (type 0)
+      nop)
+    (memory (;0;) 1)
+    (export "memory" (memory 0))
+    (export "long_trap" (func $long_trap))
+    (export "short_trap" (func $short_trap))
+    (export "complete" (func $complete)))
+"#;
+
+pub const HOOK_REPLAY_WAT: &str = r#"
+(module
+    (type (;0;) (func))
+    (type (;1;) (func (param i32 i32)))
+    (type (;2;) (func (param i32 i32) (result i32)))
+    (import "env" "getArgument" (func $getArgument (type 2)))
+    (import "env" "checkNoPayment" (func $checkNoPayment (type 0)))
+    (import "env" "signalError" (func $signalError (type 1)))
+    (func $main (type 0)
+      i32.const 0
+      i32.const 100
+      call $getArgument
+      drop
+      i32.const 100
+      i32.load8_u
+      i32.const 42
+      i32.eq
+      if
+        call $checkNoPayment
+      else
+        i32.const 0
+        i32.const 0
+        call $signalError
+      end)
+    (memory (;0;) 1)
+    (export "memory" (memory 0))
+    (export "main" (func $main)))
+"#;
+
+pub const IN_MEMORY_HOOKS_WAT: &str = r#"
+(module
+    (type (;0;) (func))
+    (type (;1;) (func (param i64) (result i32)))
+    (type (;2;) (func (param i32 i32)))
+    (type (;3;) (func (param i32 i32 i32)))
+    (type (;4;) (func (param i32 i32 i32) (result i32)))
+    (type (;5;) (func (param i32 i32) (result i64)))
+    (type (;6;) (func (param i32 i32 i64) (result i32)))
+    (type (;7;) (func (param i32 i32) (result i32)))
+    (type (;8;) (func (param i32) (result i32)))
+    (import "env" "bigIntNew" (func $bigIntNew (type 1)))
+    (import "env" "bigIntGetUnsignedArgument" (func $bigIntGetUnsignedArgument (type 2)))
+    (import "env" "bigIntAdd" (func $bigIntAdd (type 3)))
+    (import "env" "bigIntTDiv" (func $bigIntTDiv (type 3)))
+    (import "env" "bigIntStorageStoreUnsigned" (func $bigIntStorageStoreUnsigned (type 4)))
+    (import "env" "smallIntStorageLoadUnsigned" (func $smallIntStorageLoadUnsigned (type 5)))
+    (import "env" "smallIntStorageStoreUnsigned" (func $smallIntStorageStoreUnsigned (type 6)))
+    (import "env" "bigIntGetSignedBytes" (func $bigIntGetSignedBytes (type 7)))
+    (import "env" "mBufferNewFromBytes" (func $mBufferNewFromBytes (type 7)))
+    (import "env" "mBufferFinish" (func $mBufferFinish (type 8)))
+    (func $add (type 0)
+      (local $a i32) (local $b i32)
+      i64.const 0
+      call $bigIntNew
+      local.set $a
+      i64.const 0
+      call $bigIntNew
+      local.set $b
+      i32.const 0
+      local.get $a
+      call $bigIntGetUnsignedArgument
+      i32.const 1
+      local.get $b
+      call $bigIntGetUnsignedArgument
+      local.get $a
+      local.get $a
+      local.get $b
+      call $bigIntAdd
+      i32.const 0
+      i32.const 5
+      local.get $a
+      call $bigIntStorageStoreUnsigned
+      drop
+      i32.const 8
+      i32.const 5
+      i32.const 8
+      i32.const 5
+      call $smallIntStorageLoadUnsigned
+      i64.const 1
+      i64.add
+      call $smallIntStorageStoreUnsigned
+      drop
+      i32.const 100
+      local.get $a
+      i32.const 100
+      call $bigIntGetSignedBytes
+      call $mBufferNewFromBytes
+      call $mBufferFinish
+      drop)
+    (func $divideByZero (type 0)
+      (local $a i32)
+      i64.const 1
+      call $bigIntNew
+      local.tee $a
+      local.get $a
+      i64.const 0
+      call $bigIntNew
+      call $bigIntTDiv)
+    (memory (;0;) 1)
+    (export "memory" (memory 0))
+    (export "add" (func $add))
+    (export "divideByZero" (func $divideByZero))
+    (data (;0;) (i32.const 0) "total")
+    (data (;1;) (i32.const 8) "calls"))
+"#;
@@ -16,3 +16,22 @@ pub const BAD_INIT_RESULT: &str = r#"
     (export "memory" (memory 0))
     (export "init" (func 0)))
 "#;
+
+pub const BAD_IMPORTS: &str = r#"
+(module
+    (type (;0;) (func (param i64)))
+    (type (;1;) (func))
+    (import "env" "notAVMHook" (func (;0;) (type 1)))
+    (import "env" "getNumArguments" (func (;1;) (type 0)))
+    (import "other" "checkNoPayment" (func (;2;) (type 1)))
+    (import "env" "memory" (memory (;0;) 2))
+    (func (;3;) (type 1))
+    (export "init" (func 3)))
+"#;
+
+pub const NO_MEMORY: &str = r#"
+(module
+    (type (;0;) (func))
+    (func $init (type 0))
+    (export "init" (func $init)))
+"#;
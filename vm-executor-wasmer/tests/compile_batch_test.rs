@@ -0,0 +1,85 @@
+mod common;
+
+use klever_chain_vm_executor::{ExecutorError, ValidationError};
+use wasmer::wat2wasm;
+
+#[test]
+fn compile_batch_keeps_going_after_failures() {
+    let mut executor = common::test_executor();
+    executor.set_compile_threads(2).unwrap();
+
+    let empty_sc = wat2wasm(common::EMPTY_SC_WAT.as_bytes()).unwrap();
+    let adder = wat2wasm(common::ADDER_WAT.as_bytes()).unwrap();
+    let no_memory = wat2wasm(common::NO_MEMORY.as_bytes()).unwrap();
+    let contracts: Vec<(&str, &[u8])> = vec![
+        ("empty", &empty_sc),
+        ("invalid", b"not a contract"),
+        ("adder", &adder),
+        ("no-memory", &no_memory),
+    ];
+
+    let options = common::test_compilation_options(true, true);
+    let results = executor.compile_batch(&contracts, &options).unwrap();
+
+    let ids: Vec<&str> = results.iter().map(|result| result.id.as_str()).collect();
+    assert_eq!(ids, vec!["empty", "invalid", "adder", "no-memory"]);
+
+    assert!(results[0].result.is_ok());
+    assert!(matches!(
+        results[1].result,
+        Err(ExecutorError::InvalidModule(_))
+    ));
+    assert!(results[2].result.is_ok());
+    assert!(matches!(
+        results[3].result,
+        Err(ExecutorError::Validation(ValidationError::NoMemory))
+    ));
+}
+
+#[test]
+fn compile_batch_produces_usable_cache() {
+    let executor = common::test_executor();
+    let wasm_bytes = wat2wasm(common::EMPTY_SC_WAT.as_bytes()).unwrap();
+    let options = common::test_compilation_options(true, true);
+
+    let mut results = executor
+        .compile_batch(&[("empty", &wasm_bytes)], &options)
+        .unwrap();
+    let cache_bytes = results.remove(0).result.unwrap();
+
+    let instance = executor
+        .new_instance_from_cache(&cache_bytes, &options)
+        .unwrap();
+    instance.call("init").unwrap();
+
+    // Batches do not go through the module cache
+    let stats = executor.get_module_cache_stats();
+    assert_eq!(stats.len, 0);
+    assert_eq!(stats.misses, 0);
+}
+
+#[test]
+fn compile_batch_does_not_run_start_functions() {
+    let executor = common::test_executor();
+    let wasm_bytes = wat2wasm(
+        br#"
+        (module
+            (func $start unreachable)
+            (memory (;0;) 1)
+            (start $start)
+            (export "memory" (memory 0)))
+        "#,
+    )
+    .unwrap();
+    let options = common::test_compilation_options(true, true);
+
+    let mut results = executor
+        .compile_batch(&[("trapping-start", &wasm_bytes)], &options)
+        .unwrap();
+    let cache_bytes = results.remove(0).result.unwrap();
+
+    assert!(matches!(
+        executor.new_instance_from_cache(&cache_bytes, &options),
+        Err(ExecutorError::Instantiation(_))
+    ));
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use klever_chain_vm_executor::{
+    BreakpointValue, CompilationOptions, CompilerBackend, ExecutorError,
+};
+use wasmer::wat2wasm;
+
+const GAS_LIMIT: u64 = 10_000;
+
+#[derive(Debug, PartialEq)]
+struct BackendOutcome {
+    call_succeeded: bool,
+    points_used: u64,
+    breakpoint_value: BreakpointValue,
+}
+
+fn run_endpoint(wat: &str, endpoint: &str, compiler: CompilerBackend) -> BackendOutcome {
+    let mut executor = common::test_executor();
+    executor
+        .set_opcode_cost(&common::test_opcode_cost())
+        .unwrap();
+
+    let options = CompilationOptions {
+        gas_limit: GAS_LIMIT,
+        compiler: compiler as u8,
+        ..common::test_compilation_options(true, true)
+    };
+    let wasm_bytes = wat2wasm(wat.as_bytes()).unwrap();
+    let instance = executor.new_instance(&wasm_bytes, &options).unwrap();
+
+    let call_succeeded = instance.call(endpoint).is_ok();
+    BackendOutcome {
+        call_succeeded,
+        points_used: instance.get_points_used().unwrap(),
+        breakpoint_value: instance.get_breakpoint_value().unwrap(),
+    }
+}
+
+fn check_same_outcome(wat: &str, endpoint: &str) -> BackendOutcome {
+    let singlepass_outcome = run_endpoint(wat, endpoint, CompilerBackend::Singlepass);
+    let cranelift_outcome = run_endpoint(wat, endpoint, CompilerBackend::Cranelift);
+    assert_eq!(
+        singlepass_outcome, cranelift_outcome,
+        "endpoint: {endpoint}"
+    );
+    singlepass_outcome
+}
+
+#[test]
+fn same_outcome_empty_sc() {
+    let outcome = check_same_outcome(common::EMPTY_SC_WAT, "init");
+    assert!(outcome.call_succeeded);
+    assert!(outcome.points_used > 0);
+    assert_eq!(outcome.breakpoint_value, BreakpointValue::None);
+
+    check_same_outcome(common::EMPTY_SC_WAT, "callBack");
+}
+
+#[test]
+fn same_outcome_adder() {
+    for endpoint in ["init", "add", "getSum", "callBack"] {
+        check_same_outcome(common::ADDER_WAT, endpoint);
+    }
+}
+
+#[test]
+fn same_outcome_out_of_gas() {
+    let outcome = check_same_outcome(common::INFINITE_LOOP_WAT, "infiniteLoop");
+    assert!(!outcome.call_succeeded);
+    assert!(outcome.points_used >= GAS_LIMIT);
+    assert_eq!(outcome.breakpoint_value, BreakpointValue::OutOfGas);
+}
+
+#[test]
+fn unknown_compiler_backend_is_rejected() {
+    let executor = common::test_executor();
+    let options = CompilationOptions {
+        compiler: 2,
+        ..common::test_compilation_options(true, true)
+    };
+    let wasm_bytes = wat2wasm(common::EMPTY_SC_WAT.as_bytes()).unwrap();
+    assert!(matches!(
+        executor.new_instance(&wasm_bytes, &options),
+        Err(ExecutorError::UnknownCompilerBackend(2))
+    ));
+}
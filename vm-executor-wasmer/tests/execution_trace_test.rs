@@ -0,0 +1,159 @@
+mod common;
+
+use klever_chain_vm_executor::{
+    BreakpointValue, CompilationOptions, ExecutionTraceEntry, ExecutorError, Instance,
+    DEFAULT_EXECUTION_TRACE_LENGTH, MAX_EXECUTION_TRACE_LENGTH,
+};
+use wasmer::wat2wasm;
+
+fn execution_trace_options(metering: bool, runtime_breakpoints: bool) -> CompilationOptions {
+    CompilationOptions {
+        execution_trace: true,
+        ..common::test_compilation_options(metering, runtime_breakpoints)
+    }
+}
+
+fn try_execution_trace_instance(
+    compilation_options: &CompilationOptions,
+) -> Result<Box<dyn Instance>, ExecutorError> {
+    let mut executor = common::test_executor();
+    executor
+        .set_opcode_cost(&common::test_opcode_cost())
+        .unwrap();
+    let wasm_bytes = wat2wasm(common::EXECUTION_TRACE_WAT.as_bytes()).unwrap();
+    executor.new_instance(&wasm_bytes, compilation_options)
+}
+
+fn execution_trace_instance(compilation_options: &CompilationOptions) -> Box<dyn Instance> {
+    try_execution_trace_instance(compilation_options).unwrap()
+}
+
+fn block(function_index: u32, operator_index: u32) -> ExecutionTraceEntry {
+    ExecutionTraceEntry {
+        function_index,
+        operator_index,
+    }
+}
+
+#[test]
+fn trap_keeps_the_last_blocks() {
+    let instance = execution_trace_instance(&execution_trace_options(true, false));
+    assert!(instance.call("long_trap").is_err());
+
+    // The loop body starts at operator 1, the loop exit at 8 and the trapping block at 9
+    let mut expected = vec![block(0, 1); DEFAULT_EXECUTION_TRACE_LENGTH as usize - 2];
+    expected.extend([block(0, 8), block(0, 9)]);
+    assert_eq!(instance.last_execution_trace().unwrap(), Some(expected));
+}
+
+#[test]
+fn trace_length_is_configurable() {
+    for execution_trace_length in [1, 3, MAX_EXECUTION_TRACE_LENGTH] {
+        let instance = execution_trace_instance(&CompilationOptions {
+            execution_trace_length,
+            ..execution_trace_options(true, false)
+        });
+        assert!(instance.call("long_trap").is_err());
+
+        // The function entry, 20 loop iterations, the loop exit and the trapping block
+        let mut blocks = vec![block(0, 0)];
+        blocks.extend(vec![block(0, 1); 20]);
+        blocks.extend([block(0, 8), block(0, 9)]);
+        let expected = &blocks[blocks.len().saturating_sub(execution_trace_length as usize)..];
+        assert_eq!(
+            instance.last_execution_trace().unwrap(),
+            Some(expected.to_vec())
+        );
+    }
+}
+
+#[test]
+fn trace_length_out_of_range_is_rejected() {
+    for execution_trace_length in [0, MAX_EXECUTION_TRACE_LENGTH + 1] {
+        let result = try_execution_trace_instance(&CompilationOptions {
+            execution_trace_length,
+            ..execution_trace_options(true, false)
+        });
+        assert!(matches!(
+            result,
+            Err(ExecutorError::InvalidExecutionTraceLength(length)) if length == execution_trace_length
+        ));
+    }
+
+    // The length is ignored without the execution trace
+    try_execution_trace_instance(&CompilationOptions {
+        execution_trace_length: 0,
+        ..common::test_compilation_options(true, false)
+    })
+    .unwrap();
+}
+
+#[test]
+fn trace_only_holds_the_last_call() {
+    let instance = execution_trace_instance(&execution_trace_options(true, false));
+    assert_eq!(instance.last_execution_trace().unwrap(), None);
+
+    assert!(instance.call("long_trap").is_err());
+    assert!(instance.call("short_trap").is_err());
+    assert_eq!(
+        instance.last_execution_trace().unwrap(),
+        Some(vec![block(1, 0)])
+    );
+
+    instance.call("complete").unwrap();
+    assert_eq!(instance.last_execution_trace().unwrap(), None);
+
+    let outcome = instance.call_with_report("short_trap").unwrap();
+    assert!(!outcome.is_success());
+    assert_eq!(
+        instance.last_execution_trace().unwrap(),
+        Some(vec![block(1, 0)])
+    );
+}
+
+#[test]
+fn breakpoint_keeps_the_trace() {
+    let instance = execution_trace_instance(&CompilationOptions {
+        gas_limit: 50,
+        ..execution_trace_options(true, true)
+    });
+    assert!(instance.call("long_trap").is_err());
+    assert_eq!(
+        instance.get_breakpoint_value().unwrap(),
+        BreakpointValue::OutOfGas
+    );
+
+    let execution_trace = instance.last_execution_trace().unwrap().unwrap();
+    assert!(!execution_trace.is_empty());
+    assert!(execution_trace
+        .iter()
+        .all(|entry| entry.function_index == 0));
+}
+
+#[test]
+fn tracing_does_not_change_the_gas_used() {
+    let traced = execution_trace_instance(&execution_trace_options(true, false));
+    let untraced = execution_trace_instance(&common::test_compilation_options(true, false));
+    assert!(traced.call("long_trap").is_err());
+    assert!(untraced.call("long_trap").is_err());
+    assert_eq!(
+        traced.get_points_used().unwrap(),
+        untraced.get_points_used().unwrap()
+    );
+}
+
+#[test]
+fn execution_trace_is_disabled_by_default() {
+    let instance = execution_trace_instance(&common::test_compilation_options(true, false));
+    assert!(matches!(
+        instance.last_execution_trace(),
+        Err(ExecutorError::ExecutionTraceDisabled)
+    ));
+
+    // The blocks are recorded by the metering middleware
+    let instance = execution_trace_instance(&execution_trace_options(false, false));
+    assert!(matches!(
+        instance.last_execution_trace(),
+        Err(ExecutorError::ExecutionTraceDisabled)
+    ));
+}
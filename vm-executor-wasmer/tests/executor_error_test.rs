@@ -0,0 +1,69 @@
+mod common;
+
+use klever_chain_vm_executor::{BreakpointValue, ExecutorError, ValidationError};
+use wasmer::wat2wasm;
+
+#[test]
+fn missing_memory_is_a_validation_error() {
+    let wasm_bytes = wat2wasm(common::NO_MEMORY.as_bytes()).unwrap();
+    let options = common::test_compilation_options(true, true);
+    assert!(matches!(
+        common::test_executor().new_instance(&wasm_bytes, &options),
+        Err(ExecutorError::Validation(ValidationError::NoMemory))
+    ));
+}
+
+#[test]
+fn invalid_bytes_are_an_invalid_module() {
+    let options = common::test_compilation_options(true, true);
+    assert!(matches!(
+        common::test_executor().new_instance(b"not a wasm module", &options),
+        Err(ExecutorError::InvalidModule(_))
+    ));
+}
+
+#[test]
+fn missing_export() {
+    let instance = common::test_instance(common::EMPTY_SC_WAT);
+    match instance.call("missingFunction") {
+        Err(ExecutorError::MissingExport(name)) => assert_eq!(name, "missingFunction"),
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
+
+#[test]
+fn out_of_gas_trap_carries_breakpoint() {
+    let mut executor = common::test_executor();
+    executor
+        .set_opcode_cost(&common::test_opcode_cost())
+        .unwrap();
+
+    let options = common::test_compilation_options(true, true);
+    let wasm_bytes = wat2wasm(common::INFINITE_LOOP_WAT.as_bytes()).unwrap();
+    let instance = executor.new_instance(&wasm_bytes, &options).unwrap();
+    assert!(matches!(
+        instance.call("infiniteLoop"),
+        Err(ExecutorError::Trap {
+            breakpoint: BreakpointValue::OutOfGas,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn memory_access_out_of_bounds() {
+    let instance = common::test_instance(common::EMPTY_SC_WAT);
+    let memory_length = instance.memory_length().unwrap() as isize;
+
+    assert!(instance
+        .memory_store(memory_length - 4, &[1, 2, 3, 4])
+        .is_ok());
+    assert!(matches!(
+        instance.memory_store(memory_length - 2, &[1, 2, 3, 4]),
+        Err(ExecutorError::MemoryOutOfBounds { length: 4, .. })
+    ));
+    assert!(matches!(
+        instance.memory_load(memory_length, 1),
+        Err(ExecutorError::MemoryOutOfBounds { .. })
+    ));
+}
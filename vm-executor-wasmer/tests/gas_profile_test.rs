@@ -0,0 +1,103 @@
+mod common;
+
+use klever_chain_vm_executor::{CompilationOptions, ExecutorError, Instance};
+use wasmer::wat2wasm;
+
+fn gas_profile_options(metering: bool) -> CompilationOptions {
+    CompilationOptions {
+        gas_profiling: true,
+        ..common::test_compilation_options(metering, false)
+    }
+}
+
+fn gas_profile_instance(compilation_options: &CompilationOptions) -> Box<dyn Instance> {
+    let mut executor = common::test_executor();
+    executor
+        .set_opcode_cost(&common::test_opcode_cost())
+        .unwrap();
+    let wasm_bytes = wat2wasm(common::GAS_PROFILE_WAT.as_bytes()).unwrap();
+    executor
+        .new_instance(&wasm_bytes, compilation_options)
+        .unwrap()
+}
+
+#[test]
+fn gas_is_attributed_to_functions() {
+    let instance = gas_profile_instance(&gas_profile_options(true));
+    instance.call("main").unwrap();
+
+    let gas_profile = instance.gas_profile().unwrap();
+    let names: Vec<_> = gas_profile
+        .iter()
+        .map(|entry| (entry.function_index, entry.name.as_deref()))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            (0, Some("cheap")),
+            (1, Some("expensive")),
+            (2, Some("main"))
+        ]
+    );
+
+    let call_counts: Vec<_> = gas_profile.iter().map(|entry| entry.call_count).collect();
+    assert_eq!(call_counts, vec![4, 1, 1]);
+
+    // Every point used is attributed to exactly one function
+    let total_gas: u64 = gas_profile.iter().map(|entry| entry.gas).sum();
+    assert_eq!(total_gas, instance.get_points_used().unwrap());
+    assert!(gas_profile[1].gas > gas_profile[0].gas);
+    assert!(gas_profile.iter().all(|entry| entry.gas > 0));
+}
+
+#[test]
+fn gas_profile_adds_up_until_reset() {
+    // Snapshots give reset_changed_pages access to the profiling globals
+    let options = CompilationOptions {
+        snapshots: true,
+        ..gas_profile_options(true)
+    };
+    let instance = gas_profile_instance(&options);
+    instance.call("main").unwrap();
+    let first = instance.gas_profile().unwrap();
+
+    instance.call("main").unwrap();
+    let second = instance.gas_profile().unwrap();
+    for (first, second) in first.iter().zip(&second) {
+        assert_eq!(second.gas, first.gas * 2);
+        assert_eq!(second.call_count, first.call_count * 2);
+    }
+
+    instance.reset_changed_pages().unwrap();
+    let after_reset = instance.gas_profile().unwrap();
+    assert!(after_reset
+        .iter()
+        .all(|entry| entry.gas == 0 && entry.call_count == 0));
+
+    // The profiling middleware is compiled into the cached module
+    let cache_bytes = instance.cache().unwrap();
+    let mut executor = common::test_executor();
+    executor
+        .set_opcode_cost(&common::test_opcode_cost())
+        .unwrap();
+    let cached_instance = executor
+        .new_instance_from_cache(&cache_bytes, &common::test_compilation_options(true, false))
+        .unwrap();
+    cached_instance.call("main").unwrap();
+    assert_eq!(cached_instance.gas_profile().unwrap(), first);
+}
+
+#[test]
+fn gas_profile_requires_profiling_and_metering() {
+    let instances = [
+        gas_profile_instance(&common::test_compilation_options(true, false)),
+        gas_profile_instance(&gas_profile_options(false)),
+    ];
+    for instance in instances {
+        instance.call("main").unwrap();
+        assert!(matches!(
+            instance.gas_profile(),
+            Err(ExecutorError::GasProfilingDisabled)
+        ));
+    }
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use klever_chain_vm_executor::{
+    CacheArtifactError, CompilationOptions, Executor, ExecutorError, GasSchedule, GasScheduleError,
+    OpcodeCost,
+};
+use wasmer::wat2wasm;
+
+fn gas_schedule(version: u32, activation_epoch: u64, opcode_cost: OpcodeCost) -> GasSchedule {
+    GasSchedule {
+        name: format!("test schedule {version}"),
+        version,
+        activation_epoch,
+        opcode_cost,
+        bulk_memory_cost: None,
+    }
+}
+
+fn doubled_opcode_cost() -> OpcodeCost {
+    let opcode_cost = common::test_opcode_cost();
+    OpcodeCost {
+        opcode_block: opcode_cost.opcode_block * 2,
+        opcode_br: opcode_cost.opcode_br * 2,
+        opcode_brif: opcode_cost.opcode_brif * 2,
+        opcode_call: opcode_cost.opcode_call * 2,
+        opcode_drop: opcode_cost.opcode_drop * 2,
+        opcode_end: opcode_cost.opcode_end * 2,
+        opcode_if: opcode_cost.opcode_if * 2,
+        opcode_i32const: opcode_cost.opcode_i32const * 2,
+        opcode_i32add: opcode_cost.opcode_i32add * 2,
+        opcode_i32ne: opcode_cost.opcode_i32ne * 2,
+        opcode_i32load: opcode_cost.opcode_i32load * 2,
+        opcode_i32store: opcode_cost.opcode_i32store * 2,
+        ..opcode_cost
+    }
+}
+
+fn schedule_executor() -> Box<dyn Executor> {
+    let mut executor = common::test_executor();
+    executor
+        .register_gas_schedule(gas_schedule(1, 10, common::test_opcode_cost()))
+        .unwrap();
+    executor
+        .register_gas_schedule(gas_schedule(2, 20, doubled_opcode_cost()))
+        .unwrap();
+    executor
+}
+
+fn schedule_options(gas_schedule_version: u32) -> CompilationOptions {
+    CompilationOptions {
+        gas_schedule_version,
+        ..common::test_compilation_options(true, true)
+    }
+}
+
+#[test]
+fn schedules_activate_by_epoch() {
+    let mut executor = schedule_executor();
+    assert_eq!(executor.gas_schedule_version_at_epoch(5), None);
+    assert_eq!(executor.gas_schedule_version_at_epoch(10), Some(1));
+    assert_eq!(executor.gas_schedule_version_at_epoch(19), Some(1));
+    assert_eq!(executor.gas_schedule_version_at_epoch(100), Some(2));
+
+    let rejected = [
+        (
+            gas_schedule(0, 30, OpcodeCost::default()),
+            GasScheduleError::ReservedVersion(0),
+        ),
+        (
+            gas_schedule(2, 30, OpcodeCost::default()),
+            GasScheduleError::DuplicateVersion(2),
+        ),
+        (
+            gas_schedule(3, 20, OpcodeCost::default()),
+            GasScheduleError::DuplicateActivationEpoch {
+                epoch: 20,
+                existing: 2,
+            },
+        ),
+    ];
+    for (schedule, expected) in rejected {
+        match executor.register_gas_schedule(schedule) {
+            Err(ExecutorError::GasSchedule(err)) => assert_eq!(err, expected),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn instances_are_metered_with_their_schedule() {
+    let mut executor = schedule_executor();
+    let wasm_bytes = wat2wasm(common::EMPTY_SC_WAT.as_bytes()).unwrap();
+
+    let points_used_with = |executor: &dyn Executor, version| {
+        let instance = executor
+            .new_instance(&wasm_bytes, &schedule_options(version))
+            .unwrap();
+        assert_eq!(instance.gas_schedule_version(), version);
+        instance.call("init").unwrap();
+        instance.get_points_used().unwrap()
+    };
+
+    let first = points_used_with(executor.as_ref(), 1);
+    assert!(first > 0);
+    assert_eq!(points_used_with(executor.as_ref(), 2), first * 2);
+
+    // The default costs are separate from the registered schedules
+    executor.set_opcode_cost(&doubled_opcode_cost()).unwrap();
+    assert_eq!(points_used_with(executor.as_ref(), 1), first);
+    assert_eq!(points_used_with(executor.as_ref(), 0), first * 2);
+}
+
+#[test]
+fn unknown_schedule_is_rejected() {
+    let executor = schedule_executor();
+    let wasm_bytes = wat2wasm(common::EMPTY_SC_WAT.as_bytes()).unwrap();
+
+    let result = executor.new_instance(&wasm_bytes, &schedule_options(7));
+    assert!(matches!(
+        result,
+        Err(ExecutorError::GasSchedule(
+            GasScheduleError::UnknownVersion(7)
+        ))
+    ));
+}
+
+#[test]
+fn cache_records_the_schedule() {
+    let executor = schedule_executor();
+    let wasm_bytes = wat2wasm(common::EMPTY_SC_WAT.as_bytes()).unwrap();
+    let cache_bytes = executor
+        .new_instance(&wasm_bytes, &schedule_options(1))
+        .unwrap()
+        .cache()
+        .unwrap();
+
+    let instance = executor
+        .new_instance_from_cache(&cache_bytes, &schedule_options(1))
+        .unwrap();
+    assert_eq!(instance.gas_schedule_version(), 1);
+
+    let result = executor.new_instance_from_cache(&cache_bytes, &schedule_options(2));
+    assert!(matches!(
+        result,
+        Err(ExecutorError::Cache(
+            CacheArtifactError::GasScheduleMismatch {
+                cached: 1,
+                requested: 2
+            }
+        ))
+    ));
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use klever_chain_vm_executor::{
+    with_current_instance, BreakpointValue, CompilationOptions, Executor, ExecutorService,
+    HookCall, HookCallRecorder, HookCallSink, HookRecording, HookValue, Instance, ReplayVMHooks,
+    TracingVMHooks, VMHooks, VMHooksDefault,
+};
+use klever_chain_vm_executor_wasmer::BasicExecutorService;
+use wasmer::wat2wasm;
+
+/// Stands in for a node that writes the argument into contract memory,
+/// since `VMHooksDefault` writes nothing.
+#[derive(Debug)]
+struct ArgumentWriter;
+
+impl HookCallSink for ArgumentWriter {
+    fn record(&self, hook_call: HookCall) {
+        if hook_call.name == "get_argument" {
+            if let HookValue::MemPtr(arg_offset) = hook_call.arguments[1].value {
+                with_current_instance(|instance| instance.memory_store(arg_offset, &[42]).unwrap());
+            }
+        }
+    }
+}
+
+fn replay_options() -> CompilationOptions {
+    common::test_compilation_options(false, true)
+}
+
+fn replay_instance(vm_hooks: impl VMHooks) -> (Box<dyn Executor>, Box<dyn Instance>) {
+    let executor = BasicExecutorService::new()
+        .new_executor(Box::new(vm_hooks))
+        .unwrap();
+    let wasm_bytes = wat2wasm(common::HOOK_REPLAY_WAT.as_bytes()).unwrap();
+    let instance = executor
+        .new_instance(&wasm_bytes, &replay_options())
+        .unwrap();
+    (executor, instance)
+}
+
+fn record_main() -> HookRecording {
+    let recorder = HookCallRecorder::new();
+    let node = TracingVMHooks::new(VMHooksDefault, Box::new(ArgumentWriter));
+    let (_executor, instance) =
+        replay_instance(TracingVMHooks::new(node, Box::new(recorder.clone())));
+    instance.call("main").unwrap();
+    recorder.take_recording()
+}
+
+#[test]
+fn recording_captures_memory_writes() {
+    let recording = record_main();
+    let names: Vec<_> = recording
+        .calls
+        .iter()
+        .map(|call| call.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["get_argument", "check_no_payment"]);
+    assert_eq!(
+        recording.calls[0].arguments,
+        vec![HookValue::I32(0), HookValue::MemPtr(100)]
+    );
+    assert_eq!(recording.calls[0].result, Some(HookValue::I32(0)));
+    assert_eq!(recording.calls[0].memory_writes.len(), 1);
+    assert_eq!(recording.calls[0].memory_writes[0].offset, 100);
+    assert_eq!(recording.calls[0].memory_writes[0].bytes, vec![42]);
+    assert!(recording.calls[1].memory_writes.is_empty());
+
+    let path = std::env::temp_dir().join("hook_replay_test_recording.json");
+    recording.save(&path).unwrap();
+    assert!(std::fs::read_to_string(&path).unwrap().contains("\"2a\""));
+    assert_eq!(HookRecording::load(&path).unwrap(), recording);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn replay_follows_the_recording() {
+    let replay = ReplayVMHooks::new(record_main());
+    let (_executor, instance) = replay_instance(replay.clone());
+    instance.call("main").unwrap();
+
+    assert_eq!(replay.divergence(), None);
+    assert_eq!(replay.remaining_calls(), 0);
+    assert_eq!(instance.memory_load(100, 1).unwrap()[0], 42);
+}
+
+#[test]
+fn divergence_is_reported_at_the_call_index() {
+    // Without the argument, the contract signals an error instead of checking the payment
+    let mut recording = record_main();
+    recording.calls[0].memory_writes.clear();
+
+    let replay = ReplayVMHooks::new(recording);
+    let (_executor, instance) = replay_instance(replay.clone());
+    assert!(instance.call("main").is_err());
+    assert_eq!(
+        instance.get_breakpoint_value().unwrap(),
+        BreakpointValue::ExecutionFailed
+    );
+
+    let divergence = replay.divergence().unwrap();
+    assert_eq!(divergence.call_index, 1);
+    assert_eq!(divergence.expected.unwrap().name, "check_no_payment");
+    assert_eq!(divergence.actual.name, "signal_error");
+    assert_eq!(
+        divergence.actual.arguments,
+        vec![HookValue::MemPtr(0), HookValue::MemLength(0)]
+    );
+}
+
+#[test]
+fn calls_past_the_recording_diverge() {
+    let mut recording = record_main();
+    recording.calls.truncate(1);
+
+    let replay = ReplayVMHooks::new(recording);
+    let (_executor, instance) = replay_instance(replay.clone());
+    assert!(instance.call("main").is_err());
+
+    let divergence = replay.divergence().unwrap();
+    assert_eq!(divergence.call_index, 1);
+    assert_eq!(divergence.expected, None);
+    assert_eq!(
+        divergence.to_string(),
+        "hook call 1 diverged: expected no more calls, got check_no_payment()"
+    );
+}
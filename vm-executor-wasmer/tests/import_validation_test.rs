@@ -0,0 +1,63 @@
+mod common;
+
+use klever_chain_vm_executor::{ExecutorError, ImportIssue, ValidationError};
+use wasmer::wat2wasm;
+
+fn expected_issues() -> Vec<ImportIssue> {
+    vec![
+        ImportIssue::UnknownFunction {
+            name: "notAVMHook".to_string(),
+        },
+        ImportIssue::SignatureMismatch {
+            name: "getNumArguments".to_string(),
+            expected: "[] -> [I32]".to_string(),
+            found: "[I64] -> []".to_string(),
+        },
+        ImportIssue::UnsupportedNamespace {
+            namespace: "other".to_string(),
+            name: "checkNoPayment".to_string(),
+        },
+        ImportIssue::UnsupportedKind {
+            name: "memory".to_string(),
+            kind: "memory".to_string(),
+        },
+    ]
+}
+
+#[test]
+fn valid_imports() {
+    let executor = common::test_executor();
+    for wat in [common::EMPTY_SC_WAT, common::ADDER_WAT] {
+        let wasm_bytes = wat2wasm(wat.as_bytes()).unwrap();
+        assert_eq!(executor.validate_imports(&wasm_bytes).unwrap(), vec![]);
+    }
+}
+
+#[test]
+fn all_import_issues_are_reported() {
+    let wasm_bytes = wat2wasm(common::BAD_IMPORTS.as_bytes()).unwrap();
+    let issues = common::test_executor()
+        .validate_imports(&wasm_bytes)
+        .unwrap();
+    assert_eq!(issues, expected_issues());
+}
+
+#[test]
+fn instantiation_reports_import_issues() {
+    let wasm_bytes = wat2wasm(common::BAD_IMPORTS.as_bytes()).unwrap();
+    let options = common::test_compilation_options(true, true);
+    match common::test_executor().new_instance(&wasm_bytes, &options) {
+        Ok(_) => panic!("instantiation should have failed"),
+        Err(ExecutorError::Validation(ValidationError::InvalidImports(err))) => {
+            assert_eq!(err.issues, expected_issues())
+        }
+        Err(err) => panic!("unexpected error: {err}"),
+    }
+}
+
+#[test]
+fn invalid_bytes_are_an_error() {
+    assert!(common::test_executor()
+        .validate_imports(b"not a wasm module")
+        .is_err());
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use klever_chain_vm_executor::{
+    BreakpointValue, Executor, ExecutorService, InMemoryContext, InMemoryVMHooks, Instance,
+    VMHooksDefault,
+};
+use klever_chain_vm_executor_wasmer::BasicExecutorService;
+use wasmer::wat2wasm;
+
+fn in_memory_instance(context: &InMemoryContext) -> (Box<dyn Executor>, Box<dyn Instance>) {
+    let executor = BasicExecutorService::new()
+        .new_executor(Box::new(InMemoryVMHooks::new(
+            VMHooksDefault,
+            context.clone(),
+        )))
+        .unwrap();
+    let wasm_bytes = wat2wasm(common::IN_MEMORY_HOOKS_WAT.as_bytes()).unwrap();
+    let instance = executor
+        .new_instance(&wasm_bytes, &common::test_compilation_options(false, true))
+        .unwrap();
+    (executor, instance)
+}
+
+#[test]
+fn contract_reads_and_writes_memory_storage_and_return_data() {
+    let context = InMemoryContext::new();
+    let (_executor, instance) = in_memory_instance(&context);
+
+    context.set_arguments(vec![vec![0x7f], vec![0x01]]);
+    instance.call("add").unwrap();
+    context.set_arguments(vec![vec![0xff, 0xff], vec![0x01]]);
+    instance.call("add").unwrap();
+
+    assert_eq!(context.error(), None);
+    // The sums in two's complement, read back from contract memory
+    assert_eq!(
+        context.return_data(),
+        vec![vec![0x00, 0x80], vec![0x01, 0x00, 0x00]]
+    );
+    assert_eq!(context.storage(b"total"), vec![0x01, 0x00, 0x00]);
+    assert_eq!(context.storage(b"calls"), vec![0x02]);
+}
+
+#[test]
+fn failed_hook_stops_the_contract() {
+    let context = InMemoryContext::new();
+    let (_executor, instance) = in_memory_instance(&context);
+
+    assert!(instance.call("divideByZero").is_err());
+    assert_eq!(
+        instance.get_breakpoint_value().unwrap(),
+        BreakpointValue::ExecutionFailed
+    );
+    assert_eq!(context.error().unwrap(), "division by zero");
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use klever_chain_vm_executor::{BreakpointValue, CompilationOptions, Executor, Instance, Value};
+use klever_chain_vm_executor_wasmer::{InstancePool, InstancePoolStats, ResetMode};
+use wasmer::wat2wasm;
+
+fn pool_options() -> CompilationOptions {
+    CompilationOptions {
+        max_memory_grow: 1,
+        max_memory_grow_delta: 10,
+        snapshots: true,
+        ..common::test_compilation_options(true, true)
+    }
+}
+
+fn new_instance(executor: &dyn Executor, wasm_bytes: &[u8]) -> Box<dyn Instance> {
+    executor.new_instance(wasm_bytes, &pool_options()).unwrap()
+}
+
+#[test]
+fn checkout_reuses_checked_in_instances() {
+    let executor = common::test_executor();
+    let wasm_bytes = wat2wasm(common::EMPTY_SC_WAT.as_bytes()).unwrap();
+    let code_hash = InstancePool::code_hash(&wasm_bytes);
+    let pool = InstancePool::new(2, 10);
+
+    assert!(pool.checkout(&code_hash).is_none());
+    pool.checkin(code_hash, new_instance(executor.as_ref(), &wasm_bytes))
+        .unwrap();
+
+    let instance = pool.checkout(&code_hash).unwrap();
+    instance.call("init").unwrap();
+    assert!(pool.checkout(&code_hash).is_none());
+    assert!(pool
+        .checkout(&InstancePool::code_hash(b"other code"))
+        .is_none());
+
+    assert_eq!(
+        pool.stats(),
+        InstancePoolStats {
+            hits: 1,
+            misses: 3,
+            dropped: 0,
+            len: 0,
+            capacity: 10,
+        }
+    );
+}
+
+#[test]
+fn checkin_respects_capacities() {
+    let executor = common::test_executor();
+    let empty_sc = wat2wasm(common::EMPTY_SC_WAT.as_bytes()).unwrap();
+    let memory_grow = wat2wasm(common::MEMORY_GROW_WAT.as_bytes()).unwrap();
+    let pool = InstancePool::new(2, 3);
+
+    for _ in 0..3 {
+        pool.checkin(
+            InstancePool::code_hash(&empty_sc),
+            new_instance(executor.as_ref(), &empty_sc),
+        )
+        .unwrap();
+    }
+    for _ in 0..2 {
+        pool.checkin(
+            InstancePool::code_hash(&memory_grow),
+            new_instance(executor.as_ref(), &memory_grow),
+        )
+        .unwrap();
+    }
+
+    let stats = pool.stats();
+    assert_eq!(stats.len, 3);
+    assert_eq!(stats.dropped, 2);
+
+    pool.clear();
+    assert_eq!(pool.stats().len, 0);
+    assert!(pool.checkout(&InstancePool::code_hash(&empty_sc)).is_none());
+}
+
+#[test]
+fn checkin_resets_instrumentation_globals() {
+    let mut executor = common::test_executor();
+    executor
+        .set_opcode_cost(&common::test_opcode_cost())
+        .unwrap();
+    let wasm_bytes = wat2wasm(common::MEMORY_GROW_WAT.as_bytes()).unwrap();
+    let code_hash = InstancePool::code_hash(&wasm_bytes);
+    let pool = InstancePool::new(1, 1);
+
+    let instance = new_instance(executor.as_ref(), &wasm_bytes);
+    instance.call("growByTwo").unwrap();
+    assert!(instance.call("growByTwo").is_err());
+    assert_eq!(
+        instance.get_breakpoint_value().unwrap(),
+        BreakpointValue::MemoryLimit
+    );
+    assert!(instance.get_points_used().unwrap() > 0);
+
+    pool.checkin(code_hash, instance).unwrap();
+    let instance = pool.checkout(&code_hash).unwrap();
+
+    assert_eq!(
+        instance.get_breakpoint_value().unwrap(),
+        BreakpointValue::None
+    );
+    assert_eq!(instance.get_points_used().unwrap(), 0);
+    instance.call("growByTwo").unwrap();
+}
+
+#[test]
+fn changed_pages_reset_mode() {
+    let executor = common::test_executor();
+    let wasm_bytes = wat2wasm(common::COUNTER_WAT.as_bytes()).unwrap();
+    let code_hash = InstancePool::code_hash(&wasm_bytes);
+    let mut pool = InstancePool::new(1, 1);
+    pool.set_reset_mode(ResetMode::ChangedPages);
+
+    let instance = new_instance(executor.as_ref(), &wasm_bytes);
+    instance.call("increment").unwrap();
+    pool.checkin(code_hash, instance).unwrap();
+
+    let instance = pool.checkout(&code_hash).unwrap();
+    assert_eq!(instance.memory_load(16, 1).unwrap()[0], 0);
+    assert_eq!(
+        instance.call_typed("getCounter", &[]).unwrap(),
+        vec![Value::I64(0)]
+    );
+}
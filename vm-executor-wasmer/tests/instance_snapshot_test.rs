@@ -0,0 +1,241 @@
+mod common;
+
+use klever_chain_vm_executor::{
+    BreakpointValue, CompilationOptions, ExecutorError, Instance, InstanceSnapshot, SnapshotError,
+    ValidationError, Value,
+};
+use wasmer::wat2wasm;
+
+fn snapshot_options() -> CompilationOptions {
+    CompilationOptions {
+        max_memory_grow: 10,
+        max_memory_grow_delta: 10,
+        snapshots: true,
+        ..common::test_compilation_options(true, true)
+    }
+}
+
+fn metered_counter_instance() -> Box<dyn Instance> {
+    let mut executor = common::test_executor();
+    executor
+        .set_opcode_cost(&common::test_opcode_cost())
+        .unwrap();
+
+    let wasm_bytes = wat2wasm(common::COUNTER_WAT.as_bytes()).unwrap();
+    executor
+        .new_instance(&wasm_bytes, &snapshot_options())
+        .unwrap()
+}
+
+fn counter(instance: &dyn Instance) -> i64 {
+    match instance.call_typed("getCounter", &[]).unwrap()[..] {
+        [Value::I64(counter)] => counter,
+        ref results => panic!("unexpected results: {results:?}"),
+    }
+}
+
+fn stored_counter(instance: &dyn Instance) -> u8 {
+    instance.memory_load(16, 1).unwrap()[0]
+}
+
+#[test]
+fn restore_returns_to_snapshot() {
+    let instance = metered_counter_instance();
+
+    instance.call("increment").unwrap();
+    let snapshot = instance.snapshot().unwrap();
+    let points_used = instance.get_points_used().unwrap();
+    assert!(points_used > 0);
+
+    instance.call("increment").unwrap();
+    instance.call("increment").unwrap();
+    assert_eq!(counter(instance.as_ref()), 3);
+    assert_eq!(stored_counter(instance.as_ref()), 3);
+    instance
+        .set_breakpoint_value(BreakpointValue::SignalError)
+        .unwrap();
+
+    instance.restore(&snapshot).unwrap();
+    assert_eq!(instance.get_points_used().unwrap(), points_used);
+    assert_eq!(
+        instance.get_breakpoint_value().unwrap(),
+        BreakpointValue::None
+    );
+    assert_eq!(counter(instance.as_ref()), 1);
+    assert_eq!(stored_counter(instance.as_ref()), 1);
+}
+
+#[test]
+fn snapshot_bytes_roundtrip() {
+    let instance = common::test_instance_with_options(common::COUNTER_WAT, &snapshot_options());
+    instance.call("increment").unwrap();
+
+    let snapshot = instance.snapshot().unwrap();
+    let bytes = snapshot.to_bytes();
+    assert_eq!(InstanceSnapshot::from_bytes(&bytes).unwrap(), snapshot);
+
+    assert_eq!(
+        InstanceSnapshot::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(SnapshotError::Truncated)
+    );
+    let mut bad_magic = bytes.clone();
+    bad_magic[0] ^= 0xff;
+    assert_eq!(
+        InstanceSnapshot::from_bytes(&bad_magic),
+        Err(SnapshotError::BadMagic)
+    );
+}
+
+#[test]
+fn restore_on_fresh_instance() {
+    let instance = common::test_instance_with_options(common::COUNTER_WAT, &snapshot_options());
+    instance.call("increment").unwrap();
+    instance.memory_grow(1).unwrap();
+    let snapshot = instance.snapshot().unwrap();
+
+    let fresh = common::test_instance_with_options(common::COUNTER_WAT, &snapshot_options());
+    fresh.restore(&snapshot).unwrap();
+    assert_eq!(counter(fresh.as_ref()), 1);
+    assert_eq!(fresh.memory_length().unwrap(), 3 * 65536);
+}
+
+#[test]
+fn restore_rejects_foreign_snapshots() {
+    let instance = common::test_instance_with_options(common::COUNTER_WAT, &snapshot_options());
+    let snapshot = instance.snapshot().unwrap();
+
+    let other_code =
+        common::test_instance_with_options(common::MEMORY_GROW_WAT, &snapshot_options());
+    assert!(matches!(
+        other_code.restore(&snapshot),
+        Err(ExecutorError::Snapshot(SnapshotError::CodeMismatch))
+    ));
+
+    let other_middlewares = common::test_instance_with_options(
+        common::COUNTER_WAT,
+        &CompilationOptions {
+            snapshots: true,
+            ..common::test_compilation_options(false, false)
+        },
+    );
+    assert!(matches!(
+        other_middlewares.restore(&snapshot),
+        Err(ExecutorError::Snapshot(SnapshotError::GlobalsMismatch))
+    ));
+
+    instance.memory_grow(1).unwrap();
+    assert!(matches!(
+        instance.restore(&snapshot),
+        Err(ExecutorError::Snapshot(SnapshotError::MemoryGrown {
+            snapshot: 131072,
+            current: 196608
+        }))
+    ));
+}
+
+#[test]
+fn restore_rejects_memory_it_cannot_hold() {
+    let instance = common::test_instance_with_options(common::COUNTER_WAT, &snapshot_options());
+    let snapshot = instance.snapshot().unwrap();
+
+    let mut unaligned = snapshot.clone();
+    unaligned.memory.push(0);
+    assert!(matches!(
+        instance.restore(&unaligned),
+        Err(ExecutorError::Snapshot(SnapshotError::UnalignedMemory(
+            131073
+        )))
+    ));
+    assert_eq!(
+        InstanceSnapshot::from_bytes(&unaligned.to_bytes()),
+        Err(SnapshotError::UnalignedMemory(131073))
+    );
+
+    let mut too_large = snapshot;
+    too_large.memory.resize(21 * 65536, 0);
+    assert!(matches!(
+        instance.restore(&too_large),
+        Err(ExecutorError::MemoryGrowLimit {
+            requested: 21,
+            max: 20
+        })
+    ));
+    assert_eq!(instance.memory_length().unwrap(), 2 * 65536);
+}
+
+#[test]
+fn snapshots_require_the_option() {
+    let instance = common::test_instance_with_options(
+        common::COUNTER_WAT,
+        &common::test_compilation_options(true, true),
+    );
+    assert!(matches!(
+        instance.snapshot(),
+        Err(ExecutorError::SnapshotsDisabled)
+    ));
+
+    let snapshot = common::test_instance_with_options(common::COUNTER_WAT, &snapshot_options())
+        .snapshot()
+        .unwrap();
+    assert!(matches!(
+        instance.restore(&snapshot),
+        Err(ExecutorError::SnapshotsDisabled)
+    ));
+}
+
+#[test]
+fn snapshots_are_part_of_the_module_cache_key() {
+    let executor = common::test_executor();
+    let wasm_bytes = wat2wasm(common::COUNTER_WAT.as_bytes()).unwrap();
+
+    let without_snapshots = CompilationOptions {
+        snapshots: false,
+        ..snapshot_options()
+    };
+    let instance = executor
+        .new_instance(&wasm_bytes, &without_snapshots)
+        .unwrap();
+    assert!(matches!(
+        instance.snapshot(),
+        Err(ExecutorError::SnapshotsDisabled)
+    ));
+
+    let instance = executor
+        .new_instance(&wasm_bytes, &snapshot_options())
+        .unwrap();
+    instance.call("increment").unwrap();
+    let snapshot = instance.snapshot().unwrap();
+    instance.call("increment").unwrap();
+    instance.restore(&snapshot).unwrap();
+    assert_eq!(counter(instance.as_ref()), 1);
+
+    let stats = executor.get_module_cache_stats();
+    assert_eq!(stats.misses, 2);
+    assert_eq!(stats.hits, 0);
+}
+
+#[test]
+fn reserved_export_names_are_rejected() {
+    let executor = common::test_executor();
+    let wasm_bytes = wat2wasm(
+        br#"
+        (module
+            (global $own (mut i32) (i32.const 7))
+            (memory (;0;) 1)
+            (export "memory" (memory 0))
+            (export "snapshot_global_0" (global $own)))
+        "#,
+    )
+    .unwrap();
+
+    assert!(matches!(
+        executor.new_instance(&wasm_bytes, &snapshot_options()),
+        Err(ExecutorError::Validation(
+            ValidationError::ReservedExportName(name)
+        )) if name == "snapshot_global_0"
+    ));
+
+    // The names are only reserved when snapshots are enabled
+    let options = common::test_compilation_options(true, true);
+    assert!(executor.new_instance(&wasm_bytes, &options).is_ok());
+}
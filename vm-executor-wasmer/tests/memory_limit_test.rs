@@ -0,0 +1,76 @@
+mod common;
+
+use klever_chain_vm_executor::{
+    BreakpointValue, CompilationOptions, ExecutorError, ValidationError,
+};
+use wasmer::wat2wasm;
+
+fn memory_options(max_memory_pages: u64) -> CompilationOptions {
+    CompilationOptions {
+        max_memory_grow: 10,
+        max_memory_grow_delta: 10,
+        max_memory_pages,
+        ..common::test_compilation_options(true, true)
+    }
+}
+
+#[test]
+fn declared_memory_is_checked_against_option() {
+    let executor = common::test_executor();
+    let wasm_bytes = wat2wasm(common::MODULE_INFO_WAT.as_bytes()).unwrap();
+
+    let err = match executor.new_instance(&wasm_bytes, &memory_options(20)) {
+        Ok(_) => panic!("memory of 30 pages should have been rejected"),
+        Err(err) => err,
+    };
+    assert!(matches!(
+        err,
+        ExecutorError::Validation(ValidationError::MemoryTooLarge {
+            declared: 30,
+            max: 20
+        })
+    ));
+    assert_eq!(
+        err.to_string(),
+        "memory size exceeds maximum allowed: declared 30 pages, maximum is 20 pages"
+    );
+
+    assert!(executor
+        .new_instance(&wasm_bytes, &memory_options(30))
+        .is_ok());
+}
+
+#[test]
+fn host_memory_grow_is_capped() {
+    let instance = common::test_instance_with_options(common::MEMORY_GROW_WAT, &memory_options(4));
+
+    assert_eq!(instance.memory_grow(2).unwrap(), 2);
+    assert!(matches!(
+        instance.memory_grow(1),
+        Err(ExecutorError::MemoryGrowLimit {
+            requested: 5,
+            max: 4
+        })
+    ));
+}
+
+#[test]
+fn contract_memory_grow_is_capped() {
+    let instance = common::test_instance_with_options(common::MEMORY_GROW_WAT, &memory_options(4));
+    assert!(matches!(
+        instance.call("growByThree"),
+        Err(ExecutorError::Trap {
+            breakpoint: BreakpointValue::MemoryLimit,
+            ..
+        })
+    ));
+    assert_eq!(
+        instance.get_breakpoint_value().unwrap(),
+        BreakpointValue::MemoryLimit
+    );
+
+    let instance = common::test_instance_with_options(common::MEMORY_GROW_WAT, &memory_options(4));
+    assert!(instance.call("growByTwo").is_ok());
+    assert_eq!(instance.memory_length().unwrap(), 4 * 65536);
+    assert!(instance.call("growByTwo").is_err());
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use klever_chain_vm_executor::{BreakpointValue, ExecutorError, ValidationError};
+use wasmer::wat2wasm;
+
+fn too_many_locals_wat() -> String {
+    format!(
+        r#"(module
+            (func (export "init") (local {}))
+            (memory (export "memory") 2))"#,
+        "i32 ".repeat(4001)
+    )
+}
+
+const FLOAT_WAT: &str = r#"(module
+    (func (export "init") f32.const 1 drop)
+    (memory (export "memory") 2))"#;
+
+#[test]
+fn unmetered_instance() {
+    let options = common::test_compilation_options(false, true);
+    let instance = common::test_instance_with_options(common::EMPTY_SC_WAT, &options);
+    assert!(instance.call("init").is_ok());
+    assert!(matches!(
+        instance.get_points_used(),
+        Err(ExecutorError::MeteringDisabled)
+    ));
+    assert!(matches!(
+        instance.set_points_limit(100),
+        Err(ExecutorError::MeteringDisabled)
+    ));
+    assert_eq!(
+        instance.get_breakpoint_value().unwrap(),
+        BreakpointValue::None
+    );
+}
+
+#[test]
+fn instance_without_breakpoints() {
+    let options = common::test_compilation_options(true, false);
+    let instance = common::test_instance_with_options(common::EMPTY_SC_WAT, &options);
+    assert!(instance.call("init").is_ok());
+    assert!(instance.get_points_used().is_ok());
+    assert!(matches!(
+        instance.get_breakpoint_value(),
+        Err(ExecutorError::RuntimeBreakpointsDisabled)
+    ));
+    assert!(matches!(
+        instance.set_breakpoint_value(BreakpointValue::SignalError),
+        Err(ExecutorError::RuntimeBreakpointsDisabled)
+    ));
+}
+
+#[test]
+fn instance_with_all_middlewares() {
+    let options = common::test_compilation_options(true, true);
+    let instance = common::test_instance_with_options(common::EMPTY_SC_WAT, &options);
+    assert!(instance.call("init").is_ok());
+    assert!(instance.get_points_used().is_ok());
+    assert_eq!(
+        instance.get_breakpoint_value().unwrap(),
+        BreakpointValue::None
+    );
+}
+
+#[test]
+fn cache_keeps_middleware_settings() {
+    let options = common::test_compilation_options(true, false);
+    let instance = common::test_instance_with_options(common::EMPTY_SC_WAT, &options);
+    let cache_bytes = instance.cache().unwrap();
+
+    // the settings recorded in the cache win over the ones given at deserialization
+    let all_enabled = common::test_compilation_options(true, true);
+    let cached_instance = common::test_executor()
+        .new_instance_from_cache(&cache_bytes, &all_enabled)
+        .unwrap();
+    assert!(cached_instance.call("init").is_ok());
+    assert!(cached_instance.get_points_used().is_ok());
+    assert!(matches!(
+        cached_instance.get_breakpoint_value(),
+        Err(ExecutorError::RuntimeBreakpointsDisabled)
+    ));
+}
+
+#[test]
+fn unmetered_instance_is_validated() {
+    let wat = too_many_locals_wat();
+    let wasm_bytes = wat2wasm(wat.as_bytes()).unwrap();
+    for metering in [false, true] {
+        let options = common::test_compilation_options(metering, false);
+        assert!(matches!(
+            common::test_executor().new_instance(&wasm_bytes, &options),
+            Err(ExecutorError::Validation(ValidationError::TooManyLocals {
+                count: 4001,
+                max: 4000
+            }))
+        ));
+    }
+
+    let wasm_bytes = wat2wasm(FLOAT_WAT.as_bytes()).unwrap();
+    let options = common::test_compilation_options(false, false);
+    assert!(matches!(
+        common::test_executor().new_instance(&wasm_bytes, &options),
+        Err(ExecutorError::Validation(
+            ValidationError::UnsupportedOperator(_)
+        ))
+    ));
+}
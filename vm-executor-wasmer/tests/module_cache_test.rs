@@ -0,0 +1,108 @@
+mod common;
+
+use klever_chain_vm_executor::{ModuleCacheStats, OpcodeCost};
+use wasmer::wat2wasm;
+
+#[test]
+fn same_code_is_compiled_once() {
+    let executor = common::test_executor();
+    let wasm_bytes = wat2wasm(common::ADDER_WAT.as_bytes()).unwrap();
+    let options = common::test_compilation_options(true, true);
+
+    let first = executor.new_instance(&wasm_bytes, &options).unwrap();
+    let second = executor.new_instance(&wasm_bytes, &options).unwrap();
+    assert!(first.call("callBack").is_ok());
+    assert!(second.call("callBack").is_ok());
+
+    let stats = executor.get_module_cache_stats();
+    assert_eq!(stats.hits, 1);
+    assert_eq!(stats.misses, 1);
+    assert_eq!(stats.len, 1);
+}
+
+#[test]
+fn cached_instances_do_not_share_state() {
+    let executor = common::test_executor();
+    let wasm_bytes = wat2wasm(common::ADDER_WAT.as_bytes()).unwrap();
+    let options = common::test_compilation_options(true, true);
+
+    let first = executor.new_instance(&wasm_bytes, &options).unwrap();
+    first.set_points_used(42).unwrap();
+    let second = executor.new_instance(&wasm_bytes, &options).unwrap();
+
+    assert_eq!(first.get_points_used().unwrap(), 42);
+    assert_eq!(second.get_points_used().unwrap(), 0);
+}
+
+#[test]
+fn options_and_opcode_cost_are_part_of_the_key() {
+    let mut executor = common::test_executor();
+    let wasm_bytes = wat2wasm(common::ADDER_WAT.as_bytes()).unwrap();
+
+    let metered = common::test_compilation_options(true, true);
+    let unmetered = common::test_compilation_options(false, true);
+    executor.new_instance(&wasm_bytes, &metered).unwrap();
+    executor.new_instance(&wasm_bytes, &unmetered).unwrap();
+
+    executor
+        .set_opcode_cost(&OpcodeCost {
+            opcode_call: 7,
+            ..OpcodeCost::default()
+        })
+        .unwrap();
+    executor.new_instance(&wasm_bytes, &metered).unwrap();
+
+    // only the gas limit differs, which is applied after instantiation
+    let other_gas_limit = klever_chain_vm_executor::CompilationOptions {
+        gas_limit: 5,
+        ..common::test_compilation_options(true, true)
+    };
+    executor
+        .new_instance(&wasm_bytes, &other_gas_limit)
+        .unwrap();
+
+    let stats = executor.get_module_cache_stats();
+    assert_eq!(stats.misses, 3);
+    assert_eq!(stats.hits, 1);
+}
+
+#[test]
+fn least_recently_used_module_is_evicted() {
+    let mut executor = common::test_executor();
+    executor.set_module_cache_capacity(1).unwrap();
+    let options = common::test_compilation_options(true, true);
+    let adder = wat2wasm(common::ADDER_WAT.as_bytes()).unwrap();
+    let empty = wat2wasm(common::EMPTY_SC_WAT.as_bytes()).unwrap();
+
+    executor.new_instance(&adder, &options).unwrap();
+    executor.new_instance(&empty, &options).unwrap();
+    executor.new_instance(&adder, &options).unwrap();
+
+    assert_eq!(
+        executor.get_module_cache_stats(),
+        ModuleCacheStats {
+            hits: 0,
+            misses: 3,
+            evictions: 2,
+            len: 1,
+            capacity: 1,
+        }
+    );
+}
+
+#[test]
+fn zero_capacity_disables_the_cache() {
+    let mut executor = common::test_executor();
+    let wasm_bytes = wat2wasm(common::ADDER_WAT.as_bytes()).unwrap();
+    let options = common::test_compilation_options(true, true);
+
+    executor.new_instance(&wasm_bytes, &options).unwrap();
+    executor.set_module_cache_capacity(0).unwrap();
+    executor.new_instance(&wasm_bytes, &options).unwrap();
+
+    let stats = executor.get_module_cache_stats();
+    assert_eq!(stats.hits, 0);
+    assert_eq!(stats.misses, 2);
+    assert_eq!(stats.evictions, 1);
+    assert_eq!(stats.len, 0);
+}
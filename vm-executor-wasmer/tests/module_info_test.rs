@@ -0,0 +1,116 @@
+mod common;
+
+use klever_chain_vm_executor::{
+    CompilationOptions, ModuleExport, ModuleImport, ModuleItemKind, ModuleMemory, ModuleTable,
+    DEFAULT_MAX_MEMORY_PAGES,
+};
+use wasmer::wat2wasm;
+
+fn with_custom_section(mut wasm_bytes: Vec<u8>, name: &str) -> Vec<u8> {
+    wasm_bytes.push(0);
+    wasm_bytes.push(name.len() as u8 + 1);
+    wasm_bytes.push(name.len() as u8);
+    wasm_bytes.extend_from_slice(name.as_bytes());
+    wasm_bytes
+}
+
+#[test]
+fn inspect_module() {
+    let wasm_bytes = wat2wasm(common::MODULE_INFO_WAT.as_bytes()).unwrap();
+    let wasm_bytes = with_custom_section(wasm_bytes.into_owned(), "name-hint");
+    let info = common::test_executor()
+        .inspect_module(&wasm_bytes, DEFAULT_MAX_MEMORY_PAGES)
+        .unwrap();
+
+    assert_eq!(
+        info.imports,
+        vec![ModuleImport {
+            namespace: "env".to_string(),
+            name: "getNumArguments".to_string(),
+            kind: ModuleItemKind::Function,
+            signature: Some("[] -> [I32]".to_string()),
+        }]
+    );
+    assert_eq!(
+        info.exports,
+        vec![
+            ModuleExport {
+                name: "memory".to_string(),
+                kind: ModuleItemKind::Memory,
+                index: 0,
+            },
+            ModuleExport {
+                name: "init".to_string(),
+                kind: ModuleItemKind::Function,
+                index: 2,
+            },
+        ]
+    );
+    assert_eq!(
+        info.memories,
+        vec![ModuleMemory {
+            min_pages: 2,
+            max_pages: Some(30),
+            imported: false,
+        }]
+    );
+    assert_eq!(
+        info.tables,
+        vec![ModuleTable {
+            min_size: 2,
+            max_size: Some(4),
+            imported: false,
+        }]
+    );
+    assert_eq!(info.function_count, 2);
+    assert_eq!(info.data_segment_count, 2);
+    assert_eq!(info.data_segment_bytes, 8);
+    assert!(info.has_start_function);
+    // the text format also emits a `name` section for the symbolic names
+    assert_eq!(
+        info.custom_sections,
+        vec!["name".to_string(), "name-hint".to_string()]
+    );
+}
+
+#[test]
+fn memory_limits_predict_instantiation() {
+    let executor = common::test_executor();
+    let options = common::test_compilation_options(true, true);
+
+    for (wat, within_limits) in [(common::ADDER_WAT, true), (common::MODULE_INFO_WAT, false)] {
+        let wasm_bytes = wat2wasm(wat.as_bytes()).unwrap();
+        let info = executor
+            .inspect_module(&wasm_bytes, options.max_memory_pages)
+            .unwrap();
+        assert_eq!(info.memory_within_limits(), within_limits);
+        assert_eq!(
+            executor.new_instance(&wasm_bytes, &options).is_ok(),
+            within_limits
+        );
+    }
+}
+
+#[test]
+fn memory_limits_follow_the_compilation_options() {
+    let executor = common::test_executor();
+    let options = CompilationOptions {
+        max_memory_pages: 30,
+        ..common::test_compilation_options(true, true)
+    };
+
+    let wasm_bytes = wat2wasm(common::MODULE_INFO_WAT.as_bytes()).unwrap();
+    let info = executor
+        .inspect_module(&wasm_bytes, options.max_memory_pages)
+        .unwrap();
+    assert_eq!(info.max_memory_pages_allowed, 30);
+    assert!(info.memory_within_limits());
+    assert!(executor.new_instance(&wasm_bytes, &options).is_ok());
+}
+
+#[test]
+fn invalid_bytes_are_an_error() {
+    assert!(common::test_executor()
+        .inspect_module(b"not a wasm module", DEFAULT_MAX_MEMORY_PAGES)
+        .is_err());
+}
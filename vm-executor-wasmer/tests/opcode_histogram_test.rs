@@ -0,0 +1,85 @@
+mod common;
+
+use klever_chain_vm_executor::{CompilationOptions, ExecutorError, Instance, OpcodeClass};
+use wasmer::wat2wasm;
+
+fn histogram_options(metering: bool) -> CompilationOptions {
+    CompilationOptions {
+        opcode_histogram: true,
+        ..common::test_compilation_options(metering, false)
+    }
+}
+
+fn histogram_instance(compilation_options: &CompilationOptions) -> Box<dyn Instance> {
+    let mut executor = common::test_executor();
+    executor
+        .set_opcode_cost(&common::test_opcode_cost())
+        .unwrap();
+    let wasm_bytes = wat2wasm(common::GAS_PROFILE_WAT.as_bytes()).unwrap();
+    executor
+        .new_instance(&wasm_bytes, compilation_options)
+        .unwrap()
+}
+
+#[test]
+fn executed_operators_are_counted_by_class() {
+    let instance = histogram_instance(&histogram_options(true));
+    instance.call("main").unwrap();
+
+    let opcode_histogram = instance.opcode_histogram().unwrap();
+    let counts: Vec<_> = opcode_histogram
+        .counts
+        .iter()
+        .map(|(opcode_class, count)| (*opcode_class, *count))
+        .collect();
+    assert_eq!(
+        counts,
+        vec![
+            (OpcodeClass::ControlFlow, 15),
+            (OpcodeClass::Call, 5),
+            (OpcodeClass::Local, 7),
+            (OpcodeClass::Constant, 4),
+            (OpcodeClass::Arithmetic, 3),
+        ]
+    );
+    assert_eq!(opcode_histogram.count(OpcodeClass::Load), 0);
+    assert_eq!(opcode_histogram.total(), 34);
+
+    instance.call("main").unwrap();
+    assert_eq!(instance.opcode_histogram().unwrap().total(), 68);
+
+    instance.reset_opcode_histogram().unwrap();
+    assert_eq!(instance.opcode_histogram().unwrap().total(), 0);
+}
+
+#[test]
+fn counting_does_not_change_the_gas_used() {
+    let counted = histogram_instance(&histogram_options(true));
+    let uncounted = histogram_instance(&common::test_compilation_options(true, false));
+    counted.call("main").unwrap();
+    uncounted.call("main").unwrap();
+
+    assert_eq!(
+        counted.get_points_used().unwrap(),
+        uncounted.get_points_used().unwrap()
+    );
+}
+
+#[test]
+fn opcode_histogram_requires_histogram_and_metering() {
+    let instances = [
+        histogram_instance(&common::test_compilation_options(true, false)),
+        histogram_instance(&histogram_options(false)),
+    ];
+    for instance in instances {
+        instance.call("main").unwrap();
+        assert!(matches!(
+            instance.opcode_histogram(),
+            Err(ExecutorError::OpcodeHistogramDisabled)
+        ));
+        assert!(matches!(
+            instance.reset_opcode_histogram(),
+            Err(ExecutorError::OpcodeHistogramDisabled)
+        ));
+    }
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use klever_chain_vm_executor::{
+    CompilationOptions, Executor, ExecutorError, OpcodeTraceEntry, OpcodeTraceSink,
+};
+use std::fs;
+use std::sync::{Arc, Mutex};
+use wasmer::wat2wasm;
+
+fn trace_options() -> CompilationOptions {
+    CompilationOptions {
+        opcode_trace: true,
+        ..common::test_compilation_options(false, false)
+    }
+}
+
+fn trace_executor(sink: OpcodeTraceSink) -> Box<dyn Executor> {
+    let mut executor = common::test_executor();
+    executor.set_opcode_trace_sink(sink).unwrap();
+    executor
+}
+
+fn memory_trace() -> Vec<OpcodeTraceEntry> {
+    let wasm_bytes = wat2wasm(common::ADDER_WAT.as_bytes()).unwrap();
+    let instance = trace_executor(OpcodeTraceSink::Memory)
+        .new_instance(&wasm_bytes, &trace_options())
+        .unwrap();
+    instance.opcode_trace().to_vec()
+}
+
+fn sorted(mut lines: Vec<String>) -> Vec<String> {
+    lines.sort();
+    lines
+}
+
+#[test]
+fn trace_is_kept_in_memory() {
+    let opcode_trace = memory_trace();
+    assert!(!opcode_trace.is_empty());
+    assert!(opcode_trace
+        .iter()
+        .any(|entry| entry.function_index == 0 && entry.operator_index == 0));
+    assert!(opcode_trace.iter().any(|entry| entry.operator == "I32Add"));
+    assert!(opcode_trace
+        .iter()
+        .all(|entry| entry.operator.chars().all(char::is_alphanumeric)));
+
+    // Without tracing, or with another sink, instances keep nothing
+    let wasm_bytes = wat2wasm(common::ADDER_WAT.as_bytes()).unwrap();
+    let instance = trace_executor(OpcodeTraceSink::Memory)
+        .new_instance(&wasm_bytes, &common::test_compilation_options(false, false))
+        .unwrap();
+    assert!(instance.opcode_trace().is_empty());
+}
+
+#[test]
+fn trace_is_kept_in_memory_by_default() {
+    let wasm_bytes = wat2wasm(common::ADDER_WAT.as_bytes()).unwrap();
+    let instance = common::test_executor()
+        .new_instance(&wasm_bytes, &trace_options())
+        .unwrap();
+    assert_eq!(instance.opcode_trace().len(), memory_trace().len());
+}
+
+#[test]
+fn trace_is_written_as_json_lines() {
+    let path = std::env::temp_dir().join(format!("opcode_trace_test_{}.jsonl", std::process::id()));
+    let wasm_bytes = wat2wasm(common::ADDER_WAT.as_bytes()).unwrap();
+    let instance = trace_executor(OpcodeTraceSink::File(path.clone()))
+        .new_instance(&wasm_bytes, &trace_options())
+        .unwrap();
+    assert!(instance.opcode_trace().is_empty());
+
+    let written = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    // Functions may be compiled in parallel, so only the entries are compared, not their order
+    let expected: Vec<String> = memory_trace()
+        .iter()
+        .map(|entry| entry.to_json_line())
+        .collect();
+    assert_eq!(
+        sorted(written.lines().map(str::to_string).collect()),
+        sorted(expected)
+    );
+    assert!(written
+        .lines()
+        .next()
+        .unwrap()
+        .starts_with(r#"{"function_index":"#));
+}
+
+#[test]
+fn trace_is_passed_to_callback() {
+    let traced = Arc::new(Mutex::new(Vec::new()));
+    let callback_traced = traced.clone();
+    let executor = trace_executor(OpcodeTraceSink::Callback(Arc::new(move |entry| {
+        callback_traced.lock().unwrap().push(entry.clone())
+    })));
+    let wasm_bytes = wat2wasm(common::ADDER_WAT.as_bytes()).unwrap();
+    executor
+        .new_instance(&wasm_bytes, &trace_options())
+        .unwrap();
+
+    let traced = traced.lock().unwrap();
+    let json_lines = |entries: &[OpcodeTraceEntry]| -> Vec<String> {
+        entries.iter().map(|entry| entry.to_json_line()).collect()
+    };
+    assert_eq!(
+        sorted(json_lines(&traced)),
+        sorted(json_lines(&memory_trace()))
+    );
+}
+
+#[test]
+fn unwritable_trace_file_is_an_error() {
+    let path = std::env::temp_dir()
+        .join("opcode_trace_test_missing_dir")
+        .join("opcode.trace2");
+    let wasm_bytes = wat2wasm(common::ADDER_WAT.as_bytes()).unwrap();
+    let result =
+        trace_executor(OpcodeTraceSink::File(path)).new_instance(&wasm_bytes, &trace_options());
+    assert!(matches!(result, Err(ExecutorError::OpcodeTrace(_))));
+}
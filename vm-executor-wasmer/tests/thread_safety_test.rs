@@ -0,0 +1,57 @@
+mod common;
+
+use klever_chain_vm_executor::{CompilerBackend, Executor, ExecutorError, Instance, Value};
+use klever_chain_vm_executor_wasmer::{WasmerExecutor, WasmerInstance};
+use std::thread;
+use wasmer::wat2wasm;
+
+const THREAD_COUNT: i32 = 8;
+
+fn assert_send_sync<T: Send + Sync + ?Sized>() {}
+
+#[test]
+fn executor_and_instances_are_send_and_sync() {
+    assert_send_sync::<dyn Executor>();
+    assert_send_sync::<dyn Instance>();
+    assert_send_sync::<WasmerExecutor>();
+    assert_send_sync::<WasmerInstance>();
+}
+
+#[test]
+fn compile_and_call_on_several_threads() {
+    let executor = common::test_executor();
+    let wasm_bytes = wat2wasm(common::TYPED_HELPERS_WAT.as_bytes()).unwrap();
+
+    thread::scope(|scope| {
+        for thread_index in 0..THREAD_COUNT {
+            let executor = &executor;
+            let wasm_bytes = &wasm_bytes;
+            scope.spawn(move || {
+                // Different options compile separate modules, equal ones share the cached module
+                let mut options = common::test_compilation_options(false, thread_index % 2 == 1);
+                if thread_index % 4 >= 2 {
+                    options.compiler = CompilerBackend::Cranelift as u8;
+                }
+
+                let instance = executor.new_instance(wasm_bytes, &options).unwrap();
+                let results = instance
+                    .call_typed("add", &[Value::I32(thread_index), Value::I32(1)])
+                    .unwrap();
+                assert_eq!(results, vec![Value::I32(thread_index + 1)]);
+            });
+        }
+    });
+
+    let stats = executor.get_module_cache_stats();
+    assert_eq!(stats.hits + stats.misses, THREAD_COUNT as u64);
+}
+
+#[test]
+fn instance_can_move_to_another_thread() {
+    let instance = common::test_instance(common::TRAPS_WAT);
+
+    let result = thread::spawn(move || instance.call("divideByZero"))
+        .join()
+        .unwrap();
+    assert!(matches!(result, Err(ExecutorError::Trap { .. })));
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use klever_chain_vm_executor::{BreakpointValue, ExecutorError, Value, ValueType};
+use wasmer::wat2wasm;
+
+#[test]
+fn typed_call_returns_results() {
+    let instance = common::test_instance(common::TYPED_HELPERS_WAT);
+
+    assert_eq!(instance.call_typed("init", &[]).unwrap(), vec![]);
+    assert_eq!(
+        instance
+            .call_typed("add", &[Value::I32(2), Value::I32(40)])
+            .unwrap(),
+        vec![Value::I32(42)]
+    );
+    assert_eq!(
+        instance
+            .call_typed(
+                "forward",
+                &[Value::I64(7), Value::F32(1.5), Value::F64(4.0)]
+            )
+            .unwrap(),
+        vec![Value::F64(4.0), Value::I64(8)]
+    );
+}
+
+#[test]
+fn typed_call_checks_arguments() {
+    let instance = common::test_instance(common::TYPED_HELPERS_WAT);
+
+    match instance.call_typed("add", &[Value::I32(1)]) {
+        Err(ExecutorError::ArgumentCount {
+            expected, found, ..
+        }) => {
+            assert_eq!(expected, 2);
+            assert_eq!(found, 1);
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    match instance.call_typed("add", &[Value::I32(1), Value::I64(2)]) {
+        Err(ExecutorError::ArgumentType {
+            index,
+            expected,
+            found,
+            ..
+        }) => {
+            assert_eq!(index, 1);
+            assert_eq!(expected, ValueType::I32);
+            assert_eq!(found, ValueType::I64);
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    assert!(matches!(
+        instance.call_typed("missingFunction", &[]),
+        Err(ExecutorError::MissingExport(_))
+    ));
+}
+
+#[test]
+fn typed_call_keeps_breakpoints() {
+    let mut executor = common::test_executor();
+    executor
+        .set_opcode_cost(&common::test_opcode_cost())
+        .unwrap();
+
+    let options = common::test_compilation_options(true, true);
+    let wasm_bytes = wat2wasm(common::INFINITE_LOOP_WAT.as_bytes()).unwrap();
+    let instance = executor.new_instance(&wasm_bytes, &options).unwrap();
+
+    match instance.call_typed("infiniteLoop", &[]) {
+        Err(ExecutorError::Trap { breakpoint, .. }) => {
+            assert_eq!(breakpoint, BreakpointValue::OutOfGas)
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
+
+#[test]
+fn helpers_are_not_valid_endpoints() {
+    let instance = common::test_instance(common::TYPED_HELPERS_WAT);
+    assert!(!instance.check_signatures());
+}
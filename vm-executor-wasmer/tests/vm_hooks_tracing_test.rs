@@ -0,0 +1,68 @@
+mod common;
+
+use std::time::Duration;
+
+use klever_chain_vm_executor::{
+    ExecutorService, HookArgument, HookCall, HookCallLog, HookValue, TracingVMHooks, VMHooks,
+    VMHooksDefault,
+};
+use klever_chain_vm_executor_wasmer::BasicExecutorService;
+use wasmer::wat2wasm;
+
+#[test]
+fn hook_calls_are_recorded_in_order() {
+    let hook_call_log = HookCallLog::new();
+    let vm_hooks = TracingVMHooks::new(VMHooksDefault, Box::new(hook_call_log.clone()));
+    let executor = BasicExecutorService::new()
+        .new_executor(Box::new(vm_hooks))
+        .unwrap();
+
+    let wasm_bytes = wat2wasm(common::EMPTY_SC_WAT.as_bytes()).unwrap();
+    let instance = executor
+        .new_instance(&wasm_bytes, &common::test_compilation_options(false, false))
+        .unwrap();
+    instance.call("init").unwrap();
+
+    let hook_calls: Vec<_> = hook_call_log
+        .take()
+        .into_iter()
+        .map(|hook_call| (hook_call.name, hook_call.arguments, hook_call.result))
+        .collect();
+    assert_eq!(
+        hook_calls,
+        vec![
+            ("check_no_payment", vec![], None),
+            ("get_num_arguments", vec![], Some(HookValue::I32(0))),
+        ]
+    );
+
+    instance.call("callBack").unwrap();
+    assert!(hook_call_log.hook_calls().is_empty());
+}
+
+#[test]
+fn hook_arguments_are_named() {
+    let hook_call_log = HookCallLog::new();
+    let vm_hooks = TracingVMHooks::new(VMHooksDefault, Box::new(hook_call_log.clone()));
+    vm_hooks.signal_error(1048576, 25);
+    vm_hooks.get_block_hash(7, 64);
+
+    let hook_calls = hook_call_log.hook_calls();
+    assert_eq!(
+        hook_calls[0].arguments,
+        vec![
+            HookArgument::new("message_offset", HookValue::MemPtr(1048576)),
+            HookArgument::new("message_length", HookValue::MemLength(25)),
+        ]
+    );
+    assert_eq!(hook_calls[1].result, Some(HookValue::I32(0)));
+
+    let hook_call = HookCall {
+        duration: Duration::from_micros(3),
+        ..hook_calls[1].clone()
+    };
+    assert_eq!(
+        hook_call.to_string(),
+        "get_block_hash(nonce: 7, result_offset: 64) -> 0 in 3µs"
+    );
+}
@@ -0,0 +1,469 @@
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{One, ToPrimitive, Zero};
+use std::cmp::Ordering;
+
+/// The number of bits of the mantissa of every big float, as for `f64`.
+pub(crate) const BIG_FLOAT_PRECISION: u32 = 53;
+
+/// The encoding version of Go's `big.Float`, in which the node stores big floats in buffers.
+const GOB_VERSION: u8 = 1;
+
+const ZERO_FORM: u8 = 0;
+const FINITE_FORM: u8 = 1;
+const INFINITE_FORM: u8 = 2;
+
+/// The unused low bits of the mantissa word.
+const MANTISSA_PADDING: u32 = 64 - BIG_FLOAT_PRECISION;
+
+/// How a value compares to the exact result of the operation that gave it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Accuracy {
+    Below,
+    Exact,
+    Above,
+}
+
+/// A binary floating point number, rounded like the node rounds big floats.
+///
+/// The mantissa has 53 bits, as for `f64`, and is rounded half to even. The exponent has
+/// the range of an `i32`. There are no infinities nor NaNs: the operations that would give
+/// them fail instead. Zero is signed, as in Go.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct BigFloat {
+    neg: bool,
+
+    /// Zero for zero, otherwise the mantissa, highest bit first, in the upper bits.
+    mant: u64,
+
+    /// The value is `0.mant * 2^exp`.
+    exp: i32,
+    acc: Accuracy,
+}
+
+impl BigFloat {
+    pub(crate) fn zero(neg: bool) -> Self {
+        BigFloat {
+            neg,
+            mant: 0,
+            exp: 0,
+            acc: Accuracy::Exact,
+        }
+    }
+
+    pub(crate) fn from_f64(value: f64) -> Self {
+        if value == 0.0 {
+            return Self::zero(value.is_sign_negative());
+        }
+        let bits = value.to_bits();
+        let biased_exponent = ((bits >> 52) & 0x7ff) as i64;
+        let fraction = bits & ((1 << 52) - 1);
+        let (mantissa, exponent) = if biased_exponent == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1 << 52), biased_exponent - 1075)
+        };
+        round(value < 0.0, BigUint::from(mantissa), exponent, false)
+            .expect("f64 values are in range")
+    }
+
+    pub(crate) fn from_i64(value: i64) -> Self {
+        Self::from_big_int(&BigInt::from(value)).expect("i64 values are in range")
+    }
+
+    pub(crate) fn from_big_int(value: &BigInt) -> Result<Self, String> {
+        if value.is_zero() {
+            return Ok(Self::zero(false));
+        }
+        round(
+            value.sign() == Sign::Minus,
+            value.magnitude().clone(),
+            0,
+            false,
+        )
+    }
+
+    /// `integral_part` plus or minus, following its sign, `fractional_part * 10^exponent`.
+    ///
+    /// Built as the node builds it, from the `f64` values of the parts and of the power of ten.
+    /// Exponents below -322 make the fractional part zero.
+    pub(crate) fn from_parts(
+        integral_part: i32,
+        fractional_part: i32,
+        exponent: i32,
+    ) -> Result<Self, String> {
+        if exponent > 0 {
+            return Err("positive exponent".to_string());
+        }
+        let fractional = if exponent < -322 {
+            Self::zero(false)
+        } else {
+            Self::from_f64(fractional_part as f64).mul(&Self::from_f64(pow10(exponent)))?
+        };
+        let integral = Self::from_f64(integral_part as f64);
+        if integral_part >= 0 {
+            integral.add(&fractional)
+        } else {
+            integral.sub(&fractional)
+        }
+    }
+
+    /// Divides the `f64` values of the numerator and denominator, like the node.
+    pub(crate) fn from_frac(numerator: i64, denominator: i64) -> Result<Self, String> {
+        Self::from_f64(numerator as f64).div(&Self::from_f64(denominator as f64))
+    }
+
+    /// `significand * 10^exponent`, from their `f64` values, like the node.
+    /// Exponents below -322 give zero.
+    pub(crate) fn from_sci(significand: i64, exponent: i64) -> Result<Self, String> {
+        if exponent > 0 {
+            return Err("positive exponent".to_string());
+        }
+        if exponent < -322 {
+            return Ok(Self::zero(false));
+        }
+        Self::from_f64(significand as f64).mul(&Self::from_f64(pow10(exponent as i32)))
+    }
+
+    /// The same value, as it is once stored: the node keeps the accuracy of the values it
+    /// creates, but not of those it copies into an existing big float.
+    pub(crate) fn exact(self) -> Self {
+        BigFloat {
+            acc: Accuracy::Exact,
+            ..self
+        }
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.mant == 0
+    }
+
+    /// Zero for both zeros.
+    pub(crate) fn sign(&self) -> i32 {
+        match (self.is_zero(), self.neg) {
+            (true, _) => 0,
+            (false, true) => -1,
+            (false, false) => 1,
+        }
+    }
+
+    pub(crate) fn neg(&self) -> Self {
+        BigFloat {
+            neg: !self.neg,
+            ..self.exact()
+        }
+    }
+
+    pub(crate) fn abs(&self) -> Self {
+        BigFloat {
+            neg: false,
+            ..self.exact()
+        }
+    }
+
+    pub(crate) fn add(&self, other: &Self) -> Result<Self, String> {
+        if self.is_zero() && other.is_zero() {
+            return Ok(Self::zero(self.neg && other.neg));
+        }
+        if self.is_zero() {
+            return Ok(other.exact());
+        }
+        if other.is_zero() {
+            return Ok(self.exact());
+        }
+
+        let (mut x, mut x_exp) = self.integer_mantissa();
+        let (mut y, mut y_exp) = other.integer_mantissa();
+        // An operand far below the other only matters for the rounding direction,
+        // so it is replaced with a smaller value, which is quicker to align
+        let far_below = BIG_FLOAT_PRECISION as i64 + 8;
+        if y_exp + far_below < x_exp {
+            (y, y_exp) = (BigUint::one(), x_exp - 9);
+        } else if x_exp + far_below < y_exp {
+            (x, x_exp) = (BigUint::one(), y_exp - 9);
+        }
+        let exp = x_exp.min(y_exp);
+        let x = x << (x_exp - exp) as usize;
+        let y = y << (y_exp - exp) as usize;
+
+        if self.neg == other.neg {
+            return round(self.neg, x + y, exp, false);
+        }
+        match x.cmp(&y) {
+            Ordering::Equal => Ok(Self::zero(false)),
+            Ordering::Greater => round(self.neg, x - y, exp, false),
+            Ordering::Less => round(other.neg, y - x, exp, false),
+        }
+    }
+
+    pub(crate) fn sub(&self, other: &Self) -> Result<Self, String> {
+        self.add(&other.neg())
+    }
+
+    pub(crate) fn mul(&self, other: &Self) -> Result<Self, String> {
+        let neg = self.neg != other.neg;
+        if self.is_zero() || other.is_zero() {
+            return Ok(Self::zero(neg));
+        }
+        let (x, x_exp) = self.integer_mantissa();
+        let (y, y_exp) = other.integer_mantissa();
+        round(neg, x * y, x_exp + y_exp, false)
+    }
+
+    pub(crate) fn div(&self, other: &Self) -> Result<Self, String> {
+        if other.is_zero() {
+            return Err("division by zero".to_string());
+        }
+        let neg = self.neg != other.neg;
+        if self.is_zero() {
+            return Ok(Self::zero(neg));
+        }
+        let (x, x_exp) = self.integer_mantissa();
+        let (y, y_exp) = other.integer_mantissa();
+        // Enough quotient bits to round, the remainder tells if the quotient is exact
+        let shift = (BIG_FLOAT_PRECISION as u64 + 3 + y.bits()).saturating_sub(x.bits());
+        let x = x << shift as usize;
+        let quotient = &x / &y;
+        let inexact = !(x - &quotient * &y).is_zero();
+        round(neg, quotient, x_exp - y_exp - shift as i64, inexact)
+    }
+
+    /// Correctly rounded. The square root of -0 is -0.
+    pub(crate) fn sqrt(&self) -> Result<Self, String> {
+        if self.is_zero() {
+            return Ok(self.exact());
+        }
+        if self.neg {
+            return Err("square root of a negative number".to_string());
+        }
+        let (mant, exp) = self.integer_mantissa();
+        // Enough root bits to round, with an even exponent to halve
+        let mut shift = 2 * (BIG_FLOAT_PRECISION as u64 + 3) - mant.bits();
+        if (exp - shift as i64) % 2 != 0 {
+            shift += 1;
+        }
+        let radicand = mant << shift as usize;
+        let root = radicand.sqrt();
+        let inexact = &root * &root != radicand;
+        Ok(round(false, root, (exp - shift as i64) / 2, inexact)?.exact())
+    }
+
+    /// Squares and multiplies, from the lowest bit of `exponent` up, rounding each product.
+    ///
+    /// Powers whose exponent is bound to overflow are rejected before any product, since the
+    /// loop would otherwise square values far out of range.
+    pub(crate) fn pow(&self, exponent: u32) -> Result<Self, String> {
+        if !self.is_zero() && (self.exp as i64 - 1) * exponent as i64 >= i32::MAX as i64 {
+            return Err("big float overflow".to_string());
+        }
+        let mut result = Self::from_i64(1);
+        let mut square = *self;
+        let mut exponent = exponent;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.mul(&square)?;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                square = square.mul(&square)?;
+            }
+        }
+        Ok(result)
+    }
+
+    pub(crate) fn is_int(&self) -> bool {
+        let (_, has_fraction) = self.truncated();
+        !has_fraction
+    }
+
+    pub(crate) fn truncate(&self) -> BigInt {
+        let (magnitude, _) = self.truncated();
+        self.signed(magnitude)
+    }
+
+    pub(crate) fn floor(&self) -> BigInt {
+        let (magnitude, has_fraction) = self.truncated();
+        match has_fraction && self.neg {
+            true => self.signed(magnitude + 1u32),
+            false => self.signed(magnitude),
+        }
+    }
+
+    pub(crate) fn ceil(&self) -> BigInt {
+        let (magnitude, has_fraction) = self.truncated();
+        match has_fraction && !self.neg {
+            true => self.signed(magnitude + 1u32),
+            false => self.signed(magnitude),
+        }
+    }
+
+    /// In Go's `big.Float` gob encoding, like the node: zeros in 6 bytes, other values in 18.
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let form = if self.is_zero() {
+            ZERO_FORM
+        } else {
+            FINITE_FORM
+        };
+        let accuracy: u8 = match self.acc {
+            Accuracy::Below => 0,
+            Accuracy::Exact => 1,
+            Accuracy::Above => 2,
+        };
+        // The rounding mode, in the upper bits, is always "to nearest even", which is zero
+        let mut encoded = vec![GOB_VERSION, accuracy << 3 | form << 1 | self.neg as u8];
+        encoded.extend(BIG_FLOAT_PRECISION.to_be_bytes());
+        if !self.is_zero() {
+            encoded.extend(self.exp.to_be_bytes());
+            encoded.extend(self.mant.to_be_bytes());
+        }
+        encoded
+    }
+
+    /// Only takes the encodings of 53 bit values. The accuracy and rounding mode are ignored.
+    pub(crate) fn decode(encoded: &[u8]) -> Result<Self, String> {
+        if encoded.len() < 6 || encoded[0] != GOB_VERSION {
+            return Err("invalid big float encoding".to_string());
+        }
+        let precision = u32::from_be_bytes(encoded[2..6].try_into().unwrap());
+        if precision != BIG_FLOAT_PRECISION {
+            return Err("big float precision must be 53 bits".to_string());
+        }
+        let neg = encoded[1] & 1 != 0;
+        match (encoded[1] >> 1) & 3 {
+            ZERO_FORM if encoded.len() == 6 => Ok(Self::zero(neg)),
+            FINITE_FORM if encoded.len() == 18 => {
+                let exp = i32::from_be_bytes(encoded[6..10].try_into().unwrap());
+                let mant = u64::from_be_bytes(encoded[10..18].try_into().unwrap());
+                if mant >> 63 == 0 || mant.trailing_zeros() < MANTISSA_PADDING {
+                    return Err("invalid big float mantissa".to_string());
+                }
+                Ok(BigFloat {
+                    neg,
+                    mant,
+                    exp,
+                    acc: Accuracy::Exact,
+                })
+            }
+            INFINITE_FORM => Err("infinite big float".to_string()),
+            _ => Err("invalid big float encoding".to_string()),
+        }
+    }
+
+    /// The mantissa as an integer, and the matching exponent.
+    fn integer_mantissa(&self) -> (BigUint, i64) {
+        (
+            BigUint::from(self.mant >> MANTISSA_PADDING),
+            self.exp as i64 - BIG_FLOAT_PRECISION as i64,
+        )
+    }
+
+    /// The magnitude of the integer part, and whether there is a fractional part.
+    fn truncated(&self) -> (BigUint, bool) {
+        if self.is_zero() {
+            return (BigUint::zero(), false);
+        }
+        let (mant, exp) = self.integer_mantissa();
+        if exp >= 0 {
+            return (mant << exp as usize, false);
+        }
+        let fraction_bits = exp.unsigned_abs();
+        if fraction_bits >= BIG_FLOAT_PRECISION as u64 {
+            return (BigUint::zero(), true);
+        }
+        let integer = &mant >> fraction_bits as usize;
+        let has_fraction = integer.clone() << fraction_bits as usize != mant;
+        (integer, has_fraction)
+    }
+
+    fn signed(&self, magnitude: BigUint) -> BigInt {
+        let sign = if self.neg { Sign::Minus } else { Sign::Plus };
+        BigInt::from_biguint(sign, magnitude)
+    }
+}
+
+impl PartialOrd for BigFloat {
+    /// By value, so both zeros are equal.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let sign = self.sign();
+        if sign != other.sign() {
+            return Some(sign.cmp(&other.sign()));
+        }
+        let magnitude = (self.exp, self.mant).cmp(&(other.exp, other.mant));
+        Some(match sign {
+            0 => Ordering::Equal,
+            1 => magnitude,
+            _ => magnitude.reverse(),
+        })
+    }
+}
+
+/// Rounds `mant * 2^exp` to 53 bits, half to even. `inexact` tells that the exact value is
+/// a little above `mant * 2^exp`, by less than `2^exp`.
+fn round(neg: bool, mant: BigUint, exp: i64, inexact: bool) -> Result<BigFloat, String> {
+    let bits = mant.bits() as i64;
+    let precision = BIG_FLOAT_PRECISION as i64;
+    let (mut rounded, mut exp, inexact, round_up) = if bits > precision {
+        let shift = (bits - precision) as usize;
+        let rounded = &mant >> shift;
+        let remainder = mant - (&rounded << shift);
+        let half = BigUint::one() << (shift - 1);
+        let round_up = match remainder.cmp(&half) {
+            Ordering::Greater => true,
+            Ordering::Equal => inexact || rounded.bit(0),
+            Ordering::Less => false,
+        };
+        let inexact = inexact || !remainder.is_zero();
+        (rounded, exp + shift as i64, inexact, round_up)
+    } else {
+        let shift = (precision - bits) as usize;
+        (mant << shift, exp - shift as i64, inexact, false)
+    };
+    if round_up {
+        rounded += 1u32;
+        if rounded.bits() as i64 > precision {
+            rounded >>= 1;
+            exp += 1;
+        }
+    }
+
+    let acc = match (inexact, round_up != neg) {
+        (false, _) => Accuracy::Exact,
+        (true, true) => Accuracy::Above,
+        (true, false) => Accuracy::Below,
+    };
+    let exp = exp + precision;
+    if exp > i32::MAX as i64 {
+        return Err("big float overflow".to_string());
+    }
+    if exp < i32::MIN as i64 {
+        // Too small, rounds to zero, which is above negative values
+        return Ok(BigFloat {
+            acc: if neg {
+                Accuracy::Above
+            } else {
+                Accuracy::Below
+            },
+            ..BigFloat::zero(neg)
+        });
+    }
+    Ok(BigFloat {
+        neg,
+        mant: rounded.to_u64().unwrap() << MANTISSA_PADDING,
+        exp: exp as i32,
+        acc,
+    })
+}
+
+const POW10: [f64; 32] = [
+    1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15, 1e16,
+    1e17, 1e18, 1e19, 1e20, 1e21, 1e22, 1e23, 1e24, 1e25, 1e26, 1e27, 1e28, 1e29, 1e30, 1e31,
+];
+
+const POW10_NEG_32: [f64; 11] = [
+    1e-0, 1e-32, 1e-64, 1e-96, 1e-128, 1e-160, 1e-192, 1e-224, 1e-256, 1e-288, 1e-320,
+];
+
+/// `10^exponent` for exponents from -323 to 0, computed like Go's `math.Pow10`.
+fn pow10(exponent: i32) -> f64 {
+    let n = exponent.unsigned_abs() as usize;
+    POW10_NEG_32[n / 32] / POW10[n % 32]
+}
@@ -0,0 +1,18 @@
+/// The costs of the bulk memory operators, charged on top of the `OpcodeCost`.
+///
+/// `memory.copy`, `memory.fill` and `memory.init` cost their base cost plus the per byte cost
+/// times their length, which is only known at runtime.
+///
+/// Read directly from the memory of C callers, see `vm_exec_bulk_memory_cost_v1_t` in the C
+/// header, so the fields are never reordered, and new costs go in a new version of the struct.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BulkMemoryCost {
+    pub data_drop: u32,
+    pub memory_copy: u32,
+    pub memory_copy_per_byte: u32,
+    pub memory_fill: u32,
+    pub memory_fill_per_byte: u32,
+    pub memory_init: u32,
+    pub memory_init_per_byte: u32,
+}
@@ -0,0 +1,57 @@
+use crate::BreakpointValue;
+
+/// The reason WebAssembly code trapped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrapCode {
+    /// An `unreachable` instruction was executed.
+    ///
+    /// Breakpoints also stop the execution this way, see `CallOutcome::breakpoint` for the reason.
+    Unreachable,
+    MemoryOutOfBounds,
+    TableOutOfBounds,
+    IntegerOverflow,
+    IntegerDivisionByZero,
+    InvalidConversionToInteger,
+    StackOverflow,
+    IndirectCallToNull,
+    IndirectCallSignatureMismatch,
+    Other,
+}
+
+/// Details about a call that did not complete.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallTrap {
+    /// `None` if the call was stopped by the host rather than by WebAssembly code,
+    /// e.g. by a failing VM hook.
+    pub code: Option<TrapCode>,
+    pub message: String,
+
+    /// The WebAssembly frames that led to the trap, innermost first.
+    pub backtrace: Vec<String>,
+}
+
+/// Everything there is to know about how a call went, gathered in one go.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallOutcome {
+    /// The runtime breakpoint value after the call, `BreakpointValue::None` if runtime
+    /// breakpoints are disabled.
+    pub breakpoint: BreakpointValue,
+
+    /// The points used before and after the call, both zero if metering is disabled.
+    pub points_used_before: u64,
+    pub points_used_after: u64,
+
+    pub trap: Option<CallTrap>,
+}
+
+impl CallOutcome {
+    pub fn is_success(&self) -> bool {
+        self.trap.is_none()
+    }
+
+    /// The points used by the call itself.
+    pub fn points_used(&self) -> u64 {
+        self.points_used_after
+            .saturating_sub(self.points_used_before)
+    }
+}
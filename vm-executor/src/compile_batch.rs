@@ -0,0 +1,11 @@
+use crate::ExecutorError;
+
+/// The outcome of compiling one contract of a batch.
+#[derive(Debug)]
+pub struct CompileBatchResult {
+    /// The id the contract was submitted with.
+    pub id: String,
+
+    /// The cache bytes, as returned by `Instance::cache`, or the reason the contract was rejected.
+    pub result: Result<Vec<u8>, ExecutorError>,
+}
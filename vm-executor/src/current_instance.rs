@@ -0,0 +1,40 @@
+use std::cell::Cell;
+
+use crate::Instance;
+
+thread_local! {
+    static CURRENT_INSTANCE: Cell<Option<*const dyn Instance>> = const { Cell::new(None) };
+}
+
+/// Restores the previously current instance, even if the call panics.
+struct CurrentInstanceGuard {
+    previous: Option<*const dyn Instance>,
+}
+
+impl Drop for CurrentInstanceGuard {
+    fn drop(&mut self) {
+        CURRENT_INSTANCE.with(|current| current.set(self.previous));
+    }
+}
+
+/// Runs `f` with `instance` as the current instance of this thread, see `with_current_instance`.
+///
+/// Executors run their calls this way, so that the hooks implemented in Rust can reach
+/// the memory of the calling instance, like the node does through the VM hooks pointer.
+/// The previously current instance, if any, is current again afterwards, so that instances
+/// called from within hooks do not lose track of their caller.
+pub fn run_in_instance<R>(instance: &(dyn Instance + 'static), f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT_INSTANCE.with(|current| current.replace(Some(instance)));
+    let _guard = CurrentInstanceGuard { previous };
+    f()
+}
+
+/// Runs `f` with the instance being called on this thread, if any.
+///
+/// Returns `None` outside of a call, e.g. when a hook is called directly.
+pub fn with_current_instance<R>(f: impl FnOnce(&dyn Instance) -> R) -> Option<R> {
+    CURRENT_INSTANCE
+        .with(|current| current.get())
+        // Only `run_in_instance` sets the instance, which it borrows for as long as it is current
+        .map(|instance| f(unsafe { &*instance }))
+}
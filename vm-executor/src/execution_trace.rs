@@ -0,0 +1,21 @@
+/// The default for `CompilationOptions::execution_trace_length`.
+pub const DEFAULT_EXECUTION_TRACE_LENGTH: u32 = 16;
+
+/// The largest `CompilationOptions::execution_trace_length`.
+///
+/// Recording a block costs the same whatever the length, but the code injected to record it
+/// grows with the length.
+pub const MAX_EXECUTION_TRACE_LENGTH: u32 = 128;
+
+/// A basic block entered by a call, as recorded by the execution trace.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExecutionTraceEntry {
+    /// The index of the function among the functions defined by the contract, imports excluded.
+    pub function_index: u32,
+
+    /// The index of the first operator of the block among the operators of the function body,
+    /// starting at zero.
+    ///
+    /// Indexes count the operators of the contract as written, not as compiled.
+    pub operator_index: u32,
+}
@@ -1,14 +1,43 @@
-use crate::{CompilationOptions, ExecutorError, Instance, OpcodeCost};
+use crate::{
+    BulkMemoryCost, CompilationOptions, CompileBatchResult, ExecutorError, GasSchedule,
+    ImportIssue, Instance, ModuleCacheStats, ModuleInfo, OpcodeCost, OpcodeTraceSink,
+};
 
 use std::ffi::c_void;
 
-pub trait Executor {
+pub trait Executor: Send + Sync {
     /// Sets the data that can be hold by an instance context.
     fn set_vm_hooks_ptr(&mut self, vm_hooks_ptr: *mut c_void) -> Result<(), ExecutorError>;
 
     /// Sets the opcode costs for the given executor.
+    ///
+    /// They are the costs of `DEFAULT_GAS_SCHEDULE_VERSION`.
     fn set_opcode_cost(&mut self, opcode_cost: &OpcodeCost) -> Result<(), ExecutorError>;
 
+    /// Sets the costs of the bulk memory operators of `DEFAULT_GAS_SCHEDULE_VERSION`.
+    ///
+    /// Until they are set, contracts that use bulk memory operators are rejected.
+    fn set_bulk_memory_cost(
+        &mut self,
+        bulk_memory_cost: &BulkMemoryCost,
+    ) -> Result<(), ExecutorError>;
+
+    /// Adds opcode costs that contracts can be compiled with, by selecting their version in
+    /// `CompilationOptions::gas_schedule_version`.
+    ///
+    /// Registered schedules cannot be replaced, so that contracts of past epochs keep
+    /// being metered the same way.
+    fn register_gas_schedule(&mut self, gas_schedule: GasSchedule) -> Result<(), ExecutorError>;
+
+    /// Returns the version of the registered schedule active at the given epoch, i.e. the one
+    /// with the latest activation epoch up to it, if there is one.
+    fn gas_schedule_version_at_epoch(&self, epoch: u64) -> Option<u32>;
+
+    /// Sets where the contracts compiled with `CompilationOptions::opcode_trace` are traced.
+    ///
+    /// The default is `OpcodeTraceSink::Memory`, files are only written to a path given here.
+    fn set_opcode_trace_sink(&mut self, sink: OpcodeTraceSink) -> Result<(), ExecutorError>;
+
     /// Creates a new VM executor instance.
     fn new_instance(
         &self,
@@ -22,4 +51,39 @@ pub trait Executor {
         cache_bytes: &[u8],
         compilation_options: &CompilationOptions,
     ) -> Result<Box<dyn Instance>, ExecutorError>;
+
+    /// Sets the maximum number of compiled modules kept in memory. Zero disables the cache.
+    fn set_module_cache_capacity(&mut self, capacity: usize) -> Result<(), ExecutorError>;
+
+    /// Returns the counters of the compiled module cache.
+    fn get_module_cache_stats(&self) -> ModuleCacheStats;
+
+    /// Sets the number of threads used by `compile_batch`. Zero uses one thread per CPU.
+    fn set_compile_threads(&mut self, threads: usize) -> Result<(), ExecutorError>;
+
+    /// Compiles several contracts in parallel, and returns their cache bytes in the given order.
+    ///
+    /// A contract that fails to compile does not stop the others, its error is part of its result.
+    /// The compiled modules bypass the module cache, so a large batch does not evict the modules
+    /// in use.
+    fn compile_batch(
+        &self,
+        contracts: &[(&str, &[u8])],
+        compilation_options: &CompilationOptions,
+    ) -> Result<Vec<CompileBatchResult>, ExecutorError>;
+
+    /// Checks the imports of a contract against the VM hooks, without instantiating it.
+    ///
+    /// Returns every import that cannot be satisfied, an empty list means the contract links.
+    fn validate_imports(&self, wasm_bytes: &[u8]) -> Result<Vec<ImportIssue>, ExecutorError>;
+
+    /// Describes a contract without compiling or instantiating it.
+    ///
+    /// The memory of the contract is checked against `max_memory_pages`, which should be the
+    /// `CompilationOptions::max_memory_pages` it is going to be instantiated with.
+    fn inspect_module(
+        &self,
+        wasm_bytes: &[u8],
+        max_memory_pages: u64,
+    ) -> Result<ModuleInfo, ExecutorError>;
 }
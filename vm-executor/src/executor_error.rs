@@ -0,0 +1,258 @@
+use crate::{BreakpointValue, ImportValidationError, ServiceError, ValueType};
+use thiserror::Error;
+
+/// Reasons for rejecting a contract before it is instantiated.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("no memory declared in smart contract")]
+    NoMemory,
+
+    #[error("more than one memory declared in smart contract")]
+    MultipleMemories,
+
+    #[error(
+        "memory size exceeds maximum allowed: declared {declared} pages, maximum is {max} pages"
+    )]
+    MemoryTooLarge { declared: u64, max: u64 },
+
+    #[error("maximum number of locals({max}) exceeded({count})")]
+    TooManyLocals { count: u32, max: u32 },
+
+    /// The operator has no cost in the opcode cost table.
+    #[error("Unsupported operator: {0}")]
+    UnsupportedOperator(String),
+
+    #[error(transparent)]
+    InvalidImports(#[from] ImportValidationError),
+
+    /// The contract exports a name used to give snapshots access to the globals.
+    #[error("export name {0} is reserved for instance snapshots")]
+    ReservedExportName(String),
+}
+
+/// Reasons for rejecting a cached artifact, reported before the module is deserialized.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum CacheArtifactError {
+    #[error("cache artifact is truncated")]
+    Truncated,
+
+    #[error("cache artifact has an invalid header")]
+    BadMagic,
+
+    #[error("unsupported cache artifact format version {0}")]
+    UnsupportedFormatVersion(u16),
+
+    #[error("cache artifact was produced by executor version {found}, expected {expected}")]
+    ExecutorVersionMismatch { expected: String, found: String },
+
+    #[error("unknown middleware settings in cache: {0:#04x}")]
+    UnknownMiddlewareSettings(u8),
+
+    #[error("unknown compiler in cache: {0}")]
+    UnknownCompiler(u8),
+
+    #[error("invalid execution trace length in cache: {0}")]
+    InvalidExecutionTraceLength(u32),
+
+    #[error("cache artifact was compiled with different options")]
+    CompilationOptionsMismatch,
+
+    #[error("cache artifact was compiled with a different opcode cost")]
+    OpcodeCostMismatch,
+
+    #[error(
+        "cache artifact was metered with gas schedule version {cached}, requested {requested}"
+    )]
+    GasScheduleMismatch { cached: u32, requested: u32 },
+
+    #[error("cache artifact checksum mismatch")]
+    ChecksumMismatch,
+
+    #[error("cache artifact could not be deserialized: {0}")]
+    Deserialization(String),
+}
+
+/// Reasons for rejecting an instance snapshot.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum SnapshotError {
+    #[error("snapshot is truncated")]
+    Truncated,
+
+    #[error("snapshot has an invalid header")]
+    BadMagic,
+
+    #[error("unsupported snapshot format version {0}")]
+    UnsupportedFormatVersion(u16),
+
+    #[error("unknown value type in snapshot: {0}")]
+    UnknownValueType(u8),
+
+    #[error("unknown breakpoint value in snapshot: {0}")]
+    UnknownBreakpointValue(u64),
+
+    #[error("snapshot has unexpected bytes after the memory")]
+    TrailingBytes,
+
+    /// Linear memory is made of whole pages.
+    #[error("snapshot memory of {0} bytes is not a whole number of pages")]
+    UnalignedMemory(u64),
+
+    #[error("snapshot was taken from an instance of a different contract")]
+    CodeMismatch,
+
+    /// The same contract compiled with other middlewares has other injected globals.
+    #[error("snapshot globals do not match the instance globals")]
+    GlobalsMismatch,
+
+    /// Linear memory cannot shrink, so the snapshot cannot be restored.
+    #[error("memory grew to {current} bytes since the snapshot of {snapshot} bytes")]
+    MemoryGrown { snapshot: u64, current: u64 },
+
+    #[error("memory is {current} bytes after growing to the snapshot of {snapshot} bytes")]
+    MemoryNotRestored { snapshot: u64, current: u64 },
+}
+
+/// Reasons for rejecting an opcode cost schedule.
+///
+/// Unknown and missing opcodes are reported as parse errors, with the name of the opcode.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ScheduleError {
+    #[error("invalid TOML opcode cost schedule: {0}")]
+    Toml(String),
+
+    #[error("invalid JSON opcode cost schedule: {0}")]
+    Json(String),
+}
+
+/// Reasons for failing to save or load a hook recording.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum HookRecordingError {
+    #[error("hook recording file error: {0}")]
+    Io(String),
+
+    #[error("invalid JSON hook recording: {0}")]
+    Json(String),
+}
+
+/// Reasons for rejecting a gas schedule, or a request for one.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum GasScheduleError {
+    #[error("unknown gas schedule version {0}")]
+    UnknownVersion(u32),
+
+    #[error("gas schedule version {0} is reserved for the default opcode costs")]
+    ReservedVersion(u32),
+
+    /// Registered schedules cannot change, since contracts may already be compiled with them.
+    #[error("gas schedule version {0} is already registered")]
+    DuplicateVersion(u32),
+
+    #[error("gas schedule version {existing} already activates at epoch {epoch}")]
+    DuplicateActivationEpoch { epoch: u64, existing: u32 },
+}
+
+/// The errors returned by executors and instances.
+#[derive(Debug, Error)]
+pub enum ExecutorError {
+    /// The contract bytes could not be parsed.
+    #[error("invalid module: {0}")]
+    InvalidModule(String),
+
+    #[error("compilation failed: {0}")]
+    Compilation(String),
+
+    #[error("unknown compiler backend: {0}")]
+    UnknownCompilerBackend(u8),
+
+    #[error("invalid execution trace length: {0}")]
+    InvalidExecutionTraceLength(u32),
+
+    #[error(transparent)]
+    Validation(#[from] ValidationError),
+
+    /// Linking or instantiating the compiled module failed.
+    #[error("instantiation failed: {0}")]
+    Instantiation(String),
+
+    #[error(transparent)]
+    Cache(#[from] CacheArtifactError),
+
+    #[error(transparent)]
+    Snapshot(#[from] SnapshotError),
+
+    /// The execution was interrupted, either by a breakpoint or by a WebAssembly trap.
+    ///
+    /// The breakpoint is `BreakpointValue::None` for traps, and when runtime breakpoints are disabled.
+    #[error("{message}")]
+    Trap {
+        breakpoint: BreakpointValue,
+        message: String,
+    },
+
+    #[error("function not found: {0}")]
+    MissingExport(String),
+
+    /// The number of arguments does not match the function signature.
+    #[error("function {func_name} expects {expected} arguments, got {found}")]
+    ArgumentCount {
+        func_name: String,
+        expected: usize,
+        found: usize,
+    },
+
+    /// The type of an argument does not match the function signature.
+    #[error("function {func_name} expects argument {index} to be {expected}, got {found}")]
+    ArgumentType {
+        func_name: String,
+        index: usize,
+        expected: ValueType,
+        found: ValueType,
+    },
+
+    /// The function signature uses a type that has no `Value` counterpart.
+    #[error("function {0} has a signature that cannot be called with typed values")]
+    UnsupportedSignature(String),
+
+    #[error("memory access out of bounds: offset {offset}, length {length}")]
+    MemoryOutOfBounds { offset: u64, length: u64 },
+
+    #[error(
+        "memory grow exceeds maximum allowed: requested {requested} pages, maximum is {max} pages"
+    )]
+    MemoryGrowLimit { requested: u64, max: u64 },
+
+    #[error("metering is disabled for this instance")]
+    MeteringDisabled,
+
+    #[error("runtime breakpoints are disabled for this instance")]
+    RuntimeBreakpointsDisabled,
+
+    #[error("gas profiling is disabled for this instance")]
+    GasProfilingDisabled,
+
+    #[error("opcode histogram is disabled for this instance")]
+    OpcodeHistogramDisabled,
+
+    /// The opcode trace could not be written to its sink.
+    #[error("opcode trace failed: {0}")]
+    OpcodeTrace(String),
+
+    #[error("execution trace is disabled for this instance")]
+    ExecutionTraceDisabled,
+
+    #[error("snapshots are disabled for this instance")]
+    SnapshotsDisabled,
+
+    #[error("unknown breakpoint value: {0}")]
+    UnknownBreakpointValue(u64),
+
+    #[error(transparent)]
+    GasSchedule(#[from] GasScheduleError),
+
+    /// Any other failure of the underlying runtime.
+    #[error("{0}")]
+    Runtime(String),
+
+    #[error(transparent)]
+    Service(#[from] ServiceError),
+}
@@ -0,0 +1,14 @@
+/// The gas used by one function of a contract, see `Instance::gas_profile`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GasProfileEntry {
+    /// The index of the function among the functions defined by the contract, imports excluded.
+    pub function_index: u32,
+
+    /// The name of the function in the name section, if the contract has one.
+    pub name: Option<String>,
+
+    /// The points used by the function itself, excluding the functions it calls.
+    pub gas: u64,
+
+    pub call_count: u64,
+}
@@ -0,0 +1,22 @@
+use crate::{BulkMemoryCost, OpcodeCost};
+
+/// The version of the opcode costs set by `Executor::set_opcode_cost`.
+///
+/// It is not tied to an epoch, and cannot be registered as a gas schedule.
+pub const DEFAULT_GAS_SCHEDULE_VERSION: u32 = 0;
+
+/// Opcode costs that apply from an epoch on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GasSchedule {
+    /// A label for logs, e.g. the protocol upgrade that introduced the schedule.
+    pub name: String,
+
+    /// Identifies the schedule, see `CompilationOptions::gas_schedule_version`.
+    pub version: u32,
+
+    pub activation_epoch: u64,
+    pub opcode_cost: OpcodeCost,
+
+    /// `None` rejects the contracts that use bulk memory operators.
+    pub bulk_memory_cost: Option<BulkMemoryCost>,
+}
@@ -0,0 +1,44 @@
+use thiserror::Error;
+
+/// The only import namespace provided to contracts.
+pub const VM_HOOKS_NAMESPACE: &str = "env";
+
+/// A contract import that cannot be satisfied by the VM hooks.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ImportIssue {
+    /// The imported function is not a VM hook.
+    #[error("unknown import `{name}`")]
+    UnknownFunction { name: String },
+
+    /// The imported function is a VM hook, but its signature differs.
+    #[error("import `{name}` has signature {found}, expected {expected}")]
+    SignatureMismatch {
+        name: String,
+        expected: String,
+        found: String,
+    },
+
+    /// The import is not from the VM hooks namespace.
+    #[error("import `{namespace}.{name}` is not from the `{VM_HOOKS_NAMESPACE}` namespace")]
+    UnsupportedNamespace { namespace: String, name: String },
+
+    /// Memories, tables, globals and other non-function imports are never provided.
+    #[error("import `{name}` is a {kind}, only functions can be imported")]
+    UnsupportedKind { name: String, kind: String },
+}
+
+/// Returned when a contract is instantiated with imports that do not match the VM hooks.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[error("invalid imports: {}", format_issues(.issues))]
+pub struct ImportValidationError {
+    pub issues: Vec<ImportIssue>,
+}
+
+/// Joins the issues, so that all of them are reported at once.
+fn format_issues(issues: &[ImportIssue]) -> String {
+    issues
+        .iter()
+        .map(ImportIssue::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
@@ -0,0 +1,1302 @@
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{Signed, ToPrimitive, Zero};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::big_float::BigFloat;
+use crate::vm_hooks_trace::memory_bytes;
+use crate::{with_current_instance, BreakpointValue, MemLength, MemPtr};
+
+/// The length of the account addresses read from contract memory.
+const ADDRESS_LENGTH: MemLength = 32;
+
+// Returned by the storage hooks, with the values used by the node.
+const STORAGE_UNCHANGED: i32 = 0;
+const STORAGE_MODIFIED: i32 = 1;
+const STORAGE_ADDED: i32 = 2;
+const STORAGE_DELETED: i32 = 3;
+
+/// The data of the contract called with `InMemoryVMHooks`: arguments, storage, call value and
+/// balances, and the managed buffers, big ints and big floats created by the contract.
+///
+/// Clones share the same data, so a clone can be kept to set up the call and check its results.
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryContext {
+    state: Arc<Mutex<InMemoryState>>,
+}
+
+impl InMemoryContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_arguments(&self, arguments: Vec<Vec<u8>>) {
+        self.lock().arguments = arguments;
+    }
+
+    /// The values finished by the contract, in order.
+    pub fn return_data(&self) -> Vec<Vec<u8>> {
+        self.lock().return_data.clone()
+    }
+
+    /// Empty if the key is not set, like on the node.
+    pub fn storage(&self, key: &[u8]) -> Vec<u8> {
+        self.lock().storage_load(key)
+    }
+
+    pub fn set_storage(&self, key: &[u8], value: &[u8]) {
+        self.lock().storage_store(key.to_vec(), value.to_vec());
+    }
+
+    /// Sets a storage entry of another account, read by `mbuffer_storage_load_from_address`.
+    pub fn set_account_storage(&self, address: &[u8], key: &[u8], value: &[u8]) {
+        self.lock()
+            .account_storage
+            .insert((address.to_vec(), key.to_vec()), value.to_vec());
+    }
+
+    pub fn set_call_value(&self, value: impl Into<BigUint>) {
+        self.lock().call_value = value.into();
+    }
+
+    /// Adds a KDA transfer to the call, after those already added.
+    pub fn add_kda_call_value(&self, value: impl Into<BigUint>) {
+        self.lock().kda_call_values.push(value.into());
+    }
+
+    pub fn set_balance(&self, address: &[u8], value: impl Into<BigUint>) {
+        self.lock().balances.insert(address.to_vec(), value.into());
+    }
+
+    pub fn set_kda_balance(
+        &self,
+        address: &[u8],
+        token_id: &[u8],
+        nonce: u64,
+        value: impl Into<BigUint>,
+    ) {
+        self.lock()
+            .kda_balances
+            .insert((address.to_vec(), token_id.to_vec(), nonce), value.into());
+    }
+
+    /// Seeds the bytes given by `mbuffer_set_random`, which are the same for the same seed.
+    pub fn set_random_seed(&self, seed: u64) {
+        self.lock().random_state = seed;
+    }
+
+    /// The bytes of a managed buffer, `None` if there is no buffer under the handle.
+    pub fn managed_buffer(&self, handle: i32) -> Option<Vec<u8>> {
+        self.lock().managed_buffers.get(&handle).cloned()
+    }
+
+    /// The reason the first failed hook call failed, e.g. a division by zero.
+    ///
+    /// A failed hook stops the instance with an `ExecutionFailed` breakpoint, if it has
+    /// runtime breakpoints.
+    pub fn error(&self) -> Option<String> {
+        self.lock().error.clone()
+    }
+
+    pub(crate) fn lock(&self) -> MutexGuard<'_, InMemoryState> {
+        self.state.lock().unwrap()
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct InMemoryState {
+    arguments: Vec<Vec<u8>>,
+    return_data: Vec<Vec<u8>>,
+    storage: HashMap<Vec<u8>, Vec<u8>>,
+    account_storage: HashMap<(Vec<u8>, Vec<u8>), Vec<u8>>,
+    call_value: BigUint,
+    kda_call_values: Vec<BigUint>,
+    balances: HashMap<Vec<u8>, BigUint>,
+    kda_balances: HashMap<(Vec<u8>, Vec<u8>, u64), BigUint>,
+    random_state: u64,
+    error: Option<String>,
+    managed_buffers: HashMap<i32, Vec<u8>>,
+    big_ints: HashMap<i32, BigInt>,
+    big_floats: HashMap<i32, BigFloat>,
+}
+
+impl InMemoryState {
+    /// Runs a hook, and returns `failed` if it fails.
+    fn run<R>(&mut self, failed: R, hook: impl FnOnce(&mut Self) -> Result<R, String>) -> R {
+        match hook(self) {
+            Ok(result) => result,
+            Err(error) => {
+                self.error.get_or_insert(error);
+                with_current_instance(|instance| {
+                    // Without runtime breakpoints, the error is only kept in the context
+                    let _ = instance.set_breakpoint_value(BreakpointValue::ExecutionFailed);
+                });
+                failed
+            }
+        }
+    }
+
+    fn argument(&self, id: i32) -> Result<&[u8], String> {
+        usize::try_from(id)
+            .ok()
+            .and_then(|id| self.arguments.get(id))
+            .map(Vec::as_slice)
+            .ok_or_else(|| "argument index out of range".to_string())
+    }
+
+    fn storage_load(&self, key: &[u8]) -> Vec<u8> {
+        self.storage.get(key).cloned().unwrap_or_default()
+    }
+
+    /// An empty value deletes the key.
+    fn storage_store(&mut self, key: Vec<u8>, value: Vec<u8>) -> i32 {
+        if value.is_empty() {
+            return match self.storage.remove(&key) {
+                Some(_) => STORAGE_DELETED,
+                None => STORAGE_UNCHANGED,
+            };
+        }
+        let status = match self.storage.get(&key) {
+            None => STORAGE_ADDED,
+            Some(previous) if *previous == value => STORAGE_UNCHANGED,
+            Some(_) => STORAGE_MODIFIED,
+        };
+        self.storage.insert(key, value);
+        status
+    }
+
+    fn buffer(&self, handle: i32) -> Result<&Vec<u8>, String> {
+        self.managed_buffers
+            .get(&handle)
+            .ok_or_else(|| "no managed buffer under the given handle".to_string())
+    }
+
+    fn big_int(&self, handle: i32) -> Result<&BigInt, String> {
+        self.big_ints
+            .get(&handle)
+            .ok_or_else(|| "no big int under the given handle".to_string())
+    }
+
+    fn set_big_int(&mut self, handle: i32, value: BigInt) {
+        self.big_ints.insert(handle, value);
+    }
+
+    fn big_float(&self, handle: i32) -> Result<&BigFloat, String> {
+        self.big_floats
+            .get(&handle)
+            .ok_or_else(|| "no big float under the given handle".to_string())
+    }
+
+    fn new_big_float(&mut self, value: Result<BigFloat, String>) -> i32 {
+        self.run(-1, |state| {
+            let handle = new_handle(&state.big_floats);
+            state.big_floats.insert(handle, value?);
+            Ok(handle)
+        })
+    }
+
+    /// Results are stored as exact, as when the node copies them into the destination.
+    fn set_big_float(&mut self, handle: i32, value: BigFloat) {
+        self.big_floats.insert(handle, value.exact());
+    }
+
+    fn big_float_unary(
+        &mut self,
+        destination_handle: i32,
+        op_handle: i32,
+        op: impl FnOnce(&BigFloat) -> Result<BigFloat, String>,
+    ) {
+        self.run((), |state| {
+            let result = op(state.big_float(op_handle)?)?;
+            state.set_big_float(destination_handle, result);
+            Ok(())
+        })
+    }
+
+    fn big_float_binary(
+        &mut self,
+        destination_handle: i32,
+        op1_handle: i32,
+        op2_handle: i32,
+        op: impl FnOnce(&BigFloat, &BigFloat) -> Result<BigFloat, String>,
+    ) {
+        self.run((), |state| {
+            let result = op(state.big_float(op1_handle)?, state.big_float(op2_handle)?)?;
+            state.set_big_float(destination_handle, result);
+            Ok(())
+        })
+    }
+
+    fn big_float_to_big_int(
+        &mut self,
+        dest_big_int_handle: i32,
+        op_handle: i32,
+        op: impl FnOnce(&BigFloat) -> BigInt,
+    ) {
+        self.run((), |state| {
+            let result = op(state.big_float(op_handle)?);
+            state.set_big_int(dest_big_int_handle, result);
+            Ok(())
+        })
+    }
+
+    fn big_int_unary(
+        &mut self,
+        destination_handle: i32,
+        op_handle: i32,
+        op: impl FnOnce(&BigInt) -> Result<BigInt, String>,
+    ) {
+        self.run((), |state| {
+            let result = op(state.big_int(op_handle)?)?;
+            state.set_big_int(destination_handle, result);
+            Ok(())
+        })
+    }
+
+    fn big_int_binary(
+        &mut self,
+        destination_handle: i32,
+        op1_handle: i32,
+        op2_handle: i32,
+        op: impl FnOnce(&BigInt, &BigInt) -> Result<BigInt, String>,
+    ) {
+        self.run((), |state| {
+            let result = op(state.big_int(op1_handle)?, state.big_int(op2_handle)?)?;
+            state.set_big_int(destination_handle, result);
+            Ok(())
+        })
+    }
+
+    /// SplitMix64, so that the random bytes only depend on the seed.
+    fn next_random(&mut self) -> u64 {
+        self.random_state = self.random_state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.random_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
+
+// Managed buffers
+impl InMemoryState {
+    pub(crate) fn mbuffer_new(&mut self) -> i32 {
+        let handle = new_handle(&self.managed_buffers);
+        self.managed_buffers.insert(handle, Vec::new());
+        handle
+    }
+
+    pub(crate) fn mbuffer_new_from_bytes(
+        &mut self,
+        data_offset: MemPtr,
+        data_length: MemLength,
+    ) -> i32 {
+        self.run(-1, |state| {
+            let data = load(data_offset, data_length)?;
+            let handle = new_handle(&state.managed_buffers);
+            state.managed_buffers.insert(handle, data);
+            Ok(handle)
+        })
+    }
+
+    pub(crate) fn mbuffer_get_length(&mut self, m_buffer_handle: i32) -> i32 {
+        self.run(-1, |state| Ok(state.buffer(m_buffer_handle)?.len() as i32))
+    }
+
+    pub(crate) fn mbuffer_get_bytes(&mut self, m_buffer_handle: i32, result_offset: MemPtr) -> i32 {
+        self.run(-1, |state| {
+            store(result_offset, state.buffer(m_buffer_handle)?)?;
+            Ok(0)
+        })
+    }
+
+    /// Returns 1, without failing, if the slice does not fit in the buffer.
+    pub(crate) fn mbuffer_get_byte_slice(
+        &mut self,
+        source_handle: i32,
+        starting_position: i32,
+        slice_length: i32,
+        result_offset: MemPtr,
+    ) -> i32 {
+        self.run(-1, |state| {
+            let source = state.buffer(source_handle)?;
+            match byte_slice(source, starting_position, slice_length) {
+                Some(slice) => {
+                    store(result_offset, slice)?;
+                    Ok(0)
+                }
+                None => Ok(1),
+            }
+        })
+    }
+
+    /// Returns 1, without failing, if the slice does not fit in the buffer.
+    pub(crate) fn mbuffer_copy_byte_slice(
+        &mut self,
+        source_handle: i32,
+        starting_position: i32,
+        slice_length: i32,
+        destination_handle: i32,
+    ) -> i32 {
+        self.run(-1, |state| {
+            let source = state.buffer(source_handle)?;
+            match byte_slice(source, starting_position, slice_length) {
+                Some(slice) => {
+                    let slice = slice.to_vec();
+                    state.managed_buffers.insert(destination_handle, slice);
+                    Ok(0)
+                }
+                None => Ok(1),
+            }
+        })
+    }
+
+    pub(crate) fn mbuffer_eq(&mut self, m_buffer_handle1: i32, m_buffer_handle2: i32) -> i32 {
+        self.run(-1, |state| {
+            Ok((state.buffer(m_buffer_handle1)? == state.buffer(m_buffer_handle2)?) as i32)
+        })
+    }
+
+    pub(crate) fn mbuffer_set_bytes(
+        &mut self,
+        m_buffer_handle: i32,
+        data_offset: MemPtr,
+        data_length: MemLength,
+    ) -> i32 {
+        self.run(-1, |state| {
+            let data = load(data_offset, data_length)?;
+            state.managed_buffers.insert(m_buffer_handle, data);
+            Ok(0)
+        })
+    }
+
+    /// Overwrites part of the buffer. Returns 1, without failing, if the bytes do not fit in it.
+    pub(crate) fn mbuffer_set_byte_slice(
+        &mut self,
+        m_buffer_handle: i32,
+        starting_position: i32,
+        data_length: MemLength,
+        data_offset: MemPtr,
+    ) -> i32 {
+        self.run(-1, |state| {
+            state.buffer(m_buffer_handle)?;
+            let data = load(data_offset, data_length)?;
+            let buffer = state.managed_buffers.get_mut(&m_buffer_handle).unwrap();
+            let Some(start) = usize::try_from(starting_position)
+                .ok()
+                .filter(|start| start + data.len() <= buffer.len())
+            else {
+                return Ok(1);
+            };
+            buffer[start..start + data.len()].copy_from_slice(&data);
+            Ok(0)
+        })
+    }
+
+    pub(crate) fn mbuffer_append(&mut self, accumulator_handle: i32, data_handle: i32) -> i32 {
+        self.run(-1, |state| {
+            let data = state.buffer(data_handle)?.clone();
+            state.buffer(accumulator_handle)?;
+            state
+                .managed_buffers
+                .get_mut(&accumulator_handle)
+                .unwrap()
+                .extend(data);
+            Ok(0)
+        })
+    }
+
+    pub(crate) fn mbuffer_append_bytes(
+        &mut self,
+        accumulator_handle: i32,
+        data_offset: MemPtr,
+        data_length: MemLength,
+    ) -> i32 {
+        self.run(-1, |state| {
+            state.buffer(accumulator_handle)?;
+            let data = load(data_offset, data_length)?;
+            state
+                .managed_buffers
+                .get_mut(&accumulator_handle)
+                .unwrap()
+                .extend(data);
+            Ok(0)
+        })
+    }
+
+    pub(crate) fn mbuffer_to_big_int_unsigned(
+        &mut self,
+        m_buffer_handle: i32,
+        big_int_handle: i32,
+    ) -> i32 {
+        self.run(-1, |state| {
+            let value = from_unsigned_bytes(state.buffer(m_buffer_handle)?);
+            state.set_big_int(big_int_handle, value);
+            Ok(0)
+        })
+    }
+
+    pub(crate) fn mbuffer_to_big_int_signed(
+        &mut self,
+        m_buffer_handle: i32,
+        big_int_handle: i32,
+    ) -> i32 {
+        self.run(-1, |state| {
+            let value = from_signed_bytes(state.buffer(m_buffer_handle)?);
+            state.set_big_int(big_int_handle, value);
+            Ok(0)
+        })
+    }
+
+    pub(crate) fn mbuffer_from_big_int_unsigned(
+        &mut self,
+        m_buffer_handle: i32,
+        big_int_handle: i32,
+    ) -> i32 {
+        self.run(-1, |state| {
+            let bytes = unsigned_bytes(state.big_int(big_int_handle)?);
+            state.managed_buffers.insert(m_buffer_handle, bytes);
+            Ok(0)
+        })
+    }
+
+    pub(crate) fn mbuffer_from_big_int_signed(
+        &mut self,
+        m_buffer_handle: i32,
+        big_int_handle: i32,
+    ) -> i32 {
+        self.run(-1, |state| {
+            let bytes = signed_bytes(state.big_int(big_int_handle)?);
+            state.managed_buffers.insert(m_buffer_handle, bytes);
+            Ok(0)
+        })
+    }
+
+    /// Decodes a big float in the encoding of the node, see `mbuffer_from_big_float`.
+    pub(crate) fn mbuffer_to_big_float(
+        &mut self,
+        m_buffer_handle: i32,
+        big_float_handle: i32,
+    ) -> i32 {
+        self.run(-1, |state| {
+            let value = BigFloat::decode(state.buffer(m_buffer_handle)?)?;
+            state.set_big_float(big_float_handle, value);
+            Ok(0)
+        })
+    }
+
+    /// Encodes a big float like the node, in the gob encoding of Go's `big.Float`.
+    pub(crate) fn mbuffer_from_big_float(
+        &mut self,
+        m_buffer_handle: i32,
+        big_float_handle: i32,
+    ) -> i32 {
+        self.run(-1, |state| {
+            let bytes = state.big_float(big_float_handle)?.encode();
+            state.managed_buffers.insert(m_buffer_handle, bytes);
+            Ok(0)
+        })
+    }
+
+    pub(crate) fn mbuffer_storage_store(&mut self, key_handle: i32, source_handle: i32) -> i32 {
+        self.run(-1, |state| {
+            let key = state.buffer(key_handle)?.clone();
+            let value = state.buffer(source_handle)?.clone();
+            state.storage_store(key, value);
+            Ok(0)
+        })
+    }
+
+    pub(crate) fn mbuffer_storage_load(&mut self, key_handle: i32, destination_handle: i32) -> i32 {
+        self.run(-1, |state| {
+            let value = state.storage_load(state.buffer(key_handle)?);
+            state.managed_buffers.insert(destination_handle, value);
+            Ok(0)
+        })
+    }
+
+    pub(crate) fn mbuffer_storage_load_from_address(
+        &mut self,
+        address_handle: i32,
+        key_handle: i32,
+        destination_handle: i32,
+    ) {
+        self.run((), |state| {
+            let account_key = (
+                state.buffer(address_handle)?.clone(),
+                state.buffer(key_handle)?.clone(),
+            );
+            let value = state
+                .account_storage
+                .get(&account_key)
+                .cloned()
+                .unwrap_or_default();
+            state.managed_buffers.insert(destination_handle, value);
+            Ok(())
+        })
+    }
+
+    pub(crate) fn mbuffer_get_argument(&mut self, id: i32, destination_handle: i32) -> i32 {
+        self.run(-1, |state| {
+            let argument = state.argument(id)?.to_vec();
+            state.managed_buffers.insert(destination_handle, argument);
+            Ok(0)
+        })
+    }
+
+    pub(crate) fn mbuffer_finish(&mut self, source_handle: i32) -> i32 {
+        self.run(-1, |state| {
+            let value = state.buffer(source_handle)?.clone();
+            state.return_data.push(value);
+            Ok(0)
+        })
+    }
+
+    pub(crate) fn mbuffer_set_random(&mut self, destination_handle: i32, length: i32) -> i32 {
+        self.run(-1, |state| {
+            if length < 1 {
+                return Err("random length must be positive".to_string());
+            }
+            let mut bytes = Vec::with_capacity(length as usize + 8);
+            while bytes.len() < length as usize {
+                bytes.extend(state.next_random().to_le_bytes());
+            }
+            bytes.truncate(length as usize);
+            state.managed_buffers.insert(destination_handle, bytes);
+            Ok(0)
+        })
+    }
+
+    pub(crate) fn managed_buffer_to_hex(&mut self, source_handle: i32, dest_handle: i32) {
+        self.run((), |state| {
+            let hex: String = state
+                .buffer(source_handle)?
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect();
+            state.managed_buffers.insert(dest_handle, hex.into_bytes());
+            Ok(())
+        })
+    }
+}
+
+// Big ints
+impl InMemoryState {
+    pub(crate) fn big_int_get_unsigned_argument(&mut self, id: i32, destination_handle: i32) {
+        self.run((), |state| {
+            let value = from_unsigned_bytes(state.argument(id)?);
+            state.set_big_int(destination_handle, value);
+            Ok(())
+        })
+    }
+
+    pub(crate) fn big_int_get_signed_argument(&mut self, id: i32, destination_handle: i32) {
+        self.run((), |state| {
+            let value = from_signed_bytes(state.argument(id)?);
+            state.set_big_int(destination_handle, value);
+            Ok(())
+        })
+    }
+
+    pub(crate) fn big_int_storage_store_unsigned(
+        &mut self,
+        key_offset: MemPtr,
+        key_length: MemLength,
+        source_handle: i32,
+    ) -> i32 {
+        self.run(-1, |state| {
+            let key = load(key_offset, key_length)?;
+            let value = unsigned_bytes(state.big_int(source_handle)?);
+            Ok(state.storage_store(key, value))
+        })
+    }
+
+    /// Returns the length of the stored value.
+    pub(crate) fn big_int_storage_load_unsigned(
+        &mut self,
+        key_offset: MemPtr,
+        key_length: MemLength,
+        destination_handle: i32,
+    ) -> i32 {
+        self.run(-1, |state| {
+            let value = state.storage_load(&load(key_offset, key_length)?);
+            state.set_big_int(destination_handle, from_unsigned_bytes(&value));
+            Ok(value.len() as i32)
+        })
+    }
+
+    pub(crate) fn big_int_get_call_value(&mut self, destination_handle: i32) {
+        let value = BigInt::from(self.call_value.clone());
+        self.set_big_int(destination_handle, value);
+    }
+
+    /// The value of the first KDA transfer, zero if there is none.
+    pub(crate) fn big_int_get_kda_call_value(&mut self, destination: i32) {
+        let value = self.kda_call_values.first().cloned().unwrap_or_default();
+        self.set_big_int(destination, BigInt::from(value));
+    }
+
+    pub(crate) fn big_int_get_kda_call_value_by_index(
+        &mut self,
+        destination_handle: i32,
+        index: i32,
+    ) {
+        self.run((), |state| {
+            let value = usize::try_from(index)
+                .ok()
+                .and_then(|index| state.kda_call_values.get(index))
+                .cloned()
+                .ok_or_else(|| "KDA transfer index out of range".to_string())?;
+            state.set_big_int(destination_handle, BigInt::from(value));
+            Ok(())
+        })
+    }
+
+    pub(crate) fn big_int_get_external_balance(&mut self, address_offset: MemPtr, result: i32) {
+        self.run((), |state| {
+            let address = load(address_offset, ADDRESS_LENGTH)?;
+            let balance = state.balances.get(&address).cloned().unwrap_or_default();
+            state.set_big_int(result, BigInt::from(balance));
+            Ok(())
+        })
+    }
+
+    pub(crate) fn big_int_get_kda_external_balance(
+        &mut self,
+        address_offset: MemPtr,
+        token_id_offset: MemPtr,
+        token_id_len: MemLength,
+        nonce: i64,
+        result_handle: i32,
+    ) {
+        self.run((), |state| {
+            let balance_key = (
+                load(address_offset, ADDRESS_LENGTH)?,
+                load(token_id_offset, token_id_len)?,
+                nonce as u64,
+            );
+            let balance = state
+                .kda_balances
+                .get(&balance_key)
+                .cloned()
+                .unwrap_or_default();
+            state.set_big_int(result_handle, BigInt::from(balance));
+            Ok(())
+        })
+    }
+
+    pub(crate) fn big_int_new(&mut self, small_value: i64) -> i32 {
+        let handle = new_handle(&self.big_ints);
+        self.set_big_int(handle, BigInt::from(small_value));
+        handle
+    }
+
+    pub(crate) fn big_int_unsigned_byte_length(&mut self, reference_handle: i32) -> i32 {
+        self.run(-1, |state| {
+            Ok(unsigned_bytes(state.big_int(reference_handle)?).len() as i32)
+        })
+    }
+
+    pub(crate) fn big_int_signed_byte_length(&mut self, reference_handle: i32) -> i32 {
+        self.run(-1, |state| {
+            Ok(signed_bytes(state.big_int(reference_handle)?).len() as i32)
+        })
+    }
+
+    /// Writes the absolute value, and returns its length.
+    pub(crate) fn big_int_get_unsigned_bytes(
+        &mut self,
+        reference_handle: i32,
+        byte_offset: MemPtr,
+    ) -> i32 {
+        self.run(-1, |state| {
+            let bytes = unsigned_bytes(state.big_int(reference_handle)?);
+            store(byte_offset, &bytes)?;
+            Ok(bytes.len() as i32)
+        })
+    }
+
+    /// Writes the value in two's complement, and returns its length.
+    pub(crate) fn big_int_get_signed_bytes(
+        &mut self,
+        reference_handle: i32,
+        byte_offset: MemPtr,
+    ) -> i32 {
+        self.run(-1, |state| {
+            let bytes = signed_bytes(state.big_int(reference_handle)?);
+            store(byte_offset, &bytes)?;
+            Ok(bytes.len() as i32)
+        })
+    }
+
+    pub(crate) fn big_int_set_unsigned_bytes(
+        &mut self,
+        destination_handle: i32,
+        byte_offset: MemPtr,
+        byte_length: MemLength,
+    ) {
+        self.run((), |state| {
+            let value = from_unsigned_bytes(&load(byte_offset, byte_length)?);
+            state.set_big_int(destination_handle, value);
+            Ok(())
+        })
+    }
+
+    pub(crate) fn big_int_set_signed_bytes(
+        &mut self,
+        destination_handle: i32,
+        byte_offset: MemPtr,
+        byte_length: MemLength,
+    ) {
+        self.run((), |state| {
+            let value = from_signed_bytes(&load(byte_offset, byte_length)?);
+            state.set_big_int(destination_handle, value);
+            Ok(())
+        })
+    }
+
+    pub(crate) fn big_int_is_int64(&mut self, destination_handle: i32) -> i32 {
+        self.run(-1, |state| {
+            Ok(state.big_int(destination_handle)?.to_i64().is_some() as i32)
+        })
+    }
+
+    /// Keeps the lowest 64 bits of the absolute value, then the sign, for values that do not fit.
+    pub(crate) fn big_int_get_int64(&mut self, destination_handle: i32) -> i64 {
+        self.run(-1, |state| {
+            let value = state.big_int(destination_handle)?;
+            let low_bits = value.magnitude().iter_u64_digits().next().unwrap_or(0) as i64;
+            Ok(if value.is_negative() {
+                low_bits.wrapping_neg()
+            } else {
+                low_bits
+            })
+        })
+    }
+
+    pub(crate) fn big_int_set_int64(&mut self, destination_handle: i32, value: i64) {
+        self.set_big_int(destination_handle, BigInt::from(value));
+    }
+
+    pub(crate) fn big_int_add(
+        &mut self,
+        destination_handle: i32,
+        op1_handle: i32,
+        op2_handle: i32,
+    ) {
+        self.big_int_binary(destination_handle, op1_handle, op2_handle, |a, b| Ok(a + b))
+    }
+
+    pub(crate) fn big_int_sub(
+        &mut self,
+        destination_handle: i32,
+        op1_handle: i32,
+        op2_handle: i32,
+    ) {
+        self.big_int_binary(destination_handle, op1_handle, op2_handle, |a, b| Ok(a - b))
+    }
+
+    pub(crate) fn big_int_mul(
+        &mut self,
+        destination_handle: i32,
+        op1_handle: i32,
+        op2_handle: i32,
+    ) {
+        self.big_int_binary(destination_handle, op1_handle, op2_handle, |a, b| Ok(a * b))
+    }
+
+    /// Rounds towards zero.
+    pub(crate) fn big_int_tdiv(
+        &mut self,
+        destination_handle: i32,
+        op1_handle: i32,
+        op2_handle: i32,
+    ) {
+        self.big_int_binary(destination_handle, op1_handle, op2_handle, |a, b| {
+            Ok(a / non_zero(b)?)
+        })
+    }
+
+    /// Has the sign of the dividend.
+    pub(crate) fn big_int_tmod(
+        &mut self,
+        destination_handle: i32,
+        op1_handle: i32,
+        op2_handle: i32,
+    ) {
+        self.big_int_binary(destination_handle, op1_handle, op2_handle, |a, b| {
+            Ok(a % non_zero(b)?)
+        })
+    }
+
+    /// The quotient that leaves a non-negative remainder.
+    pub(crate) fn big_int_ediv(
+        &mut self,
+        destination_handle: i32,
+        op1_handle: i32,
+        op2_handle: i32,
+    ) {
+        self.big_int_binary(destination_handle, op1_handle, op2_handle, |a, b| {
+            Ok((a - euclidean_remainder(a, non_zero(b)?)) / b)
+        })
+    }
+
+    /// Never negative, whatever the signs of the operands.
+    pub(crate) fn big_int_emod(
+        &mut self,
+        destination_handle: i32,
+        op1_handle: i32,
+        op2_handle: i32,
+    ) {
+        self.big_int_binary(destination_handle, op1_handle, op2_handle, |a, b| {
+            Ok(euclidean_remainder(a, non_zero(b)?))
+        })
+    }
+
+    /// Rounds down.
+    pub(crate) fn big_int_sqrt(&mut self, destination_handle: i32, op_handle: i32) {
+        self.big_int_unary(destination_handle, op_handle, |a| {
+            Ok(non_negative(a, "square root of a negative number")?.sqrt())
+        })
+    }
+
+    pub(crate) fn big_int_pow(
+        &mut self,
+        destination_handle: i32,
+        op1_handle: i32,
+        op2_handle: i32,
+    ) {
+        self.big_int_binary(destination_handle, op1_handle, op2_handle, |a, b| {
+            let exponent = non_negative(b, "negative exponent")?
+                .to_u32()
+                .ok_or_else(|| "exponent too large".to_string())?;
+            Ok(a.pow(exponent))
+        })
+    }
+
+    /// Rounds down, and returns -1 for zero.
+    pub(crate) fn big_int_log2(&mut self, op1_handle: i32) -> i32 {
+        self.run(-1, |state| {
+            let value = non_negative(state.big_int(op1_handle)?, "logarithm of a negative number")?;
+            Ok(value.bits() as i32 - 1)
+        })
+    }
+
+    pub(crate) fn big_int_abs(&mut self, destination_handle: i32, op_handle: i32) {
+        self.big_int_unary(destination_handle, op_handle, |a| Ok(a.abs()))
+    }
+
+    pub(crate) fn big_int_neg(&mut self, destination_handle: i32, op_handle: i32) {
+        self.big_int_unary(destination_handle, op_handle, |a| Ok(-a))
+    }
+
+    pub(crate) fn big_int_sign(&mut self, op_handle: i32) -> i32 {
+        self.run(-2, |state| Ok(sign(state.big_int(op_handle)?)))
+    }
+
+    pub(crate) fn big_int_cmp(&mut self, op1_handle: i32, op2_handle: i32) -> i32 {
+        self.run(-2, |state| {
+            Ok(state.big_int(op1_handle)?.cmp(state.big_int(op2_handle)?) as i32)
+        })
+    }
+
+    /// The bitwise operations only take non-negative operands, as on the node.
+    pub(crate) fn big_int_not(&mut self, destination_handle: i32, op_handle: i32) {
+        self.big_int_unary(destination_handle, op_handle, |a| {
+            Ok(!non_negative(a, BITWISE_NEGATIVE)?.clone())
+        })
+    }
+
+    pub(crate) fn big_int_and(
+        &mut self,
+        destination_handle: i32,
+        op1_handle: i32,
+        op2_handle: i32,
+    ) {
+        self.big_int_binary(destination_handle, op1_handle, op2_handle, |a, b| {
+            Ok(non_negative(a, BITWISE_NEGATIVE)? & non_negative(b, BITWISE_NEGATIVE)?)
+        })
+    }
+
+    pub(crate) fn big_int_or(&mut self, destination_handle: i32, op1_handle: i32, op2_handle: i32) {
+        self.big_int_binary(destination_handle, op1_handle, op2_handle, |a, b| {
+            Ok(non_negative(a, BITWISE_NEGATIVE)? | non_negative(b, BITWISE_NEGATIVE)?)
+        })
+    }
+
+    pub(crate) fn big_int_xor(
+        &mut self,
+        destination_handle: i32,
+        op1_handle: i32,
+        op2_handle: i32,
+    ) {
+        self.big_int_binary(destination_handle, op1_handle, op2_handle, |a, b| {
+            Ok(non_negative(a, BITWISE_NEGATIVE)? ^ non_negative(b, BITWISE_NEGATIVE)?)
+        })
+    }
+
+    pub(crate) fn big_int_shr(&mut self, destination_handle: i32, op_handle: i32, bits: i32) {
+        self.big_int_unary(destination_handle, op_handle, |a| {
+            Ok(non_negative(a, BITWISE_NEGATIVE)? >> shift_bits(bits)?)
+        })
+    }
+
+    pub(crate) fn big_int_shl(&mut self, destination_handle: i32, op_handle: i32, bits: i32) {
+        self.big_int_unary(destination_handle, op_handle, |a| {
+            Ok(non_negative(a, BITWISE_NEGATIVE)? << shift_bits(bits)?)
+        })
+    }
+
+    pub(crate) fn big_int_finish_unsigned(&mut self, reference_handle: i32) {
+        self.run((), |state| {
+            let bytes = unsigned_bytes(state.big_int(reference_handle)?);
+            state.return_data.push(bytes);
+            Ok(())
+        })
+    }
+
+    pub(crate) fn big_int_finish_signed(&mut self, reference_handle: i32) {
+        self.run((), |state| {
+            let bytes = signed_bytes(state.big_int(reference_handle)?);
+            state.return_data.push(bytes);
+            Ok(())
+        })
+    }
+
+    /// In decimal.
+    pub(crate) fn big_int_to_string(&mut self, big_int_handle: i32, destination_handle: i32) {
+        self.run((), |state| {
+            let decimal = state.big_int(big_int_handle)?.to_string();
+            state
+                .managed_buffers
+                .insert(destination_handle, decimal.into_bytes());
+            Ok(())
+        })
+    }
+}
+
+// Big floats. They are rounded to 53 bits, half to even, see `BigFloat`.
+impl InMemoryState {
+    pub(crate) fn big_float_new_from_parts(
+        &mut self,
+        integral_part: i32,
+        fractional_part: i32,
+        exponent: i32,
+    ) -> i32 {
+        self.new_big_float(BigFloat::from_parts(
+            integral_part,
+            fractional_part,
+            exponent,
+        ))
+    }
+
+    pub(crate) fn big_float_new_from_frac(&mut self, numerator: i64, denominator: i64) -> i32 {
+        self.new_big_float(BigFloat::from_frac(numerator, denominator))
+    }
+
+    pub(crate) fn big_float_new_from_sci(&mut self, significand: i64, exponent: i64) -> i32 {
+        self.new_big_float(BigFloat::from_sci(significand, exponent))
+    }
+
+    pub(crate) fn big_float_add(
+        &mut self,
+        destination_handle: i32,
+        op1_handle: i32,
+        op2_handle: i32,
+    ) {
+        self.big_float_binary(destination_handle, op1_handle, op2_handle, BigFloat::add)
+    }
+
+    pub(crate) fn big_float_sub(
+        &mut self,
+        destination_handle: i32,
+        op1_handle: i32,
+        op2_handle: i32,
+    ) {
+        self.big_float_binary(destination_handle, op1_handle, op2_handle, BigFloat::sub)
+    }
+
+    pub(crate) fn big_float_mul(
+        &mut self,
+        destination_handle: i32,
+        op1_handle: i32,
+        op2_handle: i32,
+    ) {
+        self.big_float_binary(destination_handle, op1_handle, op2_handle, BigFloat::mul)
+    }
+
+    pub(crate) fn big_float_div(
+        &mut self,
+        destination_handle: i32,
+        op1_handle: i32,
+        op2_handle: i32,
+    ) {
+        self.big_float_binary(destination_handle, op1_handle, op2_handle, BigFloat::div)
+    }
+
+    pub(crate) fn big_float_neg(&mut self, destination_handle: i32, op_handle: i32) {
+        self.big_float_unary(destination_handle, op_handle, |a| Ok(a.neg()))
+    }
+
+    pub(crate) fn big_float_clone(&mut self, destination_handle: i32, op_handle: i32) {
+        self.big_float_unary(destination_handle, op_handle, |a| Ok(*a))
+    }
+
+    /// Both zeros are equal.
+    pub(crate) fn big_float_cmp(&mut self, op1_handle: i32, op2_handle: i32) -> i32 {
+        self.run(-2, |state| {
+            let ordering = state
+                .big_float(op1_handle)?
+                .partial_cmp(state.big_float(op2_handle)?);
+            Ok(ordering.unwrap() as i32)
+        })
+    }
+
+    pub(crate) fn big_float_abs(&mut self, destination_handle: i32, op_handle: i32) {
+        self.big_float_unary(destination_handle, op_handle, |a| Ok(a.abs()))
+    }
+
+    pub(crate) fn big_float_sign(&mut self, op_handle: i32) -> i32 {
+        self.run(-2, |state| Ok(state.big_float(op_handle)?.sign()))
+    }
+
+    pub(crate) fn big_float_sqrt(&mut self, destination_handle: i32, op_handle: i32) {
+        self.big_float_unary(destination_handle, op_handle, BigFloat::sqrt)
+    }
+
+    pub(crate) fn big_float_pow(&mut self, destination_handle: i32, op_handle: i32, exponent: i32) {
+        self.big_float_unary(destination_handle, op_handle, |a| {
+            let exponent = u32::try_from(exponent).map_err(|_| "negative exponent".to_string())?;
+            a.pow(exponent)
+        })
+    }
+
+    pub(crate) fn big_float_floor(&mut self, dest_big_int_handle: i32, op_handle: i32) {
+        self.big_float_to_big_int(dest_big_int_handle, op_handle, BigFloat::floor)
+    }
+
+    pub(crate) fn big_float_ceil(&mut self, dest_big_int_handle: i32, op_handle: i32) {
+        self.big_float_to_big_int(dest_big_int_handle, op_handle, BigFloat::ceil)
+    }
+
+    pub(crate) fn big_float_truncate(&mut self, dest_big_int_handle: i32, op_handle: i32) {
+        self.big_float_to_big_int(dest_big_int_handle, op_handle, BigFloat::truncate)
+    }
+
+    /// Rounded, like the node, and unlike the other results, kept with its accuracy.
+    pub(crate) fn big_float_set_int64(&mut self, destination_handle: i32, value: i64) {
+        self.big_floats
+            .insert(destination_handle, BigFloat::from_i64(value));
+    }
+
+    pub(crate) fn big_float_is_int(&mut self, op_handle: i32) -> i32 {
+        self.run(-1, |state| Ok(state.big_float(op_handle)?.is_int() as i32))
+    }
+
+    /// Rounded, like the node, and unlike the other results, kept with its accuracy.
+    pub(crate) fn big_float_set_big_int(&mut self, destination_handle: i32, big_int_handle: i32) {
+        self.run((), |state| {
+            let value = BigFloat::from_big_int(state.big_int(big_int_handle)?)?;
+            state.big_floats.insert(destination_handle, value);
+            Ok(())
+        })
+    }
+
+    pub(crate) fn big_float_get_const_pi(&mut self, destination_handle: i32) {
+        self.set_big_float(destination_handle, BigFloat::from_f64(std::f64::consts::PI));
+    }
+
+    pub(crate) fn big_float_get_const_e(&mut self, destination_handle: i32) {
+        self.set_big_float(destination_handle, BigFloat::from_f64(std::f64::consts::E));
+    }
+}
+
+// Small ints. The int64 hooks are the older names of the signed small int hooks.
+impl InMemoryState {
+    pub(crate) fn small_int_get_unsigned_argument(&mut self, id: i32) -> i64 {
+        self.run(0, |state| {
+            Ok(to_u64(&from_unsigned_bytes(state.argument(id)?))? as i64)
+        })
+    }
+
+    pub(crate) fn small_int_get_signed_argument(&mut self, id: i32) -> i64 {
+        self.run(0, |state| to_i64(&from_signed_bytes(state.argument(id)?)))
+    }
+
+    /// The value is read as a `u64`.
+    pub(crate) fn small_int_finish_unsigned(&mut self, value: i64) {
+        let bytes = unsigned_bytes(&BigInt::from(value as u64));
+        self.return_data.push(bytes);
+    }
+
+    pub(crate) fn small_int_finish_signed(&mut self, value: i64) {
+        let bytes = signed_bytes(&BigInt::from(value));
+        self.return_data.push(bytes);
+    }
+
+    pub(crate) fn small_int_storage_store_unsigned(
+        &mut self,
+        key_offset: MemPtr,
+        key_length: MemLength,
+        value: i64,
+    ) -> i32 {
+        self.run(-1, |state| {
+            let key = load(key_offset, key_length)?;
+            Ok(state.storage_store(key, unsigned_bytes(&BigInt::from(value as u64))))
+        })
+    }
+
+    pub(crate) fn small_int_storage_store_signed(
+        &mut self,
+        key_offset: MemPtr,
+        key_length: MemLength,
+        value: i64,
+    ) -> i32 {
+        self.run(-1, |state| {
+            let key = load(key_offset, key_length)?;
+            Ok(state.storage_store(key, signed_bytes(&BigInt::from(value))))
+        })
+    }
+
+    pub(crate) fn small_int_storage_load_unsigned(
+        &mut self,
+        key_offset: MemPtr,
+        key_length: MemLength,
+    ) -> i64 {
+        self.run(0, |state| {
+            let value = state.storage_load(&load(key_offset, key_length)?);
+            Ok(to_u64(&from_unsigned_bytes(&value))? as i64)
+        })
+    }
+
+    pub(crate) fn small_int_storage_load_signed(
+        &mut self,
+        key_offset: MemPtr,
+        key_length: MemLength,
+    ) -> i64 {
+        self.run(0, |state| {
+            let value = state.storage_load(&load(key_offset, key_length)?);
+            to_i64(&from_signed_bytes(&value))
+        })
+    }
+
+    pub(crate) fn int64get_argument(&mut self, id: i32) -> i64 {
+        self.small_int_get_signed_argument(id)
+    }
+
+    pub(crate) fn int64finish(&mut self, value: i64) {
+        self.small_int_finish_signed(value)
+    }
+
+    pub(crate) fn int64storage_store(
+        &mut self,
+        key_offset: MemPtr,
+        key_length: MemLength,
+        value: i64,
+    ) -> i32 {
+        self.small_int_storage_store_signed(key_offset, key_length, value)
+    }
+
+    pub(crate) fn int64storage_load(&mut self, key_offset: MemPtr, key_length: MemLength) -> i64 {
+        self.small_int_storage_load_signed(key_offset, key_length)
+    }
+}
+
+const BITWISE_NEGATIVE: &str = "bitwise operation on a negative number";
+
+/// The first handle not in use, counting from the number of handles, like the node.
+fn new_handle<T>(values: &HashMap<i32, T>) -> i32 {
+    let mut handle = values.len() as i32;
+    while values.contains_key(&handle) {
+        handle += 1;
+    }
+    handle
+}
+
+fn load(offset: MemPtr, length: MemLength) -> Result<Vec<u8>, String> {
+    with_current_instance(|instance| memory_bytes(instance, offset, length))
+        .ok_or_else(|| "no instance is being called".to_string())?
+        .map_err(|err| err.to_string())
+}
+
+fn store(offset: MemPtr, bytes: &[u8]) -> Result<(), String> {
+    with_current_instance(|instance| instance.memory_store(offset, bytes))
+        .ok_or_else(|| "no instance is being called".to_string())?
+        .map_err(|err| err.to_string())
+}
+
+fn byte_slice(bytes: &[u8], starting_position: i32, slice_length: i32) -> Option<&[u8]> {
+    let start = usize::try_from(starting_position).ok()?;
+    let length = usize::try_from(slice_length).ok()?;
+    bytes.get(start..start.checked_add(length)?)
+}
+
+/// The absolute value, big endian, with no leading zeros. Zero has no bytes.
+fn unsigned_bytes(value: &BigInt) -> Vec<u8> {
+    if value.is_zero() {
+        return Vec::new();
+    }
+    value.magnitude().to_bytes_be()
+}
+
+/// Two's complement, big endian, in as few bytes as the sign allows. Zero has no bytes.
+fn signed_bytes(value: &BigInt) -> Vec<u8> {
+    if value.is_zero() {
+        return Vec::new();
+    }
+    value.to_signed_bytes_be()
+}
+
+fn from_unsigned_bytes(bytes: &[u8]) -> BigInt {
+    BigInt::from_bytes_be(Sign::Plus, bytes)
+}
+
+fn from_signed_bytes(bytes: &[u8]) -> BigInt {
+    BigInt::from_signed_bytes_be(bytes)
+}
+
+fn to_u64(value: &BigInt) -> Result<u64, String> {
+    value
+        .to_u64()
+        .ok_or_else(|| "value out of range".to_string())
+}
+
+fn to_i64(value: &BigInt) -> Result<i64, String> {
+    value
+        .to_i64()
+        .ok_or_else(|| "value out of range".to_string())
+}
+
+fn sign(value: &BigInt) -> i32 {
+    match value.sign() {
+        Sign::Minus => -1,
+        Sign::NoSign => 0,
+        Sign::Plus => 1,
+    }
+}
+
+fn non_zero(value: &BigInt) -> Result<&BigInt, String> {
+    if value.is_zero() {
+        return Err("division by zero".to_string());
+    }
+    Ok(value)
+}
+
+fn non_negative<'a>(value: &'a BigInt, error: &str) -> Result<&'a BigInt, String> {
+    if value.is_negative() {
+        return Err(error.to_string());
+    }
+    Ok(value)
+}
+
+fn shift_bits(bits: i32) -> Result<usize, String> {
+    usize::try_from(bits).map_err(|_| "negative shift".to_string())
+}
+
+fn euclidean_remainder(a: &BigInt, b: &BigInt) -> BigInt {
+    let remainder = a % b;
+    if remainder.is_negative() {
+        remainder + b.abs()
+    } else {
+        remainder
+    }
+}
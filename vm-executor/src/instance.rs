@@ -1,13 +1,107 @@
-use crate::{BreakpointValue, ExecutorError};
+use crate::{
+    BreakpointValue, CallOutcome, ExecutionTraceEntry, ExecutorError, GasProfileEntry,
+    InstanceSnapshot, OpcodeHistogram, OpcodeTraceEntry, Value, MAX_EXECUTION_TRACE_LENGTH,
+};
+
+/// The compiler used to translate contract code into machine code.
+///
+/// Singlepass compiles fast and suits contracts that are rarely called, while Cranelift
+/// spends more time compiling and produces faster code for heavily used contracts.
+#[repr(u8)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompilerBackend {
+    #[default]
+    Singlepass = 0,
+    Cranelift = 1,
+}
+
+impl TryFrom<u8> for CompilerBackend {
+    type Error = ExecutorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(CompilerBackend::Singlepass),
+            1 => Ok(CompilerBackend::Cranelift),
+            _ => Err(ExecutorError::UnknownCompilerBackend(value)),
+        }
+    }
+}
+
+/// The default for `CompilationOptions::max_memory_pages`.
+pub const DEFAULT_MAX_MEMORY_PAGES: u64 = 20;
 
+/// The options of a contract compilation.
+///
+/// C callers lay them out as `vm_exec_compilation_options_t`, which the C API checks and
+/// converts.
 pub struct CompilationOptions {
     pub gas_limit: u64,
     pub unmetered_locals: usize,
     pub max_memory_grow: usize,
     pub max_memory_grow_delta: usize,
+
+    /// Writes the operators of the contract, as compiled, to the sink set by
+    /// `Executor::set_opcode_trace_sink`.
     pub opcode_trace: bool,
     pub metering: bool,
     pub runtime_breakpoints: bool,
+
+    /// A `CompilerBackend`, kept as a byte since C callers may pass any value.
+    /// See `CompilationOptions::compiler_backend`.
+    pub compiler: u8,
+
+    /// The largest memory a contract may declare, and grow to, in 64KiB pages.
+    pub max_memory_pages: u64,
+
+    /// The gas schedule the contract is metered with, see `Executor::register_gas_schedule`.
+    pub gas_schedule_version: u32,
+
+    /// Counts the gas used and the calls of each contract function, see `Instance::gas_profile`.
+    ///
+    /// The gas is counted by the metering middleware, so this has no effect without metering.
+    pub gas_profiling: bool,
+
+    /// Counts the executed operators by class, see `Instance::opcode_histogram`.
+    ///
+    /// The operators are counted by the metering middleware, so this has no effect without
+    /// metering.
+    pub opcode_histogram: bool,
+
+    /// Keeps the last basic blocks entered by each call, see `Instance::last_execution_trace`.
+    ///
+    /// The blocks are recorded by the metering middleware, so this has no effect without metering.
+    pub execution_trace: bool,
+
+    /// Gives instances access to their mutable globals, see `Instance::snapshot`.
+    ///
+    /// Exports the globals under reserved names, so contracts that export a name starting with
+    /// `snapshot_global_` are rejected.
+    pub snapshots: bool,
+
+    /// The number of basic blocks kept by the execution trace, from 1 to
+    /// `MAX_EXECUTION_TRACE_LENGTH`. Ignored without `execution_trace`.
+    pub execution_trace_length: u32,
+}
+
+impl CompilationOptions {
+    /// The compiler to use, an error if the `compiler` byte is not a `CompilerBackend`.
+    pub fn compiler_backend(&self) -> Result<CompilerBackend, ExecutorError> {
+        CompilerBackend::try_from(self.compiler)
+    }
+
+    /// The number of blocks kept by the execution trace, zero without `execution_trace`, and
+    /// an error if `execution_trace_length` is out of range.
+    pub fn checked_execution_trace_length(&self) -> Result<u32, ExecutorError> {
+        if !self.execution_trace {
+            return Ok(0);
+        }
+        if !(1..=MAX_EXECUTION_TRACE_LENGTH).contains(&self.execution_trace_length) {
+            return Err(ExecutorError::InvalidExecutionTraceLength(
+                self.execution_trace_length,
+            ));
+        }
+        Ok(self.execution_trace_length)
+    }
 }
 
 /// The argument type for dealing with executor memory pointers.
@@ -16,9 +110,22 @@ pub type MemPtr = isize;
 /// The argument type for dealing with lengths of slices of the executor memory.
 pub type MemLength = isize;
 
-pub trait Instance {
+pub trait Instance: Send + Sync {
     /// Calls an exported function of a WebAssembly instance by `name`.
-    fn call(&self, func_name: &str) -> Result<(), String>;
+    fn call(&self, func_name: &str) -> Result<(), ExecutorError>;
+
+    /// Calls an exported function like `call`, and reports the breakpoint, points used and trap
+    /// details of the call.
+    ///
+    /// A trap is part of the outcome, errors are only returned if the call could not be made.
+    fn call_with_report(&self, func_name: &str) -> Result<CallOutcome, ExecutorError>;
+
+    /// Calls an exported function with arguments, and returns its results.
+    ///
+    /// Unlike `call`, the function is not required to be a nullary SC endpoint, which is useful
+    /// for calling helper functions from tests and tooling. Metering and breakpoints apply as
+    /// usual.
+    fn call_typed(&self, func_name: &str, args: &[Value]) -> Result<Vec<Value>, ExecutorError>;
 
     /// Checks that all public module functions (SC endpoints) have no arguments or results.
     fn check_signatures(&self) -> bool;
@@ -30,19 +137,21 @@ pub trait Instance {
     fn get_exported_function_names(&self) -> Vec<String>;
 
     /// Sets the number of points(gas) limit for the given instance.
-    fn set_points_limit(&self, limit: u64) -> Result<(), String>;
+    fn set_points_limit(&self, limit: u64) -> Result<(), ExecutorError>;
 
     /// Sets the number of points(gas) for the given instance.
-    fn set_points_used(&self, points: u64) -> Result<(), String>;
+    fn set_points_used(&self, points: u64) -> Result<(), ExecutorError>;
 
     /// Returns the number of points(gas) used by the given instance.
-    fn get_points_used(&self) -> Result<u64, String>;
+    fn get_points_used(&self) -> Result<u64, ExecutorError>;
 
     /// Gets the size in bytes of the memory data.
-    fn memory_length(&self) -> Result<u64, String>;
+    fn memory_length(&self) -> Result<u64, ExecutorError>;
 
     /// Gets a pointer to the beginning of the contiguous memory data bytes.
-    fn memory_ptr(&self) -> Result<*mut u8, String>;
+    ///
+    /// Writes through the pointer must be reported with `mark_memory_written`.
+    fn memory_ptr(&self) -> Result<*mut u8, ExecutorError>;
 
     /// Loads data from executor memory.
     fn memory_load(&self, mem_ptr: MemPtr, mem_length: MemLength) -> Result<&[u8], ExecutorError>;
@@ -50,18 +159,79 @@ pub trait Instance {
     /// Loads data from executor memory.
     fn memory_store(&self, mem_ptr: MemPtr, data: &[u8]) -> Result<(), ExecutorError>;
 
+    /// Records that the host wrote to memory through `memory_ptr`, so that
+    /// `reset_changed_pages` restores the written bytes.
+    ///
+    /// The writes of the contract and of `memory_store` are recorded already.
+    fn mark_memory_written(
+        &self,
+        mem_ptr: MemPtr,
+        mem_length: MemLength,
+    ) -> Result<(), ExecutorError>;
+
     /// Grows a memory by the given number of pages (of 65Kb each).
     fn memory_grow(&self, by_num_pages: u32) -> Result<u32, ExecutorError>;
 
     /// Sets the runtime breakpoint value for the given instance.
-    fn set_breakpoint_value(&self, value: BreakpointValue) -> Result<(), String>;
+    fn set_breakpoint_value(&self, value: BreakpointValue) -> Result<(), ExecutorError>;
 
     /// Returns the runtime breakpoint value from the given instance.
-    fn get_breakpoint_value(&self) -> Result<BreakpointValue, String>;
+    fn get_breakpoint_value(&self) -> Result<BreakpointValue, ExecutorError>;
 
     /// Resets an instance, cleaning memories and globals.
-    fn reset(&self) -> Result<(), String>;
+    ///
+    /// This includes the points used, the breakpoint value and the memory grow count.
+    fn reset(&self) -> Result<(), ExecutorError>;
+
+    /// Like `reset`, but only copies back the memory pages written since instantiation.
+    ///
+    /// The range of memory written by the contract and the host is recorded as they write, so
+    /// the pages outside of it are not read. Returns the number of restored pages. If the
+    /// memory grew, or some mutable global is not numeric, it falls back to `reset`, and all
+    /// pages count as restored.
+    fn reset_changed_pages(&self) -> Result<usize, ExecutorError>;
+
+    /// Returns the gas used and the number of calls of each function defined by the contract,
+    /// in the order of their indexes.
+    ///
+    /// The counters add up over calls, like the points used, until the instance is reset.
+    fn gas_profile(&self) -> Result<Vec<GasProfileEntry>, ExecutorError>;
+
+    /// Returns the number of operators executed by the instance, by class.
+    ///
+    /// Operators are counted a whole basic block at a time, when the block ends, like the gas.
+    /// The counters add up over calls until they are reset, or the instance is.
+    fn opcode_histogram(&self) -> Result<OpcodeHistogram, ExecutorError>;
+
+    /// Sets all the counters of the opcode histogram back to zero.
+    fn reset_opcode_histogram(&self) -> Result<(), ExecutorError>;
+
+    /// The operators of the contract as compiled, if it was compiled with `opcode_trace`
+    /// and the trace sink is `OpcodeTraceSink::Memory`, empty otherwise.
+    fn opcode_trace(&self) -> &[OpcodeTraceEntry];
+
+    /// The last basic blocks entered by the last call, oldest first, if that call trapped
+    /// or stopped on a breakpoint. `None` if it completed, or if there was no call yet.
+    ///
+    /// At most `CompilationOptions::execution_trace_length` blocks are kept, the block that
+    /// trapped being the last.
+    fn last_execution_trace(&self) -> Result<Option<Vec<ExecutionTraceEntry>>, ExecutorError>;
 
     /// Caches an instance.
-    fn cache(&self) -> Result<Vec<u8>, String>;
+    fn cache(&self) -> Result<Vec<u8>, ExecutorError>;
+
+    /// The version of the gas schedule the instance was compiled with.
+    fn gas_schedule_version(&self) -> u32;
+
+    /// Captures the memory, the mutable globals and the breakpoint value of an instance.
+    ///
+    /// The instance must be compiled with `CompilationOptions::snapshots`.
+    fn snapshot(&self) -> Result<InstanceSnapshot, ExecutorError>;
+
+    /// Returns an instance to the state captured by `snapshot`.
+    ///
+    /// The instance must be compiled with `CompilationOptions::snapshots`.
+    /// The snapshot must come from an instance of the same contract, compiled with the same
+    /// middlewares. Memory cannot shrink, so restoring fails if the memory grew past the snapshot.
+    fn restore(&self, snapshot: &InstanceSnapshot) -> Result<(), ExecutorError>;
 }
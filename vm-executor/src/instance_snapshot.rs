@@ -0,0 +1,128 @@
+use crate::{BreakpointValue, SnapshotError, Value};
+
+const SNAPSHOT_MAGIC: &[u8; 4] = b"KVMS";
+const SNAPSHOT_FORMAT_VERSION: u16 = 1;
+
+/// The size of a linear memory page, snapshots hold whole pages.
+const WASM_PAGE_SIZE: u64 = 65536;
+
+const VALUE_TYPE_I32: u8 = 0;
+const VALUE_TYPE_I64: u8 = 1;
+const VALUE_TYPE_F32: u8 = 2;
+const VALUE_TYPE_F64: u8 = 3;
+
+/// The state of an instance at some point of its execution, see `Instance::snapshot`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstanceSnapshot {
+    /// Identifies the contract code of the instance the snapshot was taken from.
+    pub code_hash: [u8; 32],
+
+    /// The linear memory contents, its length is the memory size.
+    pub memory: Vec<u8>,
+
+    /// The mutable globals, both declared by the contract and injected by the middlewares,
+    /// in the order of their indexes.
+    pub globals: Vec<Value>,
+
+    /// The runtime breakpoint value, `BreakpointValue::None` if runtime breakpoints are disabled.
+    pub breakpoint_value: BreakpointValue,
+}
+
+impl InstanceSnapshot {
+    /// Encodes the snapshot, so that it can be stored and restored later.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.memory.len() + self.globals.len() * 9 + 64);
+        bytes.extend_from_slice(SNAPSHOT_MAGIC);
+        bytes.extend_from_slice(&SNAPSHOT_FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&self.code_hash);
+        bytes.extend_from_slice(&self.breakpoint_value.as_u64().to_le_bytes());
+        bytes.extend_from_slice(&(self.globals.len() as u32).to_le_bytes());
+        for global in &self.globals {
+            let (value_type, bits) = match *global {
+                Value::I32(value) => (VALUE_TYPE_I32, value as u32 as u64),
+                Value::I64(value) => (VALUE_TYPE_I64, value as u64),
+                Value::F32(value) => (VALUE_TYPE_F32, value.to_bits() as u64),
+                Value::F64(value) => (VALUE_TYPE_F64, value.to_bits()),
+            };
+            bytes.push(value_type);
+            bytes.extend_from_slice(&bits.to_le_bytes());
+        }
+        bytes.extend_from_slice(&(self.memory.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.memory);
+        bytes
+    }
+
+    /// Decodes a snapshot encoded by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let mut reader = SnapshotReader { bytes, pos: 0 };
+
+        if reader.take(SNAPSHOT_MAGIC.len())? != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::BadMagic);
+        }
+
+        let format_version = u16::from_le_bytes(reader.take(2)?.try_into().unwrap());
+        if format_version != SNAPSHOT_FORMAT_VERSION {
+            return Err(SnapshotError::UnsupportedFormatVersion(format_version));
+        }
+
+        let code_hash = reader.take(32)?.try_into().unwrap();
+        let breakpoint_value = reader.read_u64()?;
+        let breakpoint_value = BreakpointValue::try_from(breakpoint_value)
+            .map_err(|_| SnapshotError::UnknownBreakpointValue(breakpoint_value))?;
+
+        let globals_len = u32::from_le_bytes(reader.take(4)?.try_into().unwrap());
+        let mut globals = Vec::new();
+        for _ in 0..globals_len {
+            let value_type = reader.take(1)?[0];
+            let bits = reader.read_u64()?;
+            globals.push(match value_type {
+                VALUE_TYPE_I32 => Value::I32(bits as u32 as i32),
+                VALUE_TYPE_I64 => Value::I64(bits as i64),
+                VALUE_TYPE_F32 => Value::F32(f32::from_bits(bits as u32)),
+                VALUE_TYPE_F64 => Value::F64(f64::from_bits(bits)),
+                unknown => return Err(SnapshotError::UnknownValueType(unknown)),
+            });
+        }
+
+        let memory_len = reader.read_u64()?;
+        if !memory_len.is_multiple_of(WASM_PAGE_SIZE) {
+            return Err(SnapshotError::UnalignedMemory(memory_len));
+        }
+        let memory = usize::try_from(memory_len)
+            .map_err(|_| SnapshotError::Truncated)
+            .and_then(|memory_len| reader.take(memory_len))?
+            .to_vec();
+        if reader.pos != bytes.len() {
+            return Err(SnapshotError::TrailingBytes);
+        }
+
+        Ok(InstanceSnapshot {
+            code_hash,
+            memory,
+            globals,
+            breakpoint_value,
+        })
+    }
+}
+
+struct SnapshotReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> SnapshotReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(SnapshotError::Truncated)?;
+        let taken = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(taken)
+    }
+
+    fn read_u64(&mut self) -> Result<u64, SnapshotError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
@@ -1,15 +1,59 @@
+mod big_float;
 mod breakpoint_value;
+mod bulk_memory_cost;
+mod call_outcome;
+mod compile_batch;
+mod current_instance;
+mod execution_trace;
 mod executor;
+mod executor_error;
+mod gas_profile;
+mod gas_schedule;
+mod import_issue;
+mod in_memory_context;
 mod instance;
+mod instance_snapshot;
+mod module_cache_stats;
+mod module_info;
 mod opcode_cost;
+mod opcode_cost_schedule;
+mod opcode_cost_traits;
+mod opcode_histogram;
+mod opcode_trace;
 mod service_error;
 mod service_trait;
+mod value;
 mod vm_hooks;
+mod vm_hooks_in_memory;
+mod vm_hooks_recording;
+mod vm_hooks_replay;
+mod vm_hooks_trace;
+mod vm_hooks_tracing;
 
 pub use breakpoint_value::*;
+pub use bulk_memory_cost::BulkMemoryCost;
+pub use call_outcome::*;
+pub use compile_batch::CompileBatchResult;
+pub use current_instance::{run_in_instance, with_current_instance};
+pub use execution_trace::*;
 pub use executor::*;
+pub use executor_error::*;
+pub use gas_profile::GasProfileEntry;
+pub use gas_schedule::*;
+pub use import_issue::*;
+pub use in_memory_context::InMemoryContext;
 pub use instance::*;
+pub use instance_snapshot::InstanceSnapshot;
+pub use module_cache_stats::ModuleCacheStats;
+pub use module_info::*;
 pub use opcode_cost::OpcodeCost;
+pub use opcode_cost_schedule::OpcodeCostChange;
+pub use opcode_histogram::*;
+pub use opcode_trace::*;
 pub use service_error::ServiceError;
 pub use service_trait::*;
+pub use value::*;
 pub use vm_hooks::*;
+pub use vm_hooks_in_memory::InMemoryVMHooks;
+pub use vm_hooks_recording::*;
+pub use vm_hooks_trace::*;
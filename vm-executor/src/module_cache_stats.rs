@@ -0,0 +1,18 @@
+/// Counters of the compiled module cache kept by an executor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ModuleCacheStats {
+    /// Number of instances created from an already compiled module.
+    pub hits: u64,
+
+    /// Number of instances that required compiling their module.
+    pub misses: u64,
+
+    /// Number of modules dropped from the cache to make room for newer ones.
+    pub evictions: u64,
+
+    /// Number of modules currently in the cache.
+    pub len: usize,
+
+    /// Maximum number of modules the cache can hold. Zero means caching is disabled.
+    pub capacity: usize,
+}
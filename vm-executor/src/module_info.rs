@@ -0,0 +1,103 @@
+use std::fmt::{self, Display, Formatter};
+
+/// The kind of an imported or exported module item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModuleItemKind {
+    Function,
+    Table,
+    Memory,
+    Global,
+    Other,
+}
+
+impl Display for ModuleItemKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match self {
+            ModuleItemKind::Function => "function",
+            ModuleItemKind::Table => "table",
+            ModuleItemKind::Memory => "memory",
+            ModuleItemKind::Global => "global",
+            ModuleItemKind::Other => "other",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModuleImport {
+    pub namespace: String,
+    pub name: String,
+    pub kind: ModuleItemKind,
+
+    /// Set for function imports, e.g. `[I32, I32] -> [I64]`.
+    pub signature: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModuleExport {
+    pub name: String,
+    pub kind: ModuleItemKind,
+
+    /// Index of the item in the index space of its kind.
+    pub index: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ModuleMemory {
+    pub min_pages: u64,
+    pub max_pages: Option<u64>,
+    pub imported: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ModuleTable {
+    pub min_size: u32,
+    pub max_size: Option<u32>,
+    pub imported: bool,
+}
+
+/// Facts about a contract, gathered without compiling or instantiating it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModuleInfo {
+    pub imports: Vec<ModuleImport>,
+    pub exports: Vec<ModuleExport>,
+
+    /// All memories, imported ones first, in index order.
+    pub memories: Vec<ModuleMemory>,
+
+    /// All tables, imported ones first, in index order.
+    pub tables: Vec<ModuleTable>,
+
+    /// Number of functions defined by the module, imports excluded.
+    pub function_count: usize,
+
+    pub data_segment_count: usize,
+    pub data_segment_bytes: usize,
+    pub has_start_function: bool,
+    pub custom_sections: Vec<String>,
+
+    /// The memory size limit the contract was inspected with, see
+    /// `CompilationOptions::max_memory_pages`.
+    pub max_memory_pages_allowed: u64,
+}
+
+impl ModuleInfo {
+    /// Mirrors the memory checks done at instantiation: exactly one exported memory,
+    /// whose maximum (or initial size, if unbounded) is within the allowed limit.
+    pub fn memory_within_limits(&self) -> bool {
+        let mut exported_memories = self
+            .exports
+            .iter()
+            .filter(|export| export.kind == ModuleItemKind::Memory);
+
+        match (exported_memories.next(), exported_memories.next()) {
+            (Some(export), None) => match self.memories.get(export.index as usize) {
+                Some(memory) => {
+                    memory.max_pages.unwrap_or(memory.min_pages) <= self.max_memory_pages_allowed
+                }
+                None => false,
+            },
+            _ => false,
+        }
+    }
+}
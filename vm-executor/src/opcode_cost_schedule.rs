@@ -0,0 +1,82 @@
+use crate::{OpcodeCost, ScheduleError};
+use log::warn;
+
+/// The opcodes after which the metering middleware checks the gas, they should not be free.
+const CONTROL_FLOW_OPCODES: &[&str] = &[
+    "opcode_block",
+    "opcode_br",
+    "opcode_brif",
+    "opcode_brtable",
+    "opcode_call",
+    "opcode_callindirect",
+    "opcode_else",
+    "opcode_end",
+    "opcode_if",
+    "opcode_loop",
+    "opcode_return",
+    "opcode_unreachable",
+];
+
+/// An opcode whose cost differs between two schedules.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpcodeCostChange {
+    pub opcode: String,
+    pub old: u32,
+    pub new: u32,
+}
+
+impl OpcodeCost {
+    /// Parses a schedule with one key per opcode, e.g. `opcode_brif = 3`.
+    ///
+    /// Unknown and missing opcodes are rejected.
+    pub fn from_toml(schedule: &str) -> Result<OpcodeCost, ScheduleError> {
+        let opcode_cost: OpcodeCost =
+            toml::from_str(schedule).map_err(|err| ScheduleError::Toml(err.to_string()))?;
+        opcode_cost.warn_zero_cost_control_flow();
+        Ok(opcode_cost)
+    }
+
+    /// Parses a schedule with one key per opcode, e.g. `{"opcode_brif": 3, ...}`.
+    ///
+    /// Unknown and missing opcodes are rejected.
+    pub fn from_json(schedule: &str) -> Result<OpcodeCost, ScheduleError> {
+        let opcode_cost: OpcodeCost =
+            serde_json::from_str(schedule).map_err(|err| ScheduleError::Json(err.to_string()))?;
+        opcode_cost.warn_zero_cost_control_flow();
+        Ok(opcode_cost)
+    }
+
+    /// The control flow opcodes that cost nothing.
+    ///
+    /// Loops made only of free opcodes never use gas, so they can run forever.
+    pub fn zero_cost_control_flow_opcodes(&self) -> Vec<&'static str> {
+        self.fields()
+            .into_iter()
+            .filter(|(opcode, cost)| *cost == 0 && CONTROL_FLOW_OPCODES.contains(opcode))
+            .map(|(opcode, _)| opcode)
+            .collect()
+    }
+
+    /// Lists the opcodes whose cost is different in `other`, sorted by name.
+    pub fn diff(&self, other: &OpcodeCost) -> Vec<OpcodeCostChange> {
+        let mut changes: Vec<OpcodeCostChange> = self
+            .fields()
+            .into_iter()
+            .zip(other.fields())
+            .filter(|((_, old), (_, new))| old != new)
+            .map(|((opcode, old), (_, new))| OpcodeCostChange {
+                opcode: opcode.to_string(),
+                old,
+                new,
+            })
+            .collect();
+        changes.sort_by(|a, b| a.opcode.cmp(&b.opcode));
+        changes
+    }
+
+    fn warn_zero_cost_control_flow(&self) {
+        for opcode in self.zero_cost_control_flow_opcodes() {
+            warn!("control flow opcode {opcode} costs no gas");
+        }
+    }
+}
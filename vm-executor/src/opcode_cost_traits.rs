@@ -0,0 +1,231 @@
+//! Implemented by hand, since `OpcodeCost` is generated and only derives `Clone`, `Debug` and
+//! `Default`.
+//!
+//! The fields are listed once, in declaration order, in the `opcode_cost_fields!` call below.
+//! The list is matched against the struct without `..`, so adding an opcode to the struct fails
+//! to compile until it is added here too.
+
+use crate::OpcodeCost;
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+macro_rules! opcode_cost_fields {
+    ($($field:ident,)*) => {
+        /// The names of the opcode costs, in declaration order.
+        const FIELDS: &[&str] = &[$(stringify!($field)),*];
+
+        impl OpcodeCost {
+            /// Every opcode cost with its name, in declaration order.
+            pub fn fields(&self) -> Vec<(&'static str, u32)> {
+                let OpcodeCost { $($field),* } = self;
+                vec![$((stringify!($field), *$field)),*]
+            }
+
+            /// Builds the costs from a lookup by name, the error names the first missing opcode.
+            fn from_fields(
+                mut cost_of: impl FnMut(&'static str) -> Option<u32>,
+            ) -> Result<OpcodeCost, &'static str> {
+                Ok(OpcodeCost {
+                    $($field: cost_of(stringify!($field)).ok_or(stringify!($field))?,)*
+                })
+            }
+        }
+    };
+}
+
+opcode_cost_fields! {
+    opcode_block,
+    opcode_br,
+    opcode_brif,
+    opcode_brtable,
+    opcode_call,
+    opcode_callindirect,
+    opcode_catch,
+    opcode_catchall,
+    opcode_delegate,
+    opcode_drop,
+    opcode_else,
+    opcode_end,
+    opcode_globalget,
+    opcode_globalset,
+    opcode_i32add,
+    opcode_i32and,
+    opcode_i32clz,
+    opcode_i32const,
+    opcode_i32ctz,
+    opcode_i32divs,
+    opcode_i32divu,
+    opcode_i32eq,
+    opcode_i32eqz,
+    opcode_i32extend16s,
+    opcode_i32extend8s,
+    opcode_i32ges,
+    opcode_i32geu,
+    opcode_i32gts,
+    opcode_i32gtu,
+    opcode_i32les,
+    opcode_i32leu,
+    opcode_i32load,
+    opcode_i32load16s,
+    opcode_i32load16u,
+    opcode_i32load8s,
+    opcode_i32load8u,
+    opcode_i32lts,
+    opcode_i32ltu,
+    opcode_i32mul,
+    opcode_i32ne,
+    opcode_i32or,
+    opcode_i32popcnt,
+    opcode_i32rems,
+    opcode_i32remu,
+    opcode_i32rotl,
+    opcode_i32rotr,
+    opcode_i32shl,
+    opcode_i32shrs,
+    opcode_i32shru,
+    opcode_i32store,
+    opcode_i32store16,
+    opcode_i32store8,
+    opcode_i32sub,
+    opcode_i32wrapi64,
+    opcode_i32xor,
+    opcode_i64add,
+    opcode_i64and,
+    opcode_i64clz,
+    opcode_i64const,
+    opcode_i64ctz,
+    opcode_i64divs,
+    opcode_i64divu,
+    opcode_i64eq,
+    opcode_i64eqz,
+    opcode_i64extend16s,
+    opcode_i64extend32s,
+    opcode_i64extend8s,
+    opcode_i64extendi32s,
+    opcode_i64extendi32u,
+    opcode_i64ges,
+    opcode_i64geu,
+    opcode_i64gts,
+    opcode_i64gtu,
+    opcode_i64les,
+    opcode_i64leu,
+    opcode_i64load,
+    opcode_i64load16s,
+    opcode_i64load16u,
+    opcode_i64load32s,
+    opcode_i64load32u,
+    opcode_i64load8s,
+    opcode_i64load8u,
+    opcode_i64lts,
+    opcode_i64ltu,
+    opcode_i64mul,
+    opcode_i64ne,
+    opcode_i64or,
+    opcode_i64popcnt,
+    opcode_i64rems,
+    opcode_i64remu,
+    opcode_i64rotl,
+    opcode_i64rotr,
+    opcode_i64shl,
+    opcode_i64shrs,
+    opcode_i64shru,
+    opcode_i64store,
+    opcode_i64store16,
+    opcode_i64store32,
+    opcode_i64store8,
+    opcode_i64sub,
+    opcode_i64xor,
+    opcode_if,
+    opcode_localget,
+    opcode_localset,
+    opcode_localtee,
+    opcode_localallocate,
+    opcode_loop,
+    opcode_memorygrow,
+    opcode_memorysize,
+    opcode_nop,
+    opcode_reffunc,
+    opcode_refisnull,
+    opcode_refnull,
+    opcode_rethrow,
+    opcode_return,
+    opcode_returncall,
+    opcode_returncallindirect,
+    opcode_select,
+    opcode_tableget,
+    opcode_tablegrow,
+    opcode_tableinit,
+    opcode_tableset,
+    opcode_tablesize,
+    opcode_throw,
+    opcode_try,
+    opcode_typedselect,
+    opcode_unreachable,
+    opcode_unwind,
+}
+
+impl PartialEq for OpcodeCost {
+    fn eq(&self, other: &Self) -> bool {
+        self.fields() == other.fields()
+    }
+}
+
+impl Eq for OpcodeCost {}
+
+impl Hash for OpcodeCost {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for (_, cost) in self.fields() {
+            cost.hash(state);
+        }
+    }
+}
+
+impl Serialize for OpcodeCost {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("OpcodeCost", FIELDS.len())?;
+        for (name, cost) in self.fields() {
+            state.serialize_field(name, &cost)?;
+        }
+        state.end()
+    }
+}
+
+/// Rejects unknown, duplicate and missing opcodes, with the errors of a derived `Deserialize`
+/// with `deny_unknown_fields`.
+impl<'de> Deserialize<'de> for OpcodeCost {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct("OpcodeCost", FIELDS, OpcodeCostVisitor)
+    }
+}
+
+struct OpcodeCostVisitor;
+
+impl<'de> Visitor<'de> for OpcodeCostVisitor {
+    type Value = OpcodeCost;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("struct OpcodeCost")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<OpcodeCost, A::Error> {
+        let mut costs: Vec<Option<u32>> = vec![None; FIELDS.len()];
+        while let Some(name) = map.next_key::<String>()? {
+            let Some(index) = field_index(&name) else {
+                return Err(de::Error::unknown_field(&name, FIELDS));
+            };
+            if costs[index].is_some() {
+                return Err(de::Error::duplicate_field(FIELDS[index]));
+            }
+            costs[index] = Some(map.next_value()?);
+        }
+
+        OpcodeCost::from_fields(|name| costs[field_index(name)?]).map_err(de::Error::missing_field)
+    }
+}
+
+fn field_index(name: &str) -> Option<usize> {
+    FIELDS.iter().position(|field| *field == name)
+}
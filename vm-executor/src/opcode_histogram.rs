@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A group of operators with similar costs, as counted by the opcode histogram.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OpcodeClass {
+    /// Blocks, branches, returns and the like.
+    ControlFlow,
+    Call,
+    Local,
+    Global,
+    Load,
+    Store,
+
+    /// `memory.size` and `memory.grow`.
+    Memory,
+
+    /// The operators metered by length, e.g. `memory.fill`.
+    BulkMemory,
+    Constant,
+
+    /// Integer arithmetic, bitwise operators and shifts.
+    Arithmetic,
+    Comparison,
+    Conversion,
+
+    /// `drop` and `select`.
+    Parametric,
+
+    /// Anything else, e.g. float operators when metering does not reject them.
+    Other,
+}
+
+impl OpcodeClass {
+    pub const ALL: [OpcodeClass; 14] = [
+        OpcodeClass::ControlFlow,
+        OpcodeClass::Call,
+        OpcodeClass::Local,
+        OpcodeClass::Global,
+        OpcodeClass::Load,
+        OpcodeClass::Store,
+        OpcodeClass::Memory,
+        OpcodeClass::BulkMemory,
+        OpcodeClass::Constant,
+        OpcodeClass::Arithmetic,
+        OpcodeClass::Comparison,
+        OpcodeClass::Conversion,
+        OpcodeClass::Parametric,
+        OpcodeClass::Other,
+    ];
+}
+
+impl fmt::Display for OpcodeClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpcodeClass::ControlFlow => write!(f, "control_flow"),
+            OpcodeClass::Call => write!(f, "call"),
+            OpcodeClass::Local => write!(f, "local"),
+            OpcodeClass::Global => write!(f, "global"),
+            OpcodeClass::Load => write!(f, "load"),
+            OpcodeClass::Store => write!(f, "store"),
+            OpcodeClass::Memory => write!(f, "memory"),
+            OpcodeClass::BulkMemory => write!(f, "bulk_memory"),
+            OpcodeClass::Constant => write!(f, "constant"),
+            OpcodeClass::Arithmetic => write!(f, "arithmetic"),
+            OpcodeClass::Comparison => write!(f, "comparison"),
+            OpcodeClass::Conversion => write!(f, "conversion"),
+            OpcodeClass::Parametric => write!(f, "parametric"),
+            OpcodeClass::Other => write!(f, "other"),
+        }
+    }
+}
+
+/// The number of executed operators of each class, see `Instance::opcode_histogram`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OpcodeHistogram {
+    /// Classes that were never executed may be missing.
+    pub counts: BTreeMap<OpcodeClass, u64>,
+}
+
+impl OpcodeHistogram {
+    pub fn count(&self, opcode_class: OpcodeClass) -> u64 {
+        self.counts.get(&opcode_class).copied().unwrap_or(0)
+    }
+
+    /// The number of executed operators, all classes together.
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+}
@@ -0,0 +1,51 @@
+use serde::Serialize;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// One operator of a compiled contract, as seen by the opcode tracer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct OpcodeTraceEntry {
+    /// The index of the function among the functions defined by the contract, imports excluded.
+    pub function_index: u32,
+
+    /// The index of the operator among the operators of the function body, starting at zero.
+    pub operator_index: u32,
+
+    /// The operator name, e.g. `I32Const`.
+    pub operator: String,
+}
+
+impl OpcodeTraceEntry {
+    /// The entry as a single line of JSON, without the line break.
+    pub fn to_json_line(&self) -> String {
+        serde_json::to_string(self).expect("opcode trace entries are always serializable")
+    }
+}
+
+pub type OpcodeTraceCallback = Arc<dyn Fn(&OpcodeTraceEntry) + Send + Sync>;
+
+/// Where the opcode tracer writes the operators of the contracts compiled with
+/// `CompilationOptions::opcode_trace`, see `Executor::set_opcode_trace_sink`.
+#[derive(Clone, Default)]
+pub enum OpcodeTraceSink {
+    /// Recreated for each compiled contract, with one JSON entry per line.
+    File(PathBuf),
+
+    /// Kept by the instance, see `Instance::opcode_trace`.
+    #[default]
+    Memory,
+
+    /// Called for each operator while compiling, one operator at a time.
+    Callback(OpcodeTraceCallback),
+}
+
+impl fmt::Debug for OpcodeTraceSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpcodeTraceSink::File(path) => f.debug_tuple("File").field(path).finish(),
+            OpcodeTraceSink::Memory => write!(f, "Memory"),
+            OpcodeTraceSink::Callback(_) => write!(f, "Callback"),
+        }
+    }
+}
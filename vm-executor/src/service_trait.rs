@@ -1,6 +1,4 @@
-use crate::{Executor, VMHooks};
-
-pub type ExecutorError = Box<dyn std::error::Error>;
+use crate::{Executor, ExecutorError, VMHooks};
 
 pub trait ExecutorLastError {
     /// Updates the last known error.
@@ -12,6 +10,8 @@ pub trait ExecutorLastError {
 
 pub trait ExecutorService: ExecutorLastError {
     /// Creates a new VM executor.
+    ///
+    /// The hooks can be wrapped in `TracingVMHooks` to record the hook calls of its instances.
     fn new_executor(
         &self,
         vm_hooks_builder: Box<dyn VMHooks>,
@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    with_current_instance, BreakpointValue, HookCall, HookCallSink, HookRecordingError, HookResult,
+    HookValue, MemoryWrite,
+};
+
+/// A hook call as saved in a `HookRecording`.
+///
+/// The arguments are kept in the order of the `VMHooks` method parameters. The wall time
+/// is left out, since it does not take part in the replay.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedHookCall {
+    pub name: String,
+    pub arguments: Vec<HookValue>,
+    pub result: Option<HookValue>,
+    pub memory_writes: Vec<MemoryWrite>,
+}
+
+impl From<HookCall> for RecordedHookCall {
+    fn from(hook_call: HookCall) -> Self {
+        RecordedHookCall {
+            name: hook_call.name.to_string(),
+            arguments: hook_call
+                .arguments
+                .iter()
+                .map(|argument| argument.value)
+                .collect(),
+            result: hook_call.result,
+            memory_writes: hook_call.memory_writes,
+        }
+    }
+}
+
+impl fmt::Display for RecordedHookCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arguments: Vec<String> = self.arguments.iter().map(ToString::to_string).collect();
+        write!(f, "{}({})", self.name, arguments.join(", "))
+    }
+}
+
+/// The hook calls made by a contract, in order, to be replayed by `ReplayVMHooks`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookRecording {
+    pub calls: Vec<RecordedHookCall>,
+}
+
+impl HookRecording {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("hook recordings are always serializable")
+    }
+
+    pub fn from_json(recording: &str) -> Result<HookRecording, HookRecordingError> {
+        serde_json::from_str(recording).map_err(|err| HookRecordingError::Json(err.to_string()))
+    }
+
+    /// Writes the recording to a JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), HookRecordingError> {
+        std::fs::write(path, self.to_json()).map_err(|err| HookRecordingError::Io(err.to_string()))
+    }
+
+    /// Reads a recording written by `save`.
+    pub fn load(path: impl AsRef<Path>) -> Result<HookRecording, HookRecordingError> {
+        let recording =
+            std::fs::read_to_string(path).map_err(|err| HookRecordingError::Io(err.to_string()))?;
+        Self::from_json(&recording)
+    }
+}
+
+/// Records the hook calls, with the bytes they write into contract memory.
+///
+/// Give it to `TracingVMHooks` wrapping the hooks of the node, or of any other implementation,
+/// and take the recording after the call to reproduce. Clones share the same recording.
+#[derive(Clone, Debug, Default)]
+pub struct HookCallRecorder {
+    calls: Arc<Mutex<Vec<RecordedHookCall>>>,
+}
+
+impl HookCallRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the calls recorded so far, and starts a new recording.
+    pub fn take_recording(&self) -> HookRecording {
+        HookRecording {
+            calls: std::mem::take(&mut *self.calls.lock().unwrap()),
+        }
+    }
+}
+
+impl HookCallSink for HookCallRecorder {
+    fn record(&self, hook_call: HookCall) {
+        self.calls.lock().unwrap().push(hook_call.into());
+    }
+
+    fn records_memory_writes(&self) -> bool {
+        true
+    }
+}
+
+/// The first hook call of a replay that does not match the recording.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplayDivergence {
+    /// The position of the call among the hook calls of the replay, starting at zero.
+    pub call_index: usize,
+
+    /// `None` if the recording has no more calls.
+    pub expected: Option<RecordedHookCall>,
+
+    /// The call made by the contract, without result or memory writes.
+    pub actual: RecordedHookCall,
+}
+
+impl fmt::Display for ReplayDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.expected {
+            Some(expected) => write!(
+                f,
+                "hook call {} diverged: expected {expected}, got {}",
+                self.call_index, self.actual
+            ),
+            None => write!(
+                f,
+                "hook call {} diverged: expected no more calls, got {}",
+                self.call_index, self.actual
+            ),
+        }
+    }
+}
+
+/// Plays a `HookRecording` back instead of running the hooks, with no node.
+///
+/// Each hook call must match the next recorded call, by name and arguments. Then the recorded
+/// bytes are written into the memory of the calling instance, and the recorded result returned.
+/// At the first mismatch, or if the bytes cannot be written, the divergence is kept and
+/// the instance is stopped with an `ExecutionFailed` breakpoint, if it has runtime breakpoints.
+/// The remaining hook calls do nothing, and return zero.
+///
+/// Clones share the same replay, so a clone can be kept to check it.
+#[derive(Clone, Debug)]
+pub struct ReplayVMHooks {
+    state: Arc<Mutex<ReplayState>>,
+}
+
+#[derive(Debug)]
+struct ReplayState {
+    calls: Vec<RecordedHookCall>,
+    next_call: usize,
+    divergence: Option<ReplayDivergence>,
+}
+
+impl ReplayVMHooks {
+    pub fn new(recording: HookRecording) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(ReplayState {
+                calls: recording.calls,
+                next_call: 0,
+                divergence: None,
+            })),
+        }
+    }
+
+    /// The first hook call that did not match the recording, if any.
+    pub fn divergence(&self) -> Option<ReplayDivergence> {
+        self.state.lock().unwrap().divergence.clone()
+    }
+
+    /// The number of recorded calls not replayed yet.
+    pub fn remaining_calls(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state.calls.len() - state.next_call
+    }
+
+    pub(crate) fn replay_call<R: HookResult>(
+        &self,
+        name: &'static str,
+        arguments: Vec<HookValue>,
+    ) -> R {
+        let mut state = self.state.lock().unwrap();
+        if state.divergence.is_some() {
+            return R::from_hook_value(None);
+        }
+
+        let call_index = state.next_call;
+        let expected = state.calls.get(call_index).cloned();
+        if let Some(expected) = &expected {
+            if expected.name == name
+                && expected.arguments == arguments
+                && write_memory(&expected.memory_writes)
+            {
+                state.next_call += 1;
+                return R::from_hook_value(expected.result);
+            }
+        }
+
+        state.divergence = Some(ReplayDivergence {
+            call_index,
+            expected,
+            actual: RecordedHookCall {
+                name: name.to_string(),
+                arguments,
+                result: None,
+                memory_writes: Vec::new(),
+            },
+        });
+        with_current_instance(|instance| {
+            // Without runtime breakpoints, the divergence is only reported
+            let _ = instance.set_breakpoint_value(BreakpointValue::ExecutionFailed);
+        });
+        R::from_hook_value(None)
+    }
+}
+
+fn write_memory(memory_writes: &[MemoryWrite]) -> bool {
+    if memory_writes.is_empty() {
+        return true;
+    }
+    with_current_instance(|instance| {
+        memory_writes.iter().all(|memory_write| {
+            instance
+                .memory_store(memory_write.offset, &memory_write.bytes)
+                .is_ok()
+        })
+    })
+    .unwrap_or(false)
+}
@@ -0,0 +1,2063 @@
+// Code generated by vmhooks generator. DO NOT EDIT.
+
+// !!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!
+// !!!!!!!!!!!!!!!!!!!!!! AUTO-GENERATED FILE !!!!!!!!!!!!!!!!!!!!!!
+// !!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!
+
+use std::ffi::c_void;
+
+use crate::{HookValue, MemLength, MemPtr, ReplayVMHooks, VMHooks};
+
+#[rustfmt::skip]
+impl VMHooks for ReplayVMHooks {
+    fn set_vm_hooks_ptr(&mut self, _vm_hooks_ptr: *mut c_void) {
+    }
+
+    fn get_gas_left(&self) -> i64 {
+        self.replay_call("get_gas_left", Vec::new())
+    }
+
+    fn get_sc_address(&self, result_offset: MemPtr) {
+        let arguments = vec![
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("get_sc_address", arguments)
+    }
+
+    fn get_owner_address(&self, result_offset: MemPtr) {
+        let arguments = vec![
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("get_owner_address", arguments)
+    }
+
+    fn is_smart_contract(&self, address_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(address_offset),
+        ];
+        self.replay_call("is_smart_contract", arguments)
+    }
+
+    fn signal_error(&self, message_offset: MemPtr, message_length: MemLength) {
+        let arguments = vec![
+            HookValue::MemPtr(message_offset),
+            HookValue::MemLength(message_length),
+        ];
+        self.replay_call("signal_error", arguments)
+    }
+
+    fn get_external_balance(&self, address_offset: MemPtr, result_offset: MemPtr) {
+        let arguments = vec![
+            HookValue::MemPtr(address_offset),
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("get_external_balance", arguments)
+    }
+
+    fn get_block_hash(&self, nonce: i64, result_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::I64(nonce),
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("get_block_hash", arguments)
+    }
+
+    fn get_kda_balance(&self, address_offset: MemPtr, token_id_offset: MemPtr, token_id_len: MemLength, nonce: i64, result_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(address_offset),
+            HookValue::MemPtr(token_id_offset),
+            HookValue::MemLength(token_id_len),
+            HookValue::I64(nonce),
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("get_kda_balance", arguments)
+    }
+
+    fn get_kda_nft_name_length(&self, address_offset: MemPtr, token_id_offset: MemPtr, token_id_len: MemLength, nonce: i64) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(address_offset),
+            HookValue::MemPtr(token_id_offset),
+            HookValue::MemLength(token_id_len),
+            HookValue::I64(nonce),
+        ];
+        self.replay_call("get_kda_nft_name_length", arguments)
+    }
+
+    fn get_kda_nft_uri_length(&self, address_offset: MemPtr, token_id_offset: MemPtr, token_id_len: MemLength, nonce: i64) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(address_offset),
+            HookValue::MemPtr(token_id_offset),
+            HookValue::MemLength(token_id_len),
+            HookValue::I64(nonce),
+        ];
+        self.replay_call("get_kda_nft_uri_length", arguments)
+    }
+
+    fn get_kda_token_data(&self, address_offset: MemPtr, token_id_offset: MemPtr, token_id_len: MemLength, nonce: i64, precision_handle: i32, id_offset: MemPtr, name_offset: MemPtr, creator_offset: MemPtr, logo_offset: MemPtr, initial_supply_offset: MemPtr, circulating_supply_offset: MemPtr, max_supply_offset: MemPtr, minted_offset: MemPtr, burned_offset: MemPtr, royalties_offset: MemPtr, properties_offset: MemPtr, attributes_offset: MemPtr, roles_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(address_offset),
+            HookValue::MemPtr(token_id_offset),
+            HookValue::MemLength(token_id_len),
+            HookValue::I64(nonce),
+            HookValue::I32(precision_handle),
+            HookValue::MemPtr(id_offset),
+            HookValue::MemPtr(name_offset),
+            HookValue::MemPtr(creator_offset),
+            HookValue::MemPtr(logo_offset),
+            HookValue::MemPtr(initial_supply_offset),
+            HookValue::MemPtr(circulating_supply_offset),
+            HookValue::MemPtr(max_supply_offset),
+            HookValue::MemPtr(minted_offset),
+            HookValue::MemPtr(burned_offset),
+            HookValue::MemPtr(royalties_offset),
+            HookValue::MemPtr(properties_offset),
+            HookValue::MemPtr(attributes_offset),
+            HookValue::MemPtr(roles_offset),
+        ];
+        self.replay_call("get_kda_token_data", arguments)
+    }
+
+    fn validate_token_identifier(&self, token_id_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(token_id_handle),
+        ];
+        self.replay_call("validate_token_identifier", arguments)
+    }
+
+    fn upgrade_contract(&self, dest_offset: MemPtr, gas_limit: i64, value_offset: MemPtr, code_offset: MemPtr, code_metadata_offset: MemPtr, length: MemLength, num_arguments: i32, arguments_length_offset: MemPtr, data_offset: MemPtr) {
+        let arguments = vec![
+            HookValue::MemPtr(dest_offset),
+            HookValue::I64(gas_limit),
+            HookValue::MemPtr(value_offset),
+            HookValue::MemPtr(code_offset),
+            HookValue::MemPtr(code_metadata_offset),
+            HookValue::MemLength(length),
+            HookValue::I32(num_arguments),
+            HookValue::MemPtr(arguments_length_offset),
+            HookValue::MemPtr(data_offset),
+        ];
+        self.replay_call("upgrade_contract", arguments)
+    }
+
+    fn upgrade_from_source_contract(&self, dest_offset: MemPtr, gas_limit: i64, value_offset: MemPtr, source_contract_address_offset: MemPtr, code_metadata_offset: MemPtr, num_arguments: i32, arguments_length_offset: MemPtr, data_offset: MemPtr) {
+        let arguments = vec![
+            HookValue::MemPtr(dest_offset),
+            HookValue::I64(gas_limit),
+            HookValue::MemPtr(value_offset),
+            HookValue::MemPtr(source_contract_address_offset),
+            HookValue::MemPtr(code_metadata_offset),
+            HookValue::I32(num_arguments),
+            HookValue::MemPtr(arguments_length_offset),
+            HookValue::MemPtr(data_offset),
+        ];
+        self.replay_call("upgrade_from_source_contract", arguments)
+    }
+
+    fn delete_contract(&self, dest_offset: MemPtr, gas_limit: i64, num_arguments: i32, arguments_length_offset: MemPtr, data_offset: MemPtr) {
+        let arguments = vec![
+            HookValue::MemPtr(dest_offset),
+            HookValue::I64(gas_limit),
+            HookValue::I32(num_arguments),
+            HookValue::MemPtr(arguments_length_offset),
+            HookValue::MemPtr(data_offset),
+        ];
+        self.replay_call("delete_contract", arguments)
+    }
+
+    fn get_argument_length(&self, id: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(id),
+        ];
+        self.replay_call("get_argument_length", arguments)
+    }
+
+    fn get_argument(&self, id: i32, arg_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::I32(id),
+            HookValue::MemPtr(arg_offset),
+        ];
+        self.replay_call("get_argument", arguments)
+    }
+
+    fn get_function(&self, function_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(function_offset),
+        ];
+        self.replay_call("get_function", arguments)
+    }
+
+    fn get_num_arguments(&self) -> i32 {
+        self.replay_call("get_num_arguments", Vec::new())
+    }
+
+    fn storage_store(&self, key_offset: MemPtr, key_length: MemLength, data_offset: MemPtr, data_length: MemLength) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+            HookValue::MemPtr(data_offset),
+            HookValue::MemLength(data_length),
+        ];
+        self.replay_call("storage_store", arguments)
+    }
+
+    fn storage_load_length(&self, key_offset: MemPtr, key_length: MemLength) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+        ];
+        self.replay_call("storage_load_length", arguments)
+    }
+
+    fn storage_load_from_address(&self, address_offset: MemPtr, key_offset: MemPtr, key_length: MemLength, data_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(address_offset),
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+            HookValue::MemPtr(data_offset),
+        ];
+        self.replay_call("storage_load_from_address", arguments)
+    }
+
+    fn storage_load(&self, key_offset: MemPtr, key_length: MemLength, data_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+            HookValue::MemPtr(data_offset),
+        ];
+        self.replay_call("storage_load", arguments)
+    }
+
+    fn set_storage_lock(&self, key_offset: MemPtr, key_length: MemLength, lock_timestamp: i64) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+            HookValue::I64(lock_timestamp),
+        ];
+        self.replay_call("set_storage_lock", arguments)
+    }
+
+    fn get_storage_lock(&self, key_offset: MemPtr, key_length: MemLength) -> i64 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+        ];
+        self.replay_call("get_storage_lock", arguments)
+    }
+
+    fn is_storage_locked(&self, key_offset: MemPtr, key_length: MemLength) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+        ];
+        self.replay_call("is_storage_locked", arguments)
+    }
+
+    fn clear_storage_lock(&self, key_offset: MemPtr, key_length: MemLength) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+        ];
+        self.replay_call("clear_storage_lock", arguments)
+    }
+
+    fn get_caller(&self, result_offset: MemPtr) {
+        let arguments = vec![
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("get_caller", arguments)
+    }
+
+    fn check_no_payment(&self) {
+        self.replay_call("check_no_payment", Vec::new())
+    }
+
+    fn get_call_value(&self, result_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("get_call_value", arguments)
+    }
+
+    fn get_kda_value(&self, result_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("get_kda_value", arguments)
+    }
+
+    fn get_kda_value_by_index(&self, result_offset: MemPtr, index: i32) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(result_offset),
+            HookValue::I32(index),
+        ];
+        self.replay_call("get_kda_value_by_index", arguments)
+    }
+
+    fn get_kda_token_name(&self, result_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("get_kda_token_name", arguments)
+    }
+
+    fn get_kda_token_name_by_index(&self, result_offset: MemPtr, index: i32) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(result_offset),
+            HookValue::I32(index),
+        ];
+        self.replay_call("get_kda_token_name_by_index", arguments)
+    }
+
+    fn get_kda_token_nonce(&self) -> i64 {
+        self.replay_call("get_kda_token_nonce", Vec::new())
+    }
+
+    fn get_kda_token_nonce_by_index(&self, index: i32) -> i64 {
+        let arguments = vec![
+            HookValue::I32(index),
+        ];
+        self.replay_call("get_kda_token_nonce_by_index", arguments)
+    }
+
+    fn get_kda_token_type(&self) -> i32 {
+        self.replay_call("get_kda_token_type", Vec::new())
+    }
+
+    fn get_kda_token_type_by_index(&self, index: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(index),
+        ];
+        self.replay_call("get_kda_token_type_by_index", arguments)
+    }
+
+    fn get_num_kda_transfers(&self) -> i32 {
+        self.replay_call("get_num_kda_transfers", Vec::new())
+    }
+
+    fn get_call_value_by_token_name(&self, call_value_offset: MemPtr, token_name_offset: MemPtr, token_name_length: MemLength) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(call_value_offset),
+            HookValue::MemPtr(token_name_offset),
+            HookValue::MemLength(token_name_length),
+        ];
+        self.replay_call("get_call_value_by_token_name", arguments)
+    }
+
+    fn get_call_value_token_name(&self, call_value_offset: MemPtr, token_name_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(call_value_offset),
+            HookValue::MemPtr(token_name_offset),
+        ];
+        self.replay_call("get_call_value_token_name", arguments)
+    }
+
+    fn get_call_value_token_name_by_index(&self, call_value_offset: MemPtr, token_name_offset: MemPtr, index: i32) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(call_value_offset),
+            HookValue::MemPtr(token_name_offset),
+            HookValue::I32(index),
+        ];
+        self.replay_call("get_call_value_token_name_by_index", arguments)
+    }
+
+    fn write_log(&self, data_pointer: MemPtr, data_length: MemLength, topic_ptr: MemPtr, num_topics: i32) {
+        let arguments = vec![
+            HookValue::MemPtr(data_pointer),
+            HookValue::MemLength(data_length),
+            HookValue::MemPtr(topic_ptr),
+            HookValue::I32(num_topics),
+        ];
+        self.replay_call("write_log", arguments)
+    }
+
+    fn write_event_log(&self, num_topics: i32, topic_lengths_offset: MemPtr, topic_offset: MemPtr, data_offset: MemPtr, data_length: MemLength) {
+        let arguments = vec![
+            HookValue::I32(num_topics),
+            HookValue::MemPtr(topic_lengths_offset),
+            HookValue::MemPtr(topic_offset),
+            HookValue::MemPtr(data_offset),
+            HookValue::MemLength(data_length),
+        ];
+        self.replay_call("write_event_log", arguments)
+    }
+
+    fn get_block_timestamp(&self) -> i64 {
+        self.replay_call("get_block_timestamp", Vec::new())
+    }
+
+    fn get_block_nonce(&self) -> i64 {
+        self.replay_call("get_block_nonce", Vec::new())
+    }
+
+    fn get_block_round(&self) -> i64 {
+        self.replay_call("get_block_round", Vec::new())
+    }
+
+    fn get_block_epoch(&self) -> i64 {
+        self.replay_call("get_block_epoch", Vec::new())
+    }
+
+    fn get_block_random_seed(&self, pointer: MemPtr) {
+        let arguments = vec![
+            HookValue::MemPtr(pointer),
+        ];
+        self.replay_call("get_block_random_seed", arguments)
+    }
+
+    fn get_state_root_hash(&self, pointer: MemPtr) {
+        let arguments = vec![
+            HookValue::MemPtr(pointer),
+        ];
+        self.replay_call("get_state_root_hash", arguments)
+    }
+
+    fn get_prev_block_timestamp(&self) -> i64 {
+        self.replay_call("get_prev_block_timestamp", Vec::new())
+    }
+
+    fn get_prev_block_nonce(&self) -> i64 {
+        self.replay_call("get_prev_block_nonce", Vec::new())
+    }
+
+    fn get_prev_block_round(&self) -> i64 {
+        self.replay_call("get_prev_block_round", Vec::new())
+    }
+
+    fn get_prev_block_epoch(&self) -> i64 {
+        self.replay_call("get_prev_block_epoch", Vec::new())
+    }
+
+    fn get_prev_block_random_seed(&self, pointer: MemPtr) {
+        let arguments = vec![
+            HookValue::MemPtr(pointer),
+        ];
+        self.replay_call("get_prev_block_random_seed", arguments)
+    }
+
+    fn finish(&self, pointer: MemPtr, length: MemLength) {
+        let arguments = vec![
+            HookValue::MemPtr(pointer),
+            HookValue::MemLength(length),
+        ];
+        self.replay_call("finish", arguments)
+    }
+
+    fn execute_on_same_context(&self, gas_limit: i64, address_offset: MemPtr, value_offset: MemPtr, function_offset: MemPtr, function_length: MemLength, num_arguments: i32, arguments_length_offset: MemPtr, data_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::I64(gas_limit),
+            HookValue::MemPtr(address_offset),
+            HookValue::MemPtr(value_offset),
+            HookValue::MemPtr(function_offset),
+            HookValue::MemLength(function_length),
+            HookValue::I32(num_arguments),
+            HookValue::MemPtr(arguments_length_offset),
+            HookValue::MemPtr(data_offset),
+        ];
+        self.replay_call("execute_on_same_context", arguments)
+    }
+
+    fn execute_on_dest_context(&self, gas_limit: i64, address_offset: MemPtr, value_offset: MemPtr, function_offset: MemPtr, function_length: MemLength, num_arguments: i32, arguments_length_offset: MemPtr, data_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::I64(gas_limit),
+            HookValue::MemPtr(address_offset),
+            HookValue::MemPtr(value_offset),
+            HookValue::MemPtr(function_offset),
+            HookValue::MemLength(function_length),
+            HookValue::I32(num_arguments),
+            HookValue::MemPtr(arguments_length_offset),
+            HookValue::MemPtr(data_offset),
+        ];
+        self.replay_call("execute_on_dest_context", arguments)
+    }
+
+    fn execute_read_only(&self, gas_limit: i64, address_offset: MemPtr, function_offset: MemPtr, function_length: MemLength, num_arguments: i32, arguments_length_offset: MemPtr, data_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::I64(gas_limit),
+            HookValue::MemPtr(address_offset),
+            HookValue::MemPtr(function_offset),
+            HookValue::MemLength(function_length),
+            HookValue::I32(num_arguments),
+            HookValue::MemPtr(arguments_length_offset),
+            HookValue::MemPtr(data_offset),
+        ];
+        self.replay_call("execute_read_only", arguments)
+    }
+
+    fn create_contract(&self, gas_limit: i64, value_offset: MemPtr, code_offset: MemPtr, code_metadata_offset: MemPtr, length: MemLength, result_offset: MemPtr, num_arguments: i32, arguments_length_offset: MemPtr, data_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::I64(gas_limit),
+            HookValue::MemPtr(value_offset),
+            HookValue::MemPtr(code_offset),
+            HookValue::MemPtr(code_metadata_offset),
+            HookValue::MemLength(length),
+            HookValue::MemPtr(result_offset),
+            HookValue::I32(num_arguments),
+            HookValue::MemPtr(arguments_length_offset),
+            HookValue::MemPtr(data_offset),
+        ];
+        self.replay_call("create_contract", arguments)
+    }
+
+    fn deploy_from_source_contract(&self, gas_limit: i64, value_offset: MemPtr, source_contract_address_offset: MemPtr, code_metadata_offset: MemPtr, result_address_offset: MemPtr, num_arguments: i32, arguments_length_offset: MemPtr, data_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::I64(gas_limit),
+            HookValue::MemPtr(value_offset),
+            HookValue::MemPtr(source_contract_address_offset),
+            HookValue::MemPtr(code_metadata_offset),
+            HookValue::MemPtr(result_address_offset),
+            HookValue::I32(num_arguments),
+            HookValue::MemPtr(arguments_length_offset),
+            HookValue::MemPtr(data_offset),
+        ];
+        self.replay_call("deploy_from_source_contract", arguments)
+    }
+
+    fn get_num_return_data(&self) -> i32 {
+        self.replay_call("get_num_return_data", Vec::new())
+    }
+
+    fn get_return_data_size(&self, result_id: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(result_id),
+        ];
+        self.replay_call("get_return_data_size", arguments)
+    }
+
+    fn get_return_data(&self, result_id: i32, data_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::I32(result_id),
+            HookValue::MemPtr(data_offset),
+        ];
+        self.replay_call("get_return_data", arguments)
+    }
+
+    fn clean_return_data(&self) {
+        self.replay_call("clean_return_data", Vec::new())
+    }
+
+    fn delete_from_return_data(&self, result_id: i32) {
+        let arguments = vec![
+            HookValue::I32(result_id),
+        ];
+        self.replay_call("delete_from_return_data", arguments)
+    }
+
+    fn get_original_tx_hash(&self, data_offset: MemPtr) {
+        let arguments = vec![
+            HookValue::MemPtr(data_offset),
+        ];
+        self.replay_call("get_original_tx_hash", arguments)
+    }
+
+    fn get_current_tx_hash(&self, data_offset: MemPtr) {
+        let arguments = vec![
+            HookValue::MemPtr(data_offset),
+        ];
+        self.replay_call("get_current_tx_hash", arguments)
+    }
+
+    fn get_prev_tx_hash(&self, data_offset: MemPtr) {
+        let arguments = vec![
+            HookValue::MemPtr(data_offset),
+        ];
+        self.replay_call("get_prev_tx_hash", arguments)
+    }
+
+    fn managed_sc_address(&self, destination_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+        ];
+        self.replay_call("managed_sc_address", arguments)
+    }
+
+    fn managed_owner_address(&self, destination_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+        ];
+        self.replay_call("managed_owner_address", arguments)
+    }
+
+    fn managed_caller(&self, destination_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+        ];
+        self.replay_call("managed_caller", arguments)
+    }
+
+    fn managed_signal_error(&self, err_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(err_handle),
+        ];
+        self.replay_call("managed_signal_error", arguments)
+    }
+
+    fn managed_write_log(&self, topics_handle: i32, data_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(topics_handle),
+            HookValue::I32(data_handle),
+        ];
+        self.replay_call("managed_write_log", arguments)
+    }
+
+    fn managed_get_original_tx_hash(&self, result_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(result_handle),
+        ];
+        self.replay_call("managed_get_original_tx_hash", arguments)
+    }
+
+    fn managed_get_state_root_hash(&self, result_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(result_handle),
+        ];
+        self.replay_call("managed_get_state_root_hash", arguments)
+    }
+
+    fn managed_get_block_random_seed(&self, result_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(result_handle),
+        ];
+        self.replay_call("managed_get_block_random_seed", arguments)
+    }
+
+    fn managed_get_prev_block_random_seed(&self, result_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(result_handle),
+        ];
+        self.replay_call("managed_get_prev_block_random_seed", arguments)
+    }
+
+    fn managed_get_return_data(&self, result_id: i32, result_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(result_id),
+            HookValue::I32(result_handle),
+        ];
+        self.replay_call("managed_get_return_data", arguments)
+    }
+
+    fn managed_get_kda_call_value(&self, kda_call_value_handle: i32, kda_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(kda_call_value_handle),
+            HookValue::I32(kda_handle),
+        ];
+        self.replay_call("managed_get_kda_call_value", arguments)
+    }
+
+    fn managed_get_multi_kda_call_value(&self, multi_call_value_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(multi_call_value_handle),
+        ];
+        self.replay_call("managed_get_multi_kda_call_value", arguments)
+    }
+
+    fn managed_get_back_transfers(&self, kda_transfers_value_handle: i32, call_value_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(kda_transfers_value_handle),
+            HookValue::I32(call_value_handle),
+        ];
+        self.replay_call("managed_get_back_transfers", arguments)
+    }
+
+    fn managed_get_kda_balance(&self, address_handle: i32, token_id_handle: i32, nonce: i64, value_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(address_handle),
+            HookValue::I32(token_id_handle),
+            HookValue::I64(nonce),
+            HookValue::I32(value_handle),
+        ];
+        self.replay_call("managed_get_kda_balance", arguments)
+    }
+
+    fn managed_get_user_kda(&self, address_handle: i32, ticker_handle: i32, nonce: i64, balance_handle: i32, frozen_handle: i32, last_claim_handle: i32, buckets_handle: i32, mime_handle: i32, metadata_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(address_handle),
+            HookValue::I32(ticker_handle),
+            HookValue::I64(nonce),
+            HookValue::I32(balance_handle),
+            HookValue::I32(frozen_handle),
+            HookValue::I32(last_claim_handle),
+            HookValue::I32(buckets_handle),
+            HookValue::I32(mime_handle),
+            HookValue::I32(metadata_handle),
+        ];
+        self.replay_call("managed_get_user_kda", arguments)
+    }
+
+    fn managed_get_kda_token_data(&self, address_handle: i32, ticker_handle: i32, nonce: i64, precision_handle: i32, id_handle: i32, name_handle: i32, creator_handle: i32, logo_handle: i32, uris_handle: i32, initial_supply_handle: i32, circulating_supply_handle: i32, max_supply_handle: i32, minted_handle: i32, burned_handle: i32, royalties_handle: i32, properties_handle: i32, attributes_handle: i32, roles_handle: i32, issue_date_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(address_handle),
+            HookValue::I32(ticker_handle),
+            HookValue::I64(nonce),
+            HookValue::I32(precision_handle),
+            HookValue::I32(id_handle),
+            HookValue::I32(name_handle),
+            HookValue::I32(creator_handle),
+            HookValue::I32(logo_handle),
+            HookValue::I32(uris_handle),
+            HookValue::I32(initial_supply_handle),
+            HookValue::I32(circulating_supply_handle),
+            HookValue::I32(max_supply_handle),
+            HookValue::I32(minted_handle),
+            HookValue::I32(burned_handle),
+            HookValue::I32(royalties_handle),
+            HookValue::I32(properties_handle),
+            HookValue::I32(attributes_handle),
+            HookValue::I32(roles_handle),
+            HookValue::I32(issue_date_handle),
+        ];
+        self.replay_call("managed_get_kda_token_data", arguments)
+    }
+
+    fn managed_get_kda_roles(&self, ticker_handle: i32, roles_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(ticker_handle),
+            HookValue::I32(roles_handle),
+        ];
+        self.replay_call("managed_get_kda_roles", arguments)
+    }
+
+    fn managed_upgrade_from_source_contract(&self, dest_handle: i32, gas: i64, value_handle: i32, address_handle: i32, code_metadata_handle: i32, arguments_handle: i32, result_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(dest_handle),
+            HookValue::I64(gas),
+            HookValue::I32(value_handle),
+            HookValue::I32(address_handle),
+            HookValue::I32(code_metadata_handle),
+            HookValue::I32(arguments_handle),
+            HookValue::I32(result_handle),
+        ];
+        self.replay_call("managed_upgrade_from_source_contract", arguments)
+    }
+
+    fn managed_upgrade_contract(&self, dest_handle: i32, gas: i64, value_handle: i32, code_handle: i32, code_metadata_handle: i32, arguments_handle: i32, result_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(dest_handle),
+            HookValue::I64(gas),
+            HookValue::I32(value_handle),
+            HookValue::I32(code_handle),
+            HookValue::I32(code_metadata_handle),
+            HookValue::I32(arguments_handle),
+            HookValue::I32(result_handle),
+        ];
+        self.replay_call("managed_upgrade_contract", arguments)
+    }
+
+    fn managed_delete_contract(&self, dest_handle: i32, gas_limit: i64, arguments_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(dest_handle),
+            HookValue::I64(gas_limit),
+            HookValue::I32(arguments_handle),
+        ];
+        self.replay_call("managed_delete_contract", arguments)
+    }
+
+    fn managed_deploy_from_source_contract(&self, gas: i64, value_handle: i32, address_handle: i32, code_metadata_handle: i32, arguments_handle: i32, result_address_handle: i32, result_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I64(gas),
+            HookValue::I32(value_handle),
+            HookValue::I32(address_handle),
+            HookValue::I32(code_metadata_handle),
+            HookValue::I32(arguments_handle),
+            HookValue::I32(result_address_handle),
+            HookValue::I32(result_handle),
+        ];
+        self.replay_call("managed_deploy_from_source_contract", arguments)
+    }
+
+    fn managed_create_contract(&self, gas: i64, value_handle: i32, code_handle: i32, code_metadata_handle: i32, arguments_handle: i32, result_address_handle: i32, result_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I64(gas),
+            HookValue::I32(value_handle),
+            HookValue::I32(code_handle),
+            HookValue::I32(code_metadata_handle),
+            HookValue::I32(arguments_handle),
+            HookValue::I32(result_address_handle),
+            HookValue::I32(result_handle),
+        ];
+        self.replay_call("managed_create_contract", arguments)
+    }
+
+    fn managed_execute_read_only(&self, gas: i64, address_handle: i32, function_handle: i32, arguments_handle: i32, result_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I64(gas),
+            HookValue::I32(address_handle),
+            HookValue::I32(function_handle),
+            HookValue::I32(arguments_handle),
+            HookValue::I32(result_handle),
+        ];
+        self.replay_call("managed_execute_read_only", arguments)
+    }
+
+    fn managed_execute_on_same_context(&self, gas: i64, address_handle: i32, value_handle: i32, function_handle: i32, arguments_handle: i32, result_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I64(gas),
+            HookValue::I32(address_handle),
+            HookValue::I32(value_handle),
+            HookValue::I32(function_handle),
+            HookValue::I32(arguments_handle),
+            HookValue::I32(result_handle),
+        ];
+        self.replay_call("managed_execute_on_same_context", arguments)
+    }
+
+    fn managed_execute_on_dest_context(&self, gas: i64, address_handle: i32, value_handle: i32, function_handle: i32, arguments_handle: i32, result_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I64(gas),
+            HookValue::I32(address_handle),
+            HookValue::I32(value_handle),
+            HookValue::I32(function_handle),
+            HookValue::I32(arguments_handle),
+            HookValue::I32(result_handle),
+        ];
+        self.replay_call("managed_execute_on_dest_context", arguments)
+    }
+
+    fn managed_multi_transfer_kda_nft_execute(&self, dst_handle: i32, token_transfers_handle: i32, gas_limit: i64, function_handle: i32, arguments_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(dst_handle),
+            HookValue::I32(token_transfers_handle),
+            HookValue::I64(gas_limit),
+            HookValue::I32(function_handle),
+            HookValue::I32(arguments_handle),
+        ];
+        self.replay_call("managed_multi_transfer_kda_nft_execute", arguments)
+    }
+
+    fn managed_buffer_to_hex(&self, source_handle: i32, dest_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(source_handle),
+            HookValue::I32(dest_handle),
+        ];
+        self.replay_call("managed_buffer_to_hex", arguments)
+    }
+
+    fn managed_get_code_metadata(&self, address_handle: i32, response_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(address_handle),
+            HookValue::I32(response_handle),
+        ];
+        self.replay_call("managed_get_code_metadata", arguments)
+    }
+
+    fn managed_is_builtin_function(&self, function_name_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(function_name_handle),
+        ];
+        self.replay_call("managed_is_builtin_function", arguments)
+    }
+
+    fn big_float_new_from_parts(&self, integral_part: i32, fractional_part: i32, exponent: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(integral_part),
+            HookValue::I32(fractional_part),
+            HookValue::I32(exponent),
+        ];
+        self.replay_call("big_float_new_from_parts", arguments)
+    }
+
+    fn big_float_new_from_frac(&self, numerator: i64, denominator: i64) -> i32 {
+        let arguments = vec![
+            HookValue::I64(numerator),
+            HookValue::I64(denominator),
+        ];
+        self.replay_call("big_float_new_from_frac", arguments)
+    }
+
+    fn big_float_new_from_sci(&self, significand: i64, exponent: i64) -> i32 {
+        let arguments = vec![
+            HookValue::I64(significand),
+            HookValue::I64(exponent),
+        ];
+        self.replay_call("big_float_new_from_sci", arguments)
+    }
+
+    fn big_float_add(&self, destination_handle: i32, op1_handle: i32, op2_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_float_add", arguments)
+    }
+
+    fn big_float_sub(&self, destination_handle: i32, op1_handle: i32, op2_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_float_sub", arguments)
+    }
+
+    fn big_float_mul(&self, destination_handle: i32, op1_handle: i32, op2_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_float_mul", arguments)
+    }
+
+    fn big_float_div(&self, destination_handle: i32, op1_handle: i32, op2_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_float_div", arguments)
+    }
+
+    fn big_float_neg(&self, destination_handle: i32, op_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op_handle),
+        ];
+        self.replay_call("big_float_neg", arguments)
+    }
+
+    fn big_float_clone(&self, destination_handle: i32, op_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op_handle),
+        ];
+        self.replay_call("big_float_clone", arguments)
+    }
+
+    fn big_float_cmp(&self, op1_handle: i32, op2_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_float_cmp", arguments)
+    }
+
+    fn big_float_abs(&self, destination_handle: i32, op_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op_handle),
+        ];
+        self.replay_call("big_float_abs", arguments)
+    }
+
+    fn big_float_sign(&self, op_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(op_handle),
+        ];
+        self.replay_call("big_float_sign", arguments)
+    }
+
+    fn big_float_sqrt(&self, destination_handle: i32, op_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op_handle),
+        ];
+        self.replay_call("big_float_sqrt", arguments)
+    }
+
+    fn big_float_pow(&self, destination_handle: i32, op_handle: i32, exponent: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op_handle),
+            HookValue::I32(exponent),
+        ];
+        self.replay_call("big_float_pow", arguments)
+    }
+
+    fn big_float_floor(&self, dest_big_int_handle: i32, op_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(dest_big_int_handle),
+            HookValue::I32(op_handle),
+        ];
+        self.replay_call("big_float_floor", arguments)
+    }
+
+    fn big_float_ceil(&self, dest_big_int_handle: i32, op_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(dest_big_int_handle),
+            HookValue::I32(op_handle),
+        ];
+        self.replay_call("big_float_ceil", arguments)
+    }
+
+    fn big_float_truncate(&self, dest_big_int_handle: i32, op_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(dest_big_int_handle),
+            HookValue::I32(op_handle),
+        ];
+        self.replay_call("big_float_truncate", arguments)
+    }
+
+    fn big_float_set_int64(&self, destination_handle: i32, value: i64) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I64(value),
+        ];
+        self.replay_call("big_float_set_int64", arguments)
+    }
+
+    fn big_float_is_int(&self, op_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(op_handle),
+        ];
+        self.replay_call("big_float_is_int", arguments)
+    }
+
+    fn big_float_set_big_int(&self, destination_handle: i32, big_int_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(big_int_handle),
+        ];
+        self.replay_call("big_float_set_big_int", arguments)
+    }
+
+    fn big_float_get_const_pi(&self, destination_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+        ];
+        self.replay_call("big_float_get_const_pi", arguments)
+    }
+
+    fn big_float_get_const_e(&self, destination_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+        ];
+        self.replay_call("big_float_get_const_e", arguments)
+    }
+
+    fn big_int_get_unsigned_argument(&self, id: i32, destination_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(id),
+            HookValue::I32(destination_handle),
+        ];
+        self.replay_call("big_int_get_unsigned_argument", arguments)
+    }
+
+    fn big_int_get_signed_argument(&self, id: i32, destination_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(id),
+            HookValue::I32(destination_handle),
+        ];
+        self.replay_call("big_int_get_signed_argument", arguments)
+    }
+
+    fn big_int_storage_store_unsigned(&self, key_offset: MemPtr, key_length: MemLength, source_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+            HookValue::I32(source_handle),
+        ];
+        self.replay_call("big_int_storage_store_unsigned", arguments)
+    }
+
+    fn big_int_storage_load_unsigned(&self, key_offset: MemPtr, key_length: MemLength, destination_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+            HookValue::I32(destination_handle),
+        ];
+        self.replay_call("big_int_storage_load_unsigned", arguments)
+    }
+
+    fn big_int_get_call_value(&self, destination_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+        ];
+        self.replay_call("big_int_get_call_value", arguments)
+    }
+
+    fn big_int_get_kda_call_value(&self, destination: i32) {
+        let arguments = vec![
+            HookValue::I32(destination),
+        ];
+        self.replay_call("big_int_get_kda_call_value", arguments)
+    }
+
+    fn big_int_get_kda_call_value_by_index(&self, destination_handle: i32, index: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(index),
+        ];
+        self.replay_call("big_int_get_kda_call_value_by_index", arguments)
+    }
+
+    fn big_int_get_external_balance(&self, address_offset: MemPtr, result: i32) {
+        let arguments = vec![
+            HookValue::MemPtr(address_offset),
+            HookValue::I32(result),
+        ];
+        self.replay_call("big_int_get_external_balance", arguments)
+    }
+
+    fn big_int_get_kda_external_balance(&self, address_offset: MemPtr, token_id_offset: MemPtr, token_id_len: MemLength, nonce: i64, result_handle: i32) {
+        let arguments = vec![
+            HookValue::MemPtr(address_offset),
+            HookValue::MemPtr(token_id_offset),
+            HookValue::MemLength(token_id_len),
+            HookValue::I64(nonce),
+            HookValue::I32(result_handle),
+        ];
+        self.replay_call("big_int_get_kda_external_balance", arguments)
+    }
+
+    fn big_int_new(&self, small_value: i64) -> i32 {
+        let arguments = vec![
+            HookValue::I64(small_value),
+        ];
+        self.replay_call("big_int_new", arguments)
+    }
+
+    fn big_int_unsigned_byte_length(&self, reference_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(reference_handle),
+        ];
+        self.replay_call("big_int_unsigned_byte_length", arguments)
+    }
+
+    fn big_int_signed_byte_length(&self, reference_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(reference_handle),
+        ];
+        self.replay_call("big_int_signed_byte_length", arguments)
+    }
+
+    fn big_int_get_unsigned_bytes(&self, reference_handle: i32, byte_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::I32(reference_handle),
+            HookValue::MemPtr(byte_offset),
+        ];
+        self.replay_call("big_int_get_unsigned_bytes", arguments)
+    }
+
+    fn big_int_get_signed_bytes(&self, reference_handle: i32, byte_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::I32(reference_handle),
+            HookValue::MemPtr(byte_offset),
+        ];
+        self.replay_call("big_int_get_signed_bytes", arguments)
+    }
+
+    fn big_int_set_unsigned_bytes(&self, destination_handle: i32, byte_offset: MemPtr, byte_length: MemLength) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::MemPtr(byte_offset),
+            HookValue::MemLength(byte_length),
+        ];
+        self.replay_call("big_int_set_unsigned_bytes", arguments)
+    }
+
+    fn big_int_set_signed_bytes(&self, destination_handle: i32, byte_offset: MemPtr, byte_length: MemLength) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::MemPtr(byte_offset),
+            HookValue::MemLength(byte_length),
+        ];
+        self.replay_call("big_int_set_signed_bytes", arguments)
+    }
+
+    fn big_int_is_int64(&self, destination_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+        ];
+        self.replay_call("big_int_is_int64", arguments)
+    }
+
+    fn big_int_get_int64(&self, destination_handle: i32) -> i64 {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+        ];
+        self.replay_call("big_int_get_int64", arguments)
+    }
+
+    fn big_int_set_int64(&self, destination_handle: i32, value: i64) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I64(value),
+        ];
+        self.replay_call("big_int_set_int64", arguments)
+    }
+
+    fn big_int_add(&self, destination_handle: i32, op1_handle: i32, op2_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_int_add", arguments)
+    }
+
+    fn big_int_sub(&self, destination_handle: i32, op1_handle: i32, op2_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_int_sub", arguments)
+    }
+
+    fn big_int_mul(&self, destination_handle: i32, op1_handle: i32, op2_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_int_mul", arguments)
+    }
+
+    fn big_int_tdiv(&self, destination_handle: i32, op1_handle: i32, op2_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_int_tdiv", arguments)
+    }
+
+    fn big_int_tmod(&self, destination_handle: i32, op1_handle: i32, op2_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_int_tmod", arguments)
+    }
+
+    fn big_int_ediv(&self, destination_handle: i32, op1_handle: i32, op2_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_int_ediv", arguments)
+    }
+
+    fn big_int_emod(&self, destination_handle: i32, op1_handle: i32, op2_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_int_emod", arguments)
+    }
+
+    fn big_int_sqrt(&self, destination_handle: i32, op_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op_handle),
+        ];
+        self.replay_call("big_int_sqrt", arguments)
+    }
+
+    fn big_int_pow(&self, destination_handle: i32, op1_handle: i32, op2_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_int_pow", arguments)
+    }
+
+    fn big_int_log2(&self, op1_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(op1_handle),
+        ];
+        self.replay_call("big_int_log2", arguments)
+    }
+
+    fn big_int_abs(&self, destination_handle: i32, op_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op_handle),
+        ];
+        self.replay_call("big_int_abs", arguments)
+    }
+
+    fn big_int_neg(&self, destination_handle: i32, op_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op_handle),
+        ];
+        self.replay_call("big_int_neg", arguments)
+    }
+
+    fn big_int_sign(&self, op_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(op_handle),
+        ];
+        self.replay_call("big_int_sign", arguments)
+    }
+
+    fn big_int_cmp(&self, op1_handle: i32, op2_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_int_cmp", arguments)
+    }
+
+    fn big_int_not(&self, destination_handle: i32, op_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op_handle),
+        ];
+        self.replay_call("big_int_not", arguments)
+    }
+
+    fn big_int_and(&self, destination_handle: i32, op1_handle: i32, op2_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_int_and", arguments)
+    }
+
+    fn big_int_or(&self, destination_handle: i32, op1_handle: i32, op2_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_int_or", arguments)
+    }
+
+    fn big_int_xor(&self, destination_handle: i32, op1_handle: i32, op2_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op1_handle),
+            HookValue::I32(op2_handle),
+        ];
+        self.replay_call("big_int_xor", arguments)
+    }
+
+    fn big_int_shr(&self, destination_handle: i32, op_handle: i32, bits: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op_handle),
+            HookValue::I32(bits),
+        ];
+        self.replay_call("big_int_shr", arguments)
+    }
+
+    fn big_int_shl(&self, destination_handle: i32, op_handle: i32, bits: i32) {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(op_handle),
+            HookValue::I32(bits),
+        ];
+        self.replay_call("big_int_shl", arguments)
+    }
+
+    fn big_int_finish_unsigned(&self, reference_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(reference_handle),
+        ];
+        self.replay_call("big_int_finish_unsigned", arguments)
+    }
+
+    fn big_int_finish_signed(&self, reference_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(reference_handle),
+        ];
+        self.replay_call("big_int_finish_signed", arguments)
+    }
+
+    fn big_int_to_string(&self, big_int_handle: i32, destination_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(big_int_handle),
+            HookValue::I32(destination_handle),
+        ];
+        self.replay_call("big_int_to_string", arguments)
+    }
+
+    fn mbuffer_new(&self) -> i32 {
+        self.replay_call("mbuffer_new", Vec::new())
+    }
+
+    fn mbuffer_new_from_bytes(&self, data_offset: MemPtr, data_length: MemLength) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(data_offset),
+            HookValue::MemLength(data_length),
+        ];
+        self.replay_call("mbuffer_new_from_bytes", arguments)
+    }
+
+    fn mbuffer_get_length(&self, m_buffer_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(m_buffer_handle),
+        ];
+        self.replay_call("mbuffer_get_length", arguments)
+    }
+
+    fn mbuffer_get_bytes(&self, m_buffer_handle: i32, result_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::I32(m_buffer_handle),
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("mbuffer_get_bytes", arguments)
+    }
+
+    fn mbuffer_get_byte_slice(&self, source_handle: i32, starting_position: i32, slice_length: i32, result_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::I32(source_handle),
+            HookValue::I32(starting_position),
+            HookValue::I32(slice_length),
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("mbuffer_get_byte_slice", arguments)
+    }
+
+    fn mbuffer_copy_byte_slice(&self, source_handle: i32, starting_position: i32, slice_length: i32, destination_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(source_handle),
+            HookValue::I32(starting_position),
+            HookValue::I32(slice_length),
+            HookValue::I32(destination_handle),
+        ];
+        self.replay_call("mbuffer_copy_byte_slice", arguments)
+    }
+
+    fn mbuffer_eq(&self, m_buffer_handle1: i32, m_buffer_handle2: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(m_buffer_handle1),
+            HookValue::I32(m_buffer_handle2),
+        ];
+        self.replay_call("mbuffer_eq", arguments)
+    }
+
+    fn mbuffer_set_bytes(&self, m_buffer_handle: i32, data_offset: MemPtr, data_length: MemLength) -> i32 {
+        let arguments = vec![
+            HookValue::I32(m_buffer_handle),
+            HookValue::MemPtr(data_offset),
+            HookValue::MemLength(data_length),
+        ];
+        self.replay_call("mbuffer_set_bytes", arguments)
+    }
+
+    fn mbuffer_set_byte_slice(&self, m_buffer_handle: i32, starting_position: i32, data_length: MemLength, data_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::I32(m_buffer_handle),
+            HookValue::I32(starting_position),
+            HookValue::MemLength(data_length),
+            HookValue::MemPtr(data_offset),
+        ];
+        self.replay_call("mbuffer_set_byte_slice", arguments)
+    }
+
+    fn mbuffer_append(&self, accumulator_handle: i32, data_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(accumulator_handle),
+            HookValue::I32(data_handle),
+        ];
+        self.replay_call("mbuffer_append", arguments)
+    }
+
+    fn mbuffer_append_bytes(&self, accumulator_handle: i32, data_offset: MemPtr, data_length: MemLength) -> i32 {
+        let arguments = vec![
+            HookValue::I32(accumulator_handle),
+            HookValue::MemPtr(data_offset),
+            HookValue::MemLength(data_length),
+        ];
+        self.replay_call("mbuffer_append_bytes", arguments)
+    }
+
+    fn mbuffer_to_big_int_unsigned(&self, m_buffer_handle: i32, big_int_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(m_buffer_handle),
+            HookValue::I32(big_int_handle),
+        ];
+        self.replay_call("mbuffer_to_big_int_unsigned", arguments)
+    }
+
+    fn mbuffer_to_big_int_signed(&self, m_buffer_handle: i32, big_int_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(m_buffer_handle),
+            HookValue::I32(big_int_handle),
+        ];
+        self.replay_call("mbuffer_to_big_int_signed", arguments)
+    }
+
+    fn mbuffer_from_big_int_unsigned(&self, m_buffer_handle: i32, big_int_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(m_buffer_handle),
+            HookValue::I32(big_int_handle),
+        ];
+        self.replay_call("mbuffer_from_big_int_unsigned", arguments)
+    }
+
+    fn mbuffer_from_big_int_signed(&self, m_buffer_handle: i32, big_int_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(m_buffer_handle),
+            HookValue::I32(big_int_handle),
+        ];
+        self.replay_call("mbuffer_from_big_int_signed", arguments)
+    }
+
+    fn mbuffer_to_big_float(&self, m_buffer_handle: i32, big_float_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(m_buffer_handle),
+            HookValue::I32(big_float_handle),
+        ];
+        self.replay_call("mbuffer_to_big_float", arguments)
+    }
+
+    fn mbuffer_from_big_float(&self, m_buffer_handle: i32, big_float_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(m_buffer_handle),
+            HookValue::I32(big_float_handle),
+        ];
+        self.replay_call("mbuffer_from_big_float", arguments)
+    }
+
+    fn mbuffer_storage_store(&self, key_handle: i32, source_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(key_handle),
+            HookValue::I32(source_handle),
+        ];
+        self.replay_call("mbuffer_storage_store", arguments)
+    }
+
+    fn mbuffer_storage_load(&self, key_handle: i32, destination_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(key_handle),
+            HookValue::I32(destination_handle),
+        ];
+        self.replay_call("mbuffer_storage_load", arguments)
+    }
+
+    fn mbuffer_storage_load_from_address(&self, address_handle: i32, key_handle: i32, destination_handle: i32) {
+        let arguments = vec![
+            HookValue::I32(address_handle),
+            HookValue::I32(key_handle),
+            HookValue::I32(destination_handle),
+        ];
+        self.replay_call("mbuffer_storage_load_from_address", arguments)
+    }
+
+    fn mbuffer_get_argument(&self, id: i32, destination_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(id),
+            HookValue::I32(destination_handle),
+        ];
+        self.replay_call("mbuffer_get_argument", arguments)
+    }
+
+    fn mbuffer_finish(&self, source_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(source_handle),
+        ];
+        self.replay_call("mbuffer_finish", arguments)
+    }
+
+    fn mbuffer_set_random(&self, destination_handle: i32, length: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(destination_handle),
+            HookValue::I32(length),
+        ];
+        self.replay_call("mbuffer_set_random", arguments)
+    }
+
+    fn managed_map_new(&self) -> i32 {
+        self.replay_call("managed_map_new", Vec::new())
+    }
+
+    fn managed_map_put(&self, m_map_handle: i32, key_handle: i32, value_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(m_map_handle),
+            HookValue::I32(key_handle),
+            HookValue::I32(value_handle),
+        ];
+        self.replay_call("managed_map_put", arguments)
+    }
+
+    fn managed_map_get(&self, m_map_handle: i32, key_handle: i32, out_value_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(m_map_handle),
+            HookValue::I32(key_handle),
+            HookValue::I32(out_value_handle),
+        ];
+        self.replay_call("managed_map_get", arguments)
+    }
+
+    fn managed_map_remove(&self, m_map_handle: i32, key_handle: i32, out_value_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(m_map_handle),
+            HookValue::I32(key_handle),
+            HookValue::I32(out_value_handle),
+        ];
+        self.replay_call("managed_map_remove", arguments)
+    }
+
+    fn managed_map_contains(&self, m_map_handle: i32, key_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(m_map_handle),
+            HookValue::I32(key_handle),
+        ];
+        self.replay_call("managed_map_contains", arguments)
+    }
+
+    fn small_int_get_unsigned_argument(&self, id: i32) -> i64 {
+        let arguments = vec![
+            HookValue::I32(id),
+        ];
+        self.replay_call("small_int_get_unsigned_argument", arguments)
+    }
+
+    fn small_int_get_signed_argument(&self, id: i32) -> i64 {
+        let arguments = vec![
+            HookValue::I32(id),
+        ];
+        self.replay_call("small_int_get_signed_argument", arguments)
+    }
+
+    fn small_int_finish_unsigned(&self, value: i64) {
+        let arguments = vec![
+            HookValue::I64(value),
+        ];
+        self.replay_call("small_int_finish_unsigned", arguments)
+    }
+
+    fn small_int_finish_signed(&self, value: i64) {
+        let arguments = vec![
+            HookValue::I64(value),
+        ];
+        self.replay_call("small_int_finish_signed", arguments)
+    }
+
+    fn small_int_storage_store_unsigned(&self, key_offset: MemPtr, key_length: MemLength, value: i64) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+            HookValue::I64(value),
+        ];
+        self.replay_call("small_int_storage_store_unsigned", arguments)
+    }
+
+    fn small_int_storage_store_signed(&self, key_offset: MemPtr, key_length: MemLength, value: i64) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+            HookValue::I64(value),
+        ];
+        self.replay_call("small_int_storage_store_signed", arguments)
+    }
+
+    fn small_int_storage_load_unsigned(&self, key_offset: MemPtr, key_length: MemLength) -> i64 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+        ];
+        self.replay_call("small_int_storage_load_unsigned", arguments)
+    }
+
+    fn small_int_storage_load_signed(&self, key_offset: MemPtr, key_length: MemLength) -> i64 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+        ];
+        self.replay_call("small_int_storage_load_signed", arguments)
+    }
+
+    fn int64get_argument(&self, id: i32) -> i64 {
+        let arguments = vec![
+            HookValue::I32(id),
+        ];
+        self.replay_call("int64get_argument", arguments)
+    }
+
+    fn int64finish(&self, value: i64) {
+        let arguments = vec![
+            HookValue::I64(value),
+        ];
+        self.replay_call("int64finish", arguments)
+    }
+
+    fn int64storage_store(&self, key_offset: MemPtr, key_length: MemLength, value: i64) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+            HookValue::I64(value),
+        ];
+        self.replay_call("int64storage_store", arguments)
+    }
+
+    fn int64storage_load(&self, key_offset: MemPtr, key_length: MemLength) -> i64 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+        ];
+        self.replay_call("int64storage_load", arguments)
+    }
+
+    fn sha256(&self, data_offset: MemPtr, length: MemLength, result_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(data_offset),
+            HookValue::MemLength(length),
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("sha256", arguments)
+    }
+
+    fn managed_sha256(&self, input_handle: i32, output_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(input_handle),
+            HookValue::I32(output_handle),
+        ];
+        self.replay_call("managed_sha256", arguments)
+    }
+
+    fn keccak256(&self, data_offset: MemPtr, length: MemLength, result_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(data_offset),
+            HookValue::MemLength(length),
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("keccak256", arguments)
+    }
+
+    fn managed_keccak256(&self, input_handle: i32, output_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(input_handle),
+            HookValue::I32(output_handle),
+        ];
+        self.replay_call("managed_keccak256", arguments)
+    }
+
+    fn ripemd160(&self, data_offset: MemPtr, length: MemLength, result_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(data_offset),
+            HookValue::MemLength(length),
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("ripemd160", arguments)
+    }
+
+    fn managed_ripemd160(&self, input_handle: i32, output_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(input_handle),
+            HookValue::I32(output_handle),
+        ];
+        self.replay_call("managed_ripemd160", arguments)
+    }
+
+    fn verify_bls(&self, key_offset: MemPtr, message_offset: MemPtr, message_length: MemLength, sig_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemPtr(message_offset),
+            HookValue::MemLength(message_length),
+            HookValue::MemPtr(sig_offset),
+        ];
+        self.replay_call("verify_bls", arguments)
+    }
+
+    fn managed_verify_bls(&self, key_handle: i32, message_handle: i32, sig_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(key_handle),
+            HookValue::I32(message_handle),
+            HookValue::I32(sig_handle),
+        ];
+        self.replay_call("managed_verify_bls", arguments)
+    }
+
+    fn verify_ed25519(&self, key_offset: MemPtr, message_offset: MemPtr, message_length: MemLength, sig_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemPtr(message_offset),
+            HookValue::MemLength(message_length),
+            HookValue::MemPtr(sig_offset),
+        ];
+        self.replay_call("verify_ed25519", arguments)
+    }
+
+    fn managed_verify_ed25519(&self, key_handle: i32, message_handle: i32, sig_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(key_handle),
+            HookValue::I32(message_handle),
+            HookValue::I32(sig_handle),
+        ];
+        self.replay_call("managed_verify_ed25519", arguments)
+    }
+
+    fn verify_custom_secp256k1(&self, key_offset: MemPtr, key_length: MemLength, message_offset: MemPtr, message_length: MemLength, sig_offset: MemPtr, hash_type: i32) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+            HookValue::MemPtr(message_offset),
+            HookValue::MemLength(message_length),
+            HookValue::MemPtr(sig_offset),
+            HookValue::I32(hash_type),
+        ];
+        self.replay_call("verify_custom_secp256k1", arguments)
+    }
+
+    fn managed_verify_custom_secp256k1(&self, key_handle: i32, message_handle: i32, sig_handle: i32, hash_type: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(key_handle),
+            HookValue::I32(message_handle),
+            HookValue::I32(sig_handle),
+            HookValue::I32(hash_type),
+        ];
+        self.replay_call("managed_verify_custom_secp256k1", arguments)
+    }
+
+    fn verify_secp256k1(&self, key_offset: MemPtr, key_length: MemLength, message_offset: MemPtr, message_length: MemLength, sig_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(key_offset),
+            HookValue::MemLength(key_length),
+            HookValue::MemPtr(message_offset),
+            HookValue::MemLength(message_length),
+            HookValue::MemPtr(sig_offset),
+        ];
+        self.replay_call("verify_secp256k1", arguments)
+    }
+
+    fn managed_verify_secp256k1(&self, key_handle: i32, message_handle: i32, sig_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(key_handle),
+            HookValue::I32(message_handle),
+            HookValue::I32(sig_handle),
+        ];
+        self.replay_call("managed_verify_secp256k1", arguments)
+    }
+
+    fn encode_secp256k1_der_signature(&self, r_offset: MemPtr, r_length: MemLength, s_offset: MemPtr, s_length: MemLength, sig_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(r_offset),
+            HookValue::MemLength(r_length),
+            HookValue::MemPtr(s_offset),
+            HookValue::MemLength(s_length),
+            HookValue::MemPtr(sig_offset),
+        ];
+        self.replay_call("encode_secp256k1_der_signature", arguments)
+    }
+
+    fn managed_encode_secp256k1_der_signature(&self, r_handle: i32, s_handle: i32, sig_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(r_handle),
+            HookValue::I32(s_handle),
+            HookValue::I32(sig_handle),
+        ];
+        self.replay_call("managed_encode_secp256k1_der_signature", arguments)
+    }
+
+    fn add_ec(&self, x_result_handle: i32, y_result_handle: i32, ec_handle: i32, fst_point_xhandle: i32, fst_point_yhandle: i32, snd_point_xhandle: i32, snd_point_yhandle: i32) {
+        let arguments = vec![
+            HookValue::I32(x_result_handle),
+            HookValue::I32(y_result_handle),
+            HookValue::I32(ec_handle),
+            HookValue::I32(fst_point_xhandle),
+            HookValue::I32(fst_point_yhandle),
+            HookValue::I32(snd_point_xhandle),
+            HookValue::I32(snd_point_yhandle),
+        ];
+        self.replay_call("add_ec", arguments)
+    }
+
+    fn double_ec(&self, x_result_handle: i32, y_result_handle: i32, ec_handle: i32, point_xhandle: i32, point_yhandle: i32) {
+        let arguments = vec![
+            HookValue::I32(x_result_handle),
+            HookValue::I32(y_result_handle),
+            HookValue::I32(ec_handle),
+            HookValue::I32(point_xhandle),
+            HookValue::I32(point_yhandle),
+        ];
+        self.replay_call("double_ec", arguments)
+    }
+
+    fn is_on_curve_ec(&self, ec_handle: i32, point_xhandle: i32, point_yhandle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(ec_handle),
+            HookValue::I32(point_xhandle),
+            HookValue::I32(point_yhandle),
+        ];
+        self.replay_call("is_on_curve_ec", arguments)
+    }
+
+    fn scalar_base_mult_ec(&self, x_result_handle: i32, y_result_handle: i32, ec_handle: i32, data_offset: MemPtr, length: MemLength) -> i32 {
+        let arguments = vec![
+            HookValue::I32(x_result_handle),
+            HookValue::I32(y_result_handle),
+            HookValue::I32(ec_handle),
+            HookValue::MemPtr(data_offset),
+            HookValue::MemLength(length),
+        ];
+        self.replay_call("scalar_base_mult_ec", arguments)
+    }
+
+    fn managed_scalar_base_mult_ec(&self, x_result_handle: i32, y_result_handle: i32, ec_handle: i32, data_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(x_result_handle),
+            HookValue::I32(y_result_handle),
+            HookValue::I32(ec_handle),
+            HookValue::I32(data_handle),
+        ];
+        self.replay_call("managed_scalar_base_mult_ec", arguments)
+    }
+
+    fn scalar_mult_ec(&self, x_result_handle: i32, y_result_handle: i32, ec_handle: i32, point_xhandle: i32, point_yhandle: i32, data_offset: MemPtr, length: MemLength) -> i32 {
+        let arguments = vec![
+            HookValue::I32(x_result_handle),
+            HookValue::I32(y_result_handle),
+            HookValue::I32(ec_handle),
+            HookValue::I32(point_xhandle),
+            HookValue::I32(point_yhandle),
+            HookValue::MemPtr(data_offset),
+            HookValue::MemLength(length),
+        ];
+        self.replay_call("scalar_mult_ec", arguments)
+    }
+
+    fn managed_scalar_mult_ec(&self, x_result_handle: i32, y_result_handle: i32, ec_handle: i32, point_xhandle: i32, point_yhandle: i32, data_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(x_result_handle),
+            HookValue::I32(y_result_handle),
+            HookValue::I32(ec_handle),
+            HookValue::I32(point_xhandle),
+            HookValue::I32(point_yhandle),
+            HookValue::I32(data_handle),
+        ];
+        self.replay_call("managed_scalar_mult_ec", arguments)
+    }
+
+    fn marshal_ec(&self, x_pair_handle: i32, y_pair_handle: i32, ec_handle: i32, result_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::I32(x_pair_handle),
+            HookValue::I32(y_pair_handle),
+            HookValue::I32(ec_handle),
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("marshal_ec", arguments)
+    }
+
+    fn managed_marshal_ec(&self, x_pair_handle: i32, y_pair_handle: i32, ec_handle: i32, result_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(x_pair_handle),
+            HookValue::I32(y_pair_handle),
+            HookValue::I32(ec_handle),
+            HookValue::I32(result_handle),
+        ];
+        self.replay_call("managed_marshal_ec", arguments)
+    }
+
+    fn marshal_compressed_ec(&self, x_pair_handle: i32, y_pair_handle: i32, ec_handle: i32, result_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::I32(x_pair_handle),
+            HookValue::I32(y_pair_handle),
+            HookValue::I32(ec_handle),
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("marshal_compressed_ec", arguments)
+    }
+
+    fn managed_marshal_compressed_ec(&self, x_pair_handle: i32, y_pair_handle: i32, ec_handle: i32, result_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(x_pair_handle),
+            HookValue::I32(y_pair_handle),
+            HookValue::I32(ec_handle),
+            HookValue::I32(result_handle),
+        ];
+        self.replay_call("managed_marshal_compressed_ec", arguments)
+    }
+
+    fn unmarshal_ec(&self, x_result_handle: i32, y_result_handle: i32, ec_handle: i32, data_offset: MemPtr, length: MemLength) -> i32 {
+        let arguments = vec![
+            HookValue::I32(x_result_handle),
+            HookValue::I32(y_result_handle),
+            HookValue::I32(ec_handle),
+            HookValue::MemPtr(data_offset),
+            HookValue::MemLength(length),
+        ];
+        self.replay_call("unmarshal_ec", arguments)
+    }
+
+    fn managed_unmarshal_ec(&self, x_result_handle: i32, y_result_handle: i32, ec_handle: i32, data_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(x_result_handle),
+            HookValue::I32(y_result_handle),
+            HookValue::I32(ec_handle),
+            HookValue::I32(data_handle),
+        ];
+        self.replay_call("managed_unmarshal_ec", arguments)
+    }
+
+    fn unmarshal_compressed_ec(&self, x_result_handle: i32, y_result_handle: i32, ec_handle: i32, data_offset: MemPtr, length: MemLength) -> i32 {
+        let arguments = vec![
+            HookValue::I32(x_result_handle),
+            HookValue::I32(y_result_handle),
+            HookValue::I32(ec_handle),
+            HookValue::MemPtr(data_offset),
+            HookValue::MemLength(length),
+        ];
+        self.replay_call("unmarshal_compressed_ec", arguments)
+    }
+
+    fn managed_unmarshal_compressed_ec(&self, x_result_handle: i32, y_result_handle: i32, ec_handle: i32, data_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(x_result_handle),
+            HookValue::I32(y_result_handle),
+            HookValue::I32(ec_handle),
+            HookValue::I32(data_handle),
+        ];
+        self.replay_call("managed_unmarshal_compressed_ec", arguments)
+    }
+
+    fn generate_key_ec(&self, x_pub_key_handle: i32, y_pub_key_handle: i32, ec_handle: i32, result_offset: MemPtr) -> i32 {
+        let arguments = vec![
+            HookValue::I32(x_pub_key_handle),
+            HookValue::I32(y_pub_key_handle),
+            HookValue::I32(ec_handle),
+            HookValue::MemPtr(result_offset),
+        ];
+        self.replay_call("generate_key_ec", arguments)
+    }
+
+    fn managed_generate_key_ec(&self, x_pub_key_handle: i32, y_pub_key_handle: i32, ec_handle: i32, result_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(x_pub_key_handle),
+            HookValue::I32(y_pub_key_handle),
+            HookValue::I32(ec_handle),
+            HookValue::I32(result_handle),
+        ];
+        self.replay_call("managed_generate_key_ec", arguments)
+    }
+
+    fn create_ec(&self, data_offset: MemPtr, data_length: MemLength) -> i32 {
+        let arguments = vec![
+            HookValue::MemPtr(data_offset),
+            HookValue::MemLength(data_length),
+        ];
+        self.replay_call("create_ec", arguments)
+    }
+
+    fn managed_create_ec(&self, data_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(data_handle),
+        ];
+        self.replay_call("managed_create_ec", arguments)
+    }
+
+    fn get_curve_length_ec(&self, ec_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(ec_handle),
+        ];
+        self.replay_call("get_curve_length_ec", arguments)
+    }
+
+    fn get_priv_key_byte_length_ec(&self, ec_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(ec_handle),
+        ];
+        self.replay_call("get_priv_key_byte_length_ec", arguments)
+    }
+
+    fn elliptic_curve_get_values(&self, ec_handle: i32, field_order_handle: i32, base_point_order_handle: i32, eq_constant_handle: i32, x_base_point_handle: i32, y_base_point_handle: i32) -> i32 {
+        let arguments = vec![
+            HookValue::I32(ec_handle),
+            HookValue::I32(field_order_handle),
+            HookValue::I32(base_point_order_handle),
+            HookValue::I32(eq_constant_handle),
+            HookValue::I32(x_base_point_handle),
+            HookValue::I32(y_base_point_handle),
+        ];
+        self.replay_call("elliptic_curve_get_values", arguments)
+    }
+}
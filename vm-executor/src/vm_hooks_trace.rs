@@ -0,0 +1,292 @@
+use log::trace;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::{with_current_instance, ExecutorError, Instance, MemLength, MemPtr, VMHooks};
+
+/// An argument or the result of a VM hook call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HookValue {
+    I32(i32),
+    I64(i64),
+    MemPtr(MemPtr),
+    MemLength(MemLength),
+}
+
+impl fmt::Display for HookValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HookValue::I32(value) => write!(f, "{value}"),
+            HookValue::I64(value) => write!(f, "{value}"),
+            HookValue::MemPtr(value) => write!(f, "{value}"),
+            HookValue::MemLength(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// The return types of the VM hooks.
+pub(crate) trait HookResult {
+    fn hook_value(&self) -> Option<HookValue>;
+
+    /// Zero if the value is missing, or of another type.
+    fn from_hook_value(value: Option<HookValue>) -> Self;
+}
+
+impl HookResult for () {
+    fn hook_value(&self) -> Option<HookValue> {
+        None
+    }
+
+    fn from_hook_value(_value: Option<HookValue>) -> Self {}
+}
+
+impl HookResult for i32 {
+    fn hook_value(&self) -> Option<HookValue> {
+        Some(HookValue::I32(*self))
+    }
+
+    fn from_hook_value(value: Option<HookValue>) -> Self {
+        match value {
+            Some(HookValue::I32(value)) => value,
+            _ => 0,
+        }
+    }
+}
+
+impl HookResult for i64 {
+    fn hook_value(&self) -> Option<HookValue> {
+        Some(HookValue::I64(*self))
+    }
+
+    fn from_hook_value(value: Option<HookValue>) -> Self {
+        match value {
+            Some(HookValue::I64(value)) => value,
+            _ => 0,
+        }
+    }
+}
+
+/// An argument of a VM hook call, named like the parameter of the `VMHooks` method.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HookArgument {
+    pub name: &'static str,
+    pub value: HookValue,
+}
+
+impl HookArgument {
+    pub fn new(name: &'static str, value: HookValue) -> Self {
+        Self { name, value }
+    }
+}
+
+/// Bytes written by a VM hook into the memory of the calling instance.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryWrite {
+    pub offset: MemPtr,
+
+    /// Hex encoded in recordings, to keep them readable.
+    #[serde(with = "hex_bytes")]
+    pub bytes: Vec<u8>,
+}
+
+/// Finds the bytes that differ between two copies of a memory, one write per run of bytes.
+///
+/// Bytes past the end of `before` count as written, should the memory have grown.
+pub(crate) fn memory_writes(before: &[u8], after: &[u8]) -> Vec<MemoryWrite> {
+    let mut memory_writes: Vec<MemoryWrite> = Vec::new();
+    for (offset, byte) in after.iter().enumerate() {
+        if before.get(offset) == Some(byte) {
+            continue;
+        }
+        match memory_writes.last_mut() {
+            Some(last) if last.offset as usize + last.bytes.len() == offset => {
+                last.bytes.push(*byte);
+            }
+            _ => memory_writes.push(MemoryWrite {
+                offset: offset as MemPtr,
+                bytes: vec![*byte],
+            }),
+        }
+    }
+    memory_writes
+}
+
+/// A copy of the memory of an instance, `None` if it cannot be read.
+pub(crate) fn memory_copy(instance: &dyn Instance) -> Option<Vec<u8>> {
+    let memory_length = instance.memory_length().ok()?;
+    memory_bytes(instance, 0, memory_length as MemLength).ok()
+}
+
+/// Copies `length` bytes of the memory of an instance.
+pub(crate) fn memory_bytes(
+    instance: &dyn Instance,
+    offset: MemPtr,
+    length: MemLength,
+) -> Result<Vec<u8>, ExecutorError> {
+    if length == 0 {
+        return Ok(Vec::new());
+    }
+    // `memory_load` includes the byte at `mem_ptr + mem_length`
+    instance.memory_load(offset, length - 1).map(<[u8]>::to_vec)
+}
+
+mod hex_bytes {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+        serializer.serialize_str(&hex)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        if hex.len() % 2 != 0 {
+            return Err(D::Error::custom("odd number of hex digits"));
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(D::Error::custom))
+            .collect()
+    }
+}
+
+/// One VM hook call, as recorded by `TracingVMHooks`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HookCall {
+    /// The name of the `VMHooks` method, e.g. `get_num_arguments`.
+    pub name: &'static str,
+    pub arguments: Vec<HookArgument>,
+
+    /// `None` for the hooks that return nothing.
+    pub result: Option<HookValue>,
+
+    /// The wall time spent in the hook.
+    pub duration: Duration,
+
+    /// Only captured for the sinks that ask for them, see `HookCallSink::records_memory_writes`.
+    pub memory_writes: Vec<MemoryWrite>,
+}
+
+impl fmt::Display for HookCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(", self.name)?;
+        for (i, argument) in self.arguments.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}", argument.name, argument.value)?;
+        }
+        write!(f, ")")?;
+        if let Some(result) = &self.result {
+            write!(f, " -> {result}")?;
+        }
+        write!(f, " in {:?}", self.duration)
+    }
+}
+
+/// Receives the hook calls recorded by `TracingVMHooks`, in the order they were made.
+///
+/// The hooks of an executor are shared by all its instances, which may run on several threads.
+pub trait HookCallSink: fmt::Debug + Send + Sync {
+    fn record(&self, hook_call: HookCall);
+
+    /// Whether the bytes each hook writes into the memory of the calling instance should be
+    /// captured. This copies the whole memory before each hook call, so it is off by default.
+    fn records_memory_writes(&self) -> bool {
+        false
+    }
+}
+
+/// Keeps the hook calls in memory.
+///
+/// Clones share the same calls, so a clone can be kept to read the calls recorded by the sink
+/// given to `TracingVMHooks`.
+#[derive(Clone, Debug, Default)]
+pub struct HookCallLog {
+    hook_calls: Arc<Mutex<Vec<HookCall>>>,
+}
+
+impl HookCallLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The calls recorded so far.
+    pub fn hook_calls(&self) -> Vec<HookCall> {
+        self.hook_calls.lock().unwrap().clone()
+    }
+
+    /// Returns the calls recorded so far, and forgets them.
+    pub fn take(&self) -> Vec<HookCall> {
+        std::mem::take(&mut *self.hook_calls.lock().unwrap())
+    }
+}
+
+impl HookCallSink for HookCallLog {
+    fn record(&self, hook_call: HookCall) {
+        self.hook_calls.lock().unwrap().push(hook_call);
+    }
+}
+
+/// Writes each hook call to the log, at trace level.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HookCallLogger;
+
+impl HookCallSink for HookCallLogger {
+    fn record(&self, hook_call: HookCall) {
+        trace!("VM hook call: {hook_call}");
+    }
+}
+
+/// Forwards every hook to another `VMHooks`, and records each call into a sink.
+///
+/// Wrap the hooks before passing them to `ExecutorService::new_executor` to trace the hooks
+/// called by all the instances of the executor.
+#[derive(Debug)]
+pub struct TracingVMHooks<H: VMHooks> {
+    pub(crate) inner: H,
+    sink: Box<dyn HookCallSink>,
+}
+
+impl<H: VMHooks> TracingVMHooks<H> {
+    pub fn new(inner: H, sink: Box<dyn HookCallSink>) -> Self {
+        Self { inner, sink }
+    }
+
+    pub(crate) fn trace_call<R: HookResult>(
+        &self,
+        name: &'static str,
+        arguments: Vec<HookArgument>,
+        call: impl FnOnce() -> R,
+    ) -> R {
+        let memory_before = if self.sink.records_memory_writes() {
+            with_current_instance(memory_copy).flatten()
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+        let result = call();
+        let duration = start.elapsed();
+
+        let memory_writes = memory_before
+            .and_then(|memory_before| {
+                with_current_instance(memory_copy)
+                    .flatten()
+                    .map(|memory_after| memory_writes(&memory_before, &memory_after))
+            })
+            .unwrap_or_default();
+        self.sink.record(HookCall {
+            name,
+            arguments,
+            result: result.hook_value(),
+            duration,
+            memory_writes,
+        });
+        result
+    }
+}
@@ -0,0 +1,236 @@
+use klever_chain_vm_executor::{InMemoryContext, InMemoryVMHooks, VMHooks, VMHooksDefault};
+use num_bigint::BigInt;
+use serde::Deserialize;
+
+/// Shared with the node, see the description in the file, and what it says of the pow cases.
+const CONFORMANCE_CASES: &str = include_str!("big_float_conformance.json");
+
+/// Where the hooks that take a destination write their result.
+const DESTINATION_HANDLE: i32 = 100;
+
+#[derive(Deserialize)]
+struct Conformance {
+    cases: Vec<Case>,
+}
+
+#[derive(Deserialize)]
+struct Case {
+    name: String,
+    hook: String,
+    arguments: Vec<Argument>,
+    expected: Outcome,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Argument {
+    Float(String),
+    Int(i64),
+    BigInt(String),
+    Bytes(String),
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Outcome {
+    Float(String),
+    Int(i64),
+    BigInt(String),
+    Fails,
+}
+
+enum HookOutput {
+    Float(i32),
+    BigInt(i32),
+    Int(i32),
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// The big float, int and buffer arguments are passed as contract arguments, then read into
+/// handles. The int arguments are passed as they are.
+fn argument_values(hooks: &InMemoryVMHooks, context: &InMemoryContext, case: &Case) -> Vec<i64> {
+    let arguments = case.arguments.iter().map(|argument| match argument {
+        Argument::Float(hex) | Argument::Bytes(hex) => from_hex(hex),
+        Argument::BigInt(decimal) => decimal.parse::<BigInt>().unwrap().to_signed_bytes_be(),
+        Argument::Int(_) => Vec::new(),
+    });
+    context.set_arguments(arguments.collect());
+
+    let mut values = Vec::new();
+    for (id, argument) in case.arguments.iter().enumerate() {
+        let id = id as i32;
+        let value = match argument {
+            Argument::Float(_) => {
+                let buffer = hooks.mbuffer_new();
+                hooks.mbuffer_get_argument(id, buffer);
+                hooks.mbuffer_to_big_float(buffer, id);
+                id
+            }
+            Argument::BigInt(_) => {
+                let big_int = hooks.big_int_new(0);
+                hooks.big_int_get_signed_argument(id, big_int);
+                big_int
+            }
+            Argument::Bytes(_) => {
+                let buffer = hooks.mbuffer_new();
+                hooks.mbuffer_get_argument(id, buffer);
+                buffer
+            }
+            Argument::Int(value) => {
+                values.push(*value);
+                continue;
+            }
+        };
+        values.push(value as i64);
+    }
+    assert_eq!(context.error(), None, "invalid arguments in {}", case.name);
+    values
+}
+
+fn run_case(case: &Case) -> Outcome {
+    let context = InMemoryContext::new();
+    let hooks = InMemoryVMHooks::new(VMHooksDefault, context.clone());
+    let values = argument_values(&hooks, &context, case);
+    let arg = |i: usize| values[i] as i32;
+    let destination = DESTINATION_HANDLE;
+
+    let result = match case.hook.as_str() {
+        "big_float_new_from_parts" => {
+            HookOutput::Float(hooks.big_float_new_from_parts(arg(0), arg(1), arg(2)))
+        }
+        "big_float_new_from_frac" => {
+            HookOutput::Float(hooks.big_float_new_from_frac(values[0], values[1]))
+        }
+        "big_float_new_from_sci" => {
+            HookOutput::Float(hooks.big_float_new_from_sci(values[0], values[1]))
+        }
+        "big_float_add" => {
+            hooks.big_float_add(destination, arg(0), arg(1));
+            HookOutput::Float(destination)
+        }
+        "big_float_sub" => {
+            hooks.big_float_sub(destination, arg(0), arg(1));
+            HookOutput::Float(destination)
+        }
+        "big_float_mul" => {
+            hooks.big_float_mul(destination, arg(0), arg(1));
+            HookOutput::Float(destination)
+        }
+        "big_float_div" => {
+            hooks.big_float_div(destination, arg(0), arg(1));
+            HookOutput::Float(destination)
+        }
+        "big_float_neg" => {
+            hooks.big_float_neg(destination, arg(0));
+            HookOutput::Float(destination)
+        }
+        "big_float_clone" => {
+            hooks.big_float_clone(destination, arg(0));
+            HookOutput::Float(destination)
+        }
+        "big_float_abs" => {
+            hooks.big_float_abs(destination, arg(0));
+            HookOutput::Float(destination)
+        }
+        "big_float_sqrt" => {
+            hooks.big_float_sqrt(destination, arg(0));
+            HookOutput::Float(destination)
+        }
+        "big_float_pow" => {
+            hooks.big_float_pow(destination, arg(0), arg(1));
+            HookOutput::Float(destination)
+        }
+        "big_float_set_int64" => {
+            hooks.big_float_set_int64(destination, values[0]);
+            HookOutput::Float(destination)
+        }
+        "big_float_set_big_int" => {
+            hooks.big_float_set_big_int(destination, arg(0));
+            HookOutput::Float(destination)
+        }
+        "big_float_get_const_pi" => {
+            hooks.big_float_get_const_pi(destination);
+            HookOutput::Float(destination)
+        }
+        "big_float_get_const_e" => {
+            hooks.big_float_get_const_e(destination);
+            HookOutput::Float(destination)
+        }
+        "mbuffer_to_big_float" => {
+            hooks.mbuffer_to_big_float(arg(0), destination);
+            HookOutput::Float(destination)
+        }
+        "big_float_floor" => {
+            hooks.big_float_floor(destination, arg(0));
+            HookOutput::BigInt(destination)
+        }
+        "big_float_ceil" => {
+            hooks.big_float_ceil(destination, arg(0));
+            HookOutput::BigInt(destination)
+        }
+        "big_float_truncate" => {
+            hooks.big_float_truncate(destination, arg(0));
+            HookOutput::BigInt(destination)
+        }
+        "big_float_cmp" => HookOutput::Int(hooks.big_float_cmp(arg(0), arg(1))),
+        "big_float_sign" => HookOutput::Int(hooks.big_float_sign(arg(0))),
+        "big_float_is_int" => HookOutput::Int(hooks.big_float_is_int(arg(0))),
+        hook => panic!("no conformance runner for {hook}"),
+    };
+    if context.error().is_some() {
+        return Outcome::Fails;
+    }
+
+    let buffer = hooks.mbuffer_new();
+    match result {
+        HookOutput::Float(handle) => {
+            hooks.mbuffer_from_big_float(buffer, handle);
+            Outcome::Float(to_hex(&context.managed_buffer(buffer).unwrap()))
+        }
+        HookOutput::BigInt(handle) => {
+            hooks.big_int_to_string(handle, buffer);
+            Outcome::BigInt(String::from_utf8(context.managed_buffer(buffer).unwrap()).unwrap())
+        }
+        HookOutput::Int(value) => Outcome::Int(value as i64),
+    }
+}
+
+#[test]
+fn in_memory_big_floats_pass_the_conformance_cases() {
+    let conformance: Conformance = serde_json::from_str(CONFORMANCE_CASES).unwrap();
+    let mismatches: Vec<String> = conformance
+        .cases
+        .iter()
+        .filter_map(|case| {
+            let outcome = run_case(case);
+            (outcome != case.expected).then(|| {
+                format!(
+                    "{}: expected {:?}, got {outcome:?}",
+                    case.name, case.expected
+                )
+            })
+        })
+        .collect();
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}
+
+#[test]
+fn big_float_hooks_fail_on_unknown_handles() {
+    let context = InMemoryContext::new();
+    let hooks = InMemoryVMHooks::new(VMHooksDefault, context.clone());
+    assert_eq!(hooks.big_float_sign(7), -2);
+    assert_eq!(
+        context.error().unwrap(),
+        "no big float under the given handle"
+    );
+}
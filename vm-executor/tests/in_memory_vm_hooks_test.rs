@@ -0,0 +1,202 @@
+use klever_chain_vm_executor::{InMemoryContext, InMemoryVMHooks, VMHooks, VMHooksDefault};
+
+fn in_memory_hooks() -> (InMemoryVMHooks, InMemoryContext) {
+    let context = InMemoryContext::new();
+    (
+        InMemoryVMHooks::new(VMHooksDefault, context.clone()),
+        context,
+    )
+}
+
+fn big_int_string(hooks: &InMemoryVMHooks, context: &InMemoryContext, handle: i32) -> String {
+    let decimal = hooks.mbuffer_new();
+    hooks.big_int_to_string(handle, decimal);
+    String::from_utf8(context.managed_buffer(decimal).unwrap()).unwrap()
+}
+
+#[test]
+fn division_rounds_by_kind() {
+    let (hooks, context) = in_memory_hooks();
+    let cases = [
+        (7, 2, ["3", "1", "3", "1"]),
+        (-7, 2, ["-3", "-1", "-4", "1"]),
+        (7, -2, ["-3", "1", "-3", "1"]),
+        (-7, -2, ["3", "-1", "4", "1"]),
+    ];
+    for (a, b, [tdiv, tmod, ediv, emod]) in cases {
+        let (a, b, result) = (
+            hooks.big_int_new(a),
+            hooks.big_int_new(b),
+            hooks.big_int_new(0),
+        );
+        hooks.big_int_tdiv(result, a, b);
+        assert_eq!(big_int_string(&hooks, &context, result), tdiv);
+        hooks.big_int_tmod(result, a, b);
+        assert_eq!(big_int_string(&hooks, &context, result), tmod);
+        hooks.big_int_ediv(result, a, b);
+        assert_eq!(big_int_string(&hooks, &context, result), ediv);
+        hooks.big_int_emod(result, a, b);
+        assert_eq!(big_int_string(&hooks, &context, result), emod);
+    }
+    assert_eq!(context.error(), None);
+}
+
+#[test]
+fn sqrt_pow_and_log2() {
+    let (hooks, context) = in_memory_hooks();
+    let result = hooks.big_int_new(0);
+
+    hooks.big_int_pow(result, hooks.big_int_new(2), hooks.big_int_new(100));
+    assert_eq!(
+        big_int_string(&hooks, &context, result),
+        "1267650600228229401496703205376"
+    );
+    assert_eq!(hooks.big_int_log2(result), 100);
+
+    hooks.big_int_sqrt(result, hooks.big_int_new(1_000_000_007));
+    assert_eq!(big_int_string(&hooks, &context, result), "31622");
+
+    assert_eq!(hooks.big_int_log2(hooks.big_int_new(1025)), 10);
+    assert_eq!(hooks.big_int_log2(hooks.big_int_new(1)), 0);
+    assert_eq!(hooks.big_int_log2(hooks.big_int_new(0)), -1);
+    assert_eq!(context.error(), None);
+}
+
+#[test]
+fn shifts_and_bitwise_operations() {
+    let (hooks, context) = in_memory_hooks();
+    let (twelve, ten, result) = (
+        hooks.big_int_new(12),
+        hooks.big_int_new(10),
+        hooks.big_int_new(0),
+    );
+
+    hooks.big_int_and(result, twelve, ten);
+    assert_eq!(hooks.big_int_get_int64(result), 8);
+    hooks.big_int_or(result, twelve, ten);
+    assert_eq!(hooks.big_int_get_int64(result), 14);
+    hooks.big_int_xor(result, twelve, ten);
+    assert_eq!(hooks.big_int_get_int64(result), 6);
+    hooks.big_int_not(result, ten);
+    assert_eq!(hooks.big_int_get_int64(result), -11);
+
+    hooks.big_int_shl(result, hooks.big_int_new(3), 100);
+    assert_eq!(hooks.big_int_is_int64(result), 0);
+    hooks.big_int_shr(result, result, 99);
+    assert_eq!(hooks.big_int_get_int64(result), 6);
+    assert_eq!(context.error(), None);
+}
+
+#[test]
+fn arguments_and_finish_use_signed_and_unsigned_encodings() {
+    let (hooks, context) = in_memory_hooks();
+    context.set_arguments(vec![vec![0xff], vec![0x00, 0x80], vec![]]);
+
+    let (signed, unsigned) = (hooks.big_int_new(0), hooks.big_int_new(0));
+    hooks.big_int_get_signed_argument(0, signed);
+    hooks.big_int_get_unsigned_argument(0, unsigned);
+    assert_eq!(hooks.big_int_get_int64(signed), -1);
+    assert_eq!(hooks.big_int_get_int64(unsigned), 255);
+    assert_eq!(hooks.small_int_get_signed_argument(1), 128);
+    assert_eq!(hooks.small_int_get_unsigned_argument(2), 0);
+
+    hooks.big_int_finish_signed(signed);
+    hooks.big_int_finish_unsigned(signed);
+    hooks.big_int_finish_signed(unsigned);
+    hooks.small_int_finish_signed(-128);
+    hooks.small_int_finish_unsigned(-1);
+    hooks.int64finish(0);
+    assert_eq!(
+        context.return_data(),
+        vec![
+            vec![0xff],
+            vec![0x01],
+            vec![0x00, 0xff],
+            vec![0x80],
+            vec![0xff; 8],
+            vec![],
+        ]
+    );
+    assert_eq!(context.error(), None);
+}
+
+#[test]
+fn managed_buffers_and_storage() {
+    let (hooks, context) = in_memory_hooks();
+    context.set_arguments(vec![
+        b"counter".to_vec(),
+        vec![0xca, 0xfe, 0x01],
+        b"owner".to_vec(),
+    ]);
+    context.set_storage(b"owner", b"alice");
+
+    let (key, value) = (hooks.mbuffer_new(), hooks.mbuffer_new());
+    assert_eq!(hooks.mbuffer_get_argument(0, key), 0);
+    assert_eq!(hooks.mbuffer_get_argument(1, value), 0);
+    assert_eq!(hooks.mbuffer_get_length(value), 3);
+    assert_eq!(hooks.mbuffer_storage_store(key, value), 0);
+    assert_eq!(context.storage(b"counter"), vec![0xca, 0xfe, 0x01]);
+
+    let hex = hooks.mbuffer_new();
+    hooks.managed_buffer_to_hex(value, hex);
+    assert_eq!(context.managed_buffer(hex).unwrap(), b"cafe01");
+
+    let slice = hooks.mbuffer_new();
+    assert_eq!(hooks.mbuffer_copy_byte_slice(value, 1, 2, slice), 0);
+    assert_eq!(context.managed_buffer(slice).unwrap(), vec![0xfe, 0x01]);
+    // Out of bounds slices are refused, but do not fail the execution
+    assert_eq!(hooks.mbuffer_copy_byte_slice(value, 2, 2, slice), 1);
+    assert_eq!(hooks.mbuffer_append(slice, hex), 0);
+    assert_eq!(context.managed_buffer(slice).unwrap(), b"\xfe\x01cafe01");
+
+    let (owner_key, owner) = (hooks.mbuffer_new(), hooks.mbuffer_new());
+    hooks.mbuffer_get_argument(2, owner_key);
+    assert_eq!(hooks.mbuffer_storage_load(owner_key, owner), 0);
+    assert_eq!(context.managed_buffer(owner).unwrap(), b"alice");
+    assert_eq!(hooks.mbuffer_storage_load(value, owner), 0);
+    assert_eq!(hooks.mbuffer_eq(owner, hooks.mbuffer_new()), 1);
+    assert_eq!(context.error(), None);
+}
+
+#[test]
+fn random_bytes_only_depend_on_the_seed() {
+    let random_bytes = |seed| {
+        let (hooks, context) = in_memory_hooks();
+        context.set_random_seed(seed);
+        let random = hooks.mbuffer_new();
+        assert_eq!(hooks.mbuffer_set_random(random, 20), 0);
+        context.managed_buffer(random).unwrap()
+    };
+    assert_eq!(random_bytes(7).len(), 20);
+    assert_eq!(random_bytes(7), random_bytes(7));
+    assert_ne!(random_bytes(7), random_bytes(8));
+}
+
+#[test]
+fn failed_hooks_keep_the_first_error() {
+    let (hooks, context) = in_memory_hooks();
+    let (zero, result) = (hooks.big_int_new(0), hooks.big_int_new(0));
+
+    hooks.big_int_tdiv(result, hooks.big_int_new(1), zero);
+    hooks.big_int_not(result, hooks.big_int_new(-1));
+    assert_eq!(context.error().unwrap(), "division by zero");
+
+    let (hooks, context) = in_memory_hooks();
+    hooks.big_int_not(hooks.big_int_new(0), hooks.big_int_new(-1));
+    assert_eq!(
+        context.error().unwrap(),
+        "bitwise operation on a negative number"
+    );
+
+    let (hooks, context) = in_memory_hooks();
+    assert_eq!(hooks.mbuffer_get_length(1000), -1);
+    assert_eq!(
+        context.error().unwrap(),
+        "no managed buffer under the given handle"
+    );
+
+    // Memory can only be reached during a call
+    let (hooks, context) = in_memory_hooks();
+    assert_eq!(hooks.mbuffer_get_bytes(hooks.mbuffer_new(), 0), -1);
+    assert_eq!(context.error().unwrap(), "no instance is being called");
+}
@@ -0,0 +1,96 @@
+use klever_chain_vm_executor::{OpcodeCost, OpcodeCostChange, ScheduleError};
+
+fn test_schedule() -> OpcodeCost {
+    OpcodeCost {
+        opcode_block: 1,
+        opcode_br: 2,
+        opcode_brif: 3,
+        opcode_brtable: 4,
+        opcode_call: 5,
+        opcode_callindirect: 6,
+        opcode_else: 1,
+        opcode_end: 1,
+        opcode_if: 2,
+        opcode_loop: 1,
+        opcode_return: 2,
+        opcode_unreachable: 1,
+        opcode_i32add: 1,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn toml_roundtrip() {
+    let schedule = toml::to_string(&test_schedule()).unwrap();
+    assert_eq!(OpcodeCost::from_toml(&schedule).unwrap(), test_schedule());
+}
+
+#[test]
+fn json_roundtrip() {
+    let schedule = serde_json::to_string(&test_schedule()).unwrap();
+    assert_eq!(OpcodeCost::from_json(&schedule).unwrap(), test_schedule());
+}
+
+#[test]
+fn unknown_and_missing_opcodes_are_rejected() {
+    let schedule = toml::to_string(&test_schedule()).unwrap();
+
+    let unknown = format!("{schedule}opcode_i32teleport = 1\n");
+    assert!(matches!(
+        OpcodeCost::from_toml(&unknown),
+        Err(ScheduleError::Toml(message)) if message.contains("opcode_i32teleport")
+    ));
+
+    let missing = schedule.replace("opcode_brif = 3\n", "");
+    assert!(matches!(
+        OpcodeCost::from_toml(&missing),
+        Err(ScheduleError::Toml(message)) if message.contains("opcode_brif")
+    ));
+
+    assert!(matches!(
+        OpcodeCost::from_json(r#"{"opcode_block": 1}"#),
+        Err(ScheduleError::Json(_))
+    ));
+}
+
+#[test]
+fn zero_cost_control_flow_opcodes() {
+    assert!(test_schedule().zero_cost_control_flow_opcodes().is_empty());
+
+    let free_loops = OpcodeCost {
+        opcode_loop: 0,
+        opcode_br: 0,
+        ..test_schedule()
+    };
+    assert_eq!(
+        free_loops.zero_cost_control_flow_opcodes(),
+        vec!["opcode_br", "opcode_loop"]
+    );
+}
+
+#[test]
+fn diff_lists_changed_opcodes() {
+    let old = test_schedule();
+    assert!(old.diff(&old).is_empty());
+
+    let new = OpcodeCost {
+        opcode_call: 7,
+        opcode_i64add: 1,
+        ..test_schedule()
+    };
+    assert_eq!(
+        old.diff(&new),
+        vec![
+            OpcodeCostChange {
+                opcode: "opcode_call".to_string(),
+                old: 5,
+                new: 7,
+            },
+            OpcodeCostChange {
+                opcode: "opcode_i64add".to_string(),
+                old: 0,
+                new: 1,
+            },
+        ]
+    );
+}
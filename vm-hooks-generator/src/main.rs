@@ -0,0 +1,234 @@
+//! Generates the `VMHooks` wrappers of the executor from the `VMHooks` trait:
+//! `vm_hooks_tracing.rs` and `vm_hooks_replay.rs`.
+//!
+//! `vm_hooks.rs` comes from the vmhooks generator of the VM. Every hook it declares is wrapped
+//! the same way, so the wrappers are generated from it rather than kept in sync by hand.
+//! Run `cargo run -p klever-chain-vm-hooks-generator` after `vm_hooks.rs` changes.
+
+use std::{fs, path::PathBuf};
+
+const GENERATED_BANNER: &str = "// Code generated by vmhooks generator. DO NOT EDIT.
+
+// !!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!
+// !!!!!!!!!!!!!!!!!!!!!! AUTO-GENERATED FILE !!!!!!!!!!!!!!!!!!!!!!
+// !!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!
+";
+
+/// Set by the host when the hooks are created, it is not a hook call.
+const SET_VM_HOOKS_PTR: &str = "set_vm_hooks_ptr";
+
+struct Param {
+    name: String,
+    ty: String,
+}
+
+struct Hook {
+    name: String,
+    params: Vec<Param>,
+    result: Option<String>,
+}
+
+impl Hook {
+    fn signature(&self) -> String {
+        let mut signature = format!("fn {}(&self", self.name);
+        for param in &self.params {
+            signature.push_str(&format!(", {}: {}", param.name, param.ty));
+        }
+        signature.push(')');
+        if let Some(result) = &self.result {
+            signature.push_str(&format!(" -> {result}"));
+        }
+        signature
+    }
+
+    fn param_names(&self) -> String {
+        let names: Vec<&str> = self
+            .params
+            .iter()
+            .map(|param| param.name.as_str())
+            .collect();
+        names.join(", ")
+    }
+}
+
+/// The `HookValue` variant of the argument and result types of the hooks.
+fn hook_value_variant(ty: &str) -> &'static str {
+    match ty {
+        "i32" => "I32",
+        "i64" => "I64",
+        "MemPtr" => "MemPtr",
+        "MemLength" => "MemLength",
+        _ => panic!("no HookValue for VM hook type {ty}"),
+    }
+}
+
+fn parse_hook(declaration: &str) -> Hook {
+    let declaration = declaration
+        .strip_prefix("fn ")
+        .and_then(|declaration| declaration.strip_suffix(';'))
+        .unwrap_or_else(|| panic!("unexpected VM hook declaration: {declaration}"));
+    let (name, rest) = declaration.split_once('(').unwrap();
+    let (params, result) = rest.split_once(')').unwrap();
+
+    let params = params
+        .split(", ")
+        .skip(1)
+        .map(|param| {
+            let (name, ty) = param.split_once(": ").unwrap();
+            hook_value_variant(ty);
+            Param {
+                name: name.to_string(),
+                ty: ty.to_string(),
+            }
+        })
+        .collect();
+    let result = result.strip_prefix(" -> ").map(|result| {
+        hook_value_variant(result);
+        result.to_string()
+    });
+
+    Hook {
+        name: name.to_string(),
+        params,
+        result,
+    }
+}
+
+/// Reads the hooks declared by the `VMHooks` trait, in order.
+fn parse_hooks(vm_hooks: &str) -> Vec<Hook> {
+    vm_hooks
+        .lines()
+        .skip_while(|line| !line.starts_with("pub trait VMHooks"))
+        .skip(1)
+        .take_while(|line| *line != "}")
+        .map(str::trim)
+        .filter(|line| {
+            line.starts_with("fn ") && !line.starts_with(&format!("fn {SET_VM_HOOKS_PTR}("))
+        })
+        .map(parse_hook)
+        .collect()
+}
+
+/// Writes the `arguments` vector of a hook call, and returns the expression to pass it.
+fn push_arguments(
+    code: &mut String,
+    hook: &Hook,
+    argument: impl Fn(&Param) -> String,
+) -> &'static str {
+    if hook.params.is_empty() {
+        return "Vec::new()";
+    }
+    code.push_str("        let arguments = vec![\n");
+    for param in &hook.params {
+        code.push_str(&format!("            {},\n", argument(param)));
+    }
+    code.push_str("        ];\n");
+    "arguments"
+}
+
+fn generate_tracing(hooks: &[Hook]) -> String {
+    let mut code = format!(
+        "{GENERATED_BANNER}
+use std::ffi::c_void;
+
+use crate::{{HookArgument, HookValue, MemLength, MemPtr, TracingVMHooks, VMHooks}};
+
+#[rustfmt::skip]
+impl<H: VMHooks> VMHooks for TracingVMHooks<H> {{
+    fn set_vm_hooks_ptr(&mut self, vm_hooks_ptr: *mut c_void) {{
+        self.inner.set_vm_hooks_ptr(vm_hooks_ptr);
+    }}
+"
+    );
+    for hook in hooks {
+        code.push_str(&format!("\n    {} {{\n", hook.signature()));
+        let arguments = push_arguments(&mut code, hook, |param| {
+            format!(
+                "HookArgument::new(\"{}\", HookValue::{}({}))",
+                param.name,
+                hook_value_variant(&param.ty),
+                param.name
+            )
+        });
+        code.push_str(&format!(
+            "        self.trace_call(\"{}\", {arguments}, || self.inner.{}({}))\n    }}\n",
+            hook.name,
+            hook.name,
+            hook.param_names()
+        ));
+    }
+    code.push_str("}\n");
+    code
+}
+
+fn generate_replay(hooks: &[Hook]) -> String {
+    let mut code = format!(
+        "{GENERATED_BANNER}
+use std::ffi::c_void;
+
+use crate::{{HookValue, MemLength, MemPtr, ReplayVMHooks, VMHooks}};
+
+#[rustfmt::skip]
+impl VMHooks for ReplayVMHooks {{
+    fn set_vm_hooks_ptr(&mut self, _vm_hooks_ptr: *mut c_void) {{
+    }}
+"
+    );
+    for hook in hooks {
+        code.push_str(&format!("\n    {} {{\n", hook.signature()));
+        let arguments = push_arguments(&mut code, hook, |param| {
+            format!(
+                "HookValue::{}({})",
+                hook_value_variant(&param.ty),
+                param.name
+            )
+        });
+        code.push_str(&format!(
+            "        self.replay_call(\"{}\", {arguments})\n    }}\n",
+            hook.name
+        ));
+    }
+    code.push_str("}\n");
+    code
+}
+
+fn executor_src_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../vm-executor/src")
+}
+
+fn main() {
+    let src_dir = executor_src_dir();
+    let vm_hooks = fs::read_to_string(src_dir.join("vm_hooks.rs")).unwrap();
+    let hooks = parse_hooks(&vm_hooks);
+
+    fs::write(
+        src_dir.join("vm_hooks_tracing.rs"),
+        generate_tracing(&hooks),
+    )
+    .unwrap();
+    fs::write(src_dir.join("vm_hooks_replay.rs"), generate_replay(&hooks)).unwrap();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generated_files_are_up_to_date() {
+        let src_dir = executor_src_dir();
+        let vm_hooks = fs::read_to_string(src_dir.join("vm_hooks.rs")).unwrap();
+        let hooks = parse_hooks(&vm_hooks);
+
+        let tracing = fs::read_to_string(src_dir.join("vm_hooks_tracing.rs")).unwrap();
+        assert!(
+            tracing == generate_tracing(&hooks),
+            "vm_hooks_tracing.rs is out of date, run the vm hooks generator"
+        );
+
+        let replay = fs::read_to_string(src_dir.join("vm_hooks_replay.rs")).unwrap();
+        assert!(
+            replay == generate_replay(&hooks),
+            "vm_hooks_replay.rs is out of date, run the vm hooks generator"
+        );
+    }
+}